@@ -0,0 +1,531 @@
+//! Derive macros for [generic-std](https://crates.io/crates/generic-std).
+//!
+//! `Sequence`, `SequenceMut` and `WithCapacity` implement traits for newtypes
+//! by delegating to an inner field. The field is either the only field of the
+//! struct or the one marked with `#[delegate]`.
+//!
+//! `HktClone`, `HktDebug` and `HktPartialEq` are replacements for the std
+//! derives that work on types parameterized by HKT forms. The std derives
+//! bound every type parameter, which fails for forms, while these bound the
+//! field types instead, such as `<K as PlugType<String>>::T: Clone`.
+//!
+//! Use them through the `derive` feature of `generic-std`.
+
+use proc_macro::TokenStream;
+use proc_macro2::{TokenStream as TokenStream2, TokenTree};
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, parse_quote, spanned::Spanned, Data, DeriveInput, Error,
+    Fields, GenericParam, Generics, Ident, Member, Type,
+};
+
+/// Derive `Sequence<T>` for all `T` the delegated field is a sequence of.
+#[proc_macro_derive(Sequence, attributes(delegate))]
+pub fn derive_sequence(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_sequence(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Derive `SequenceMut<T>` for all `T` the delegated field is a sequence of.
+#[proc_macro_derive(SequenceMut, attributes(delegate))]
+pub fn derive_sequence_mut(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_sequence_mut(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Derive `WithCapacity`. Fields other than the delegated one are initialized
+/// with `Default::default()`.
+#[proc_macro_derive(WithCapacity, attributes(delegate))]
+pub fn derive_with_capacity(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_with_capacity(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Derive `Clone`, bounding field types instead of type parameters.
+#[proc_macro_derive(HktClone)]
+pub fn derive_hkt_clone(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_hkt_clone(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Derive `Debug`, bounding field types instead of type parameters.
+#[proc_macro_derive(HktDebug)]
+pub fn derive_hkt_debug(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_hkt_debug(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Derive `PartialEq`, bounding field types instead of type parameters.
+#[proc_macro_derive(HktPartialEq)]
+pub fn derive_hkt_partial_eq(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_hkt_partial_eq(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_sequence(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let (member, ty) = delegate(input)?;
+    let name = &input.ident;
+    let generics = with_item_param(&input.generics, &ty, quote!(Sequence));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::generic_std::Sequence<__Item>
+            for #name #ty_generics #where_clause
+        {
+            type H1Iterator =
+                <#ty as ::generic_std::Sequence<__Item>>::H1Iterator;
+
+            fn len(&self) -> usize {
+                <#ty as ::generic_std::Sequence<__Item>>::len(&self.#member)
+            }
+
+            fn is_empty(&self) -> bool {
+                <#ty as ::generic_std::Sequence<__Item>>::is_empty(
+                    &self.#member,
+                )
+            }
+
+            fn contains(&self, x: &__Item) -> bool
+            where
+                __Item: PartialEq,
+            {
+                <#ty as ::generic_std::Sequence<__Item>>::contains(
+                    &self.#member,
+                    x,
+                )
+            }
+
+            fn get(&self, index: usize) -> Option<&__Item> {
+                <#ty as ::generic_std::Sequence<__Item>>::get(
+                    &self.#member,
+                    index,
+                )
+            }
+
+            fn first(&self) -> Option<&__Item> {
+                <#ty as ::generic_std::Sequence<__Item>>::first(&self.#member)
+            }
+
+            fn last(&self) -> Option<&__Item> {
+                <#ty as ::generic_std::Sequence<__Item>>::last(&self.#member)
+            }
+
+            fn iter<'a>(
+                &'a self,
+            ) -> <Self::H1Iterator as ::generic_std::plug::PlugLifetime<'a>>::T
+            where
+                <Self::H1Iterator as ::generic_std::plug::PlugLifetime<'a>>::T:
+                    ::generic_std::StreamingIterator,
+            {
+                <#ty as ::generic_std::Sequence<__Item>>::iter(&self.#member)
+            }
+        }
+    })
+}
+
+fn expand_sequence_mut(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let (member, ty) = delegate(input)?;
+    let name = &input.ident;
+    let generics = with_item_param(&input.generics, &ty, quote!(SequenceMut));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let delegated = quote!(<#ty as ::generic_std::SequenceMut<__Item>>);
+
+    Ok(quote! {
+        impl #impl_generics ::generic_std::SequenceMut<__Item>
+            for #name #ty_generics #where_clause
+        {
+            fn capacity(&self) -> usize {
+                #delegated::capacity(&self.#member)
+            }
+
+            fn clear(&mut self) {
+                #delegated::clear(&mut self.#member)
+            }
+
+            fn reserve(&mut self, additional: usize) {
+                #delegated::reserve(&mut self.#member, additional)
+            }
+
+            fn reserve_exact(&mut self, additional: usize) {
+                #delegated::reserve_exact(&mut self.#member, additional)
+            }
+
+            fn shrink_to_fit(&mut self) {
+                #delegated::shrink_to_fit(&mut self.#member)
+            }
+
+            fn push(&mut self, x: __Item) {
+                #delegated::push(&mut self.#member, x)
+            }
+
+            fn pop(&mut self) -> Option<__Item> {
+                #delegated::pop(&mut self.#member)
+            }
+
+            fn insert(&mut self, index: usize, x: __Item) {
+                #delegated::insert(&mut self.#member, index, x)
+            }
+
+            fn remove(&mut self, index: usize) -> __Item {
+                #delegated::remove(&mut self.#member, index)
+            }
+        }
+    })
+}
+
+fn expand_with_capacity(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let (member, ty) = delegate(input)?;
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#ty: ::generic_std::WithCapacity));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let fields = fields(input)?.iter().enumerate().map(|(i, field)| {
+        let field_member = field
+            .ident
+            .clone()
+            .map(Member::Named)
+            .unwrap_or_else(|| Member::Unnamed(i.into()));
+        if field_member == member {
+            quote! {
+                #field_member:
+                    <#ty as ::generic_std::WithCapacity>::with_capacity(
+                        capacity,
+                    )
+            }
+        } else {
+            quote!(#field_member: ::core::default::Default::default())
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::generic_std::WithCapacity
+            for #name #ty_generics #where_clause
+        {
+            fn with_capacity(capacity: usize) -> Self {
+                Self { #(#fields),* }
+            }
+        }
+    })
+}
+
+fn expand_hkt_clone(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let name = &input.ident;
+    let generics = with_field_bounds(input, quote!(::core::clone::Clone))?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let arms = variants(input)?.into_iter().map(|variant| {
+        let pattern = variant.pattern("__self");
+        let constructor = &variant.constructor;
+        let clones = variant.members.iter().zip(variant.bindings("__self")).map(
+            |(member, binding)| {
+                quote!(#member: ::core::clone::Clone::clone(#binding))
+            },
+        );
+
+        quote!(#pattern => #constructor { #(#clones),* })
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::core::clone::Clone
+            for #name #ty_generics #where_clause
+        {
+            fn clone(&self) -> Self {
+                match self {
+                    #(#arms,)*
+                }
+            }
+        }
+    })
+}
+
+fn expand_hkt_debug(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let name = &input.ident;
+    let generics = with_field_bounds(input, quote!(::core::fmt::Debug))?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let arms = variants(input)?.into_iter().map(|variant| {
+        let pattern = variant.pattern("__self");
+        let label = variant.label.to_string();
+        let bindings = variant.bindings("__self");
+        let body = match variant.fields {
+            Fields::Named(_) => {
+                let names = variant.members.iter().map(|member| match member {
+                    Member::Named(ident) => ident.to_string(),
+                    Member::Unnamed(index) => index.index.to_string(),
+                });
+
+                quote! {
+                    f.debug_struct(#label)
+                        #(.field(#names, #bindings))*
+                        .finish()
+                }
+            }
+            Fields::Unnamed(_) => quote! {
+                f.debug_tuple(#label)#(.field(#bindings))*.finish()
+            },
+            Fields::Unit => quote!(f.write_str(#label)),
+        };
+
+        quote!(#pattern => #body)
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::core::fmt::Debug
+            for #name #ty_generics #where_clause
+        {
+            fn fmt(
+                &self,
+                f: &mut ::core::fmt::Formatter<'_>,
+            ) -> ::core::fmt::Result {
+                match self {
+                    #(#arms,)*
+                }
+            }
+        }
+    })
+}
+
+fn expand_hkt_partial_eq(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let name = &input.ident;
+    let generics = with_field_bounds(input, quote!(::core::cmp::PartialEq))?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let variants = variants(input)?;
+    let multiple = variants.len() > 1;
+    let arms = variants.into_iter().map(|variant| {
+        let self_pattern = variant.pattern("__self");
+        let other_pattern = variant.pattern("__other");
+        let comparisons = variant
+            .bindings("__self")
+            .into_iter()
+            .zip(variant.bindings("__other"))
+            .map(|(x, y)| quote!(::core::cmp::PartialEq::eq(#x, #y)));
+
+        quote! {
+            (#self_pattern, #other_pattern) => true #(&& #comparisons)*
+        }
+    });
+    let fallback = if multiple {
+        quote!(_ => false,)
+    } else {
+        quote!()
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::core::cmp::PartialEq
+            for #name #ty_generics #where_clause
+        {
+            fn eq(&self, other: &Self) -> bool {
+                match (self, other) {
+                    #(#arms,)*
+                    #fallback
+                }
+            }
+        }
+    })
+}
+
+/// A struct or an enum variant.
+struct Variant<'a> {
+    /// Path used to construct and match the variant.
+    constructor: TokenStream2,
+    /// Name used in debug output.
+    label: &'a Ident,
+    fields: &'a Fields,
+    members: Vec<Member>,
+}
+
+impl Variant<'_> {
+    fn bindings(&self, prefix: &str) -> Vec<Ident> {
+        (0..self.members.len())
+            .map(|i| format_ident!("{}_{}", prefix, i))
+            .collect()
+    }
+
+    fn pattern(&self, prefix: &str) -> TokenStream2 {
+        let constructor = &self.constructor;
+        let members = &self.members;
+        let bindings = self.bindings(prefix);
+
+        quote!(#constructor { #(#members: #bindings),* })
+    }
+}
+
+fn variants(input: &DeriveInput) -> Result<Vec<Variant<'_>>, Error> {
+    let name = &input.ident;
+    let members = |fields: &Fields| {
+        fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                field
+                    .ident
+                    .clone()
+                    .map(Member::Named)
+                    .unwrap_or_else(|| Member::Unnamed(i.into()))
+            })
+            .collect()
+    };
+
+    match &input.data {
+        Data::Struct(data) => Ok(vec![Variant {
+            constructor: quote!(#name),
+            label: name,
+            fields: &data.fields,
+            members: members(&data.fields),
+        }]),
+        Data::Enum(data) => Ok(data
+            .variants
+            .iter()
+            .map(|variant| {
+                let ident = &variant.ident;
+
+                Variant {
+                    constructor: quote!(#name::#ident),
+                    label: ident,
+                    fields: &variant.fields,
+                    members: members(&variant.fields),
+                }
+            })
+            .collect()),
+        Data::Union(_) => Err(Error::new(
+            input.ident.span(),
+            "HKT derives are not supported for unions",
+        )),
+    }
+}
+
+/// All identifiers in `tokens`, including nested groups.
+fn idents(tokens: TokenStream2) -> Vec<Ident> {
+    tokens
+        .into_iter()
+        .flat_map(|token| match token {
+            TokenTree::Ident(ident) => vec![ident],
+            TokenTree::Group(group) => idents(group.stream()),
+            _ => Vec::new(),
+        })
+        .collect()
+}
+
+/// Bound field types that depend on type parameters by `bound`, leaving the
+/// type parameters themselves unbounded.
+fn with_field_bounds(
+    input: &DeriveInput,
+    bound: TokenStream2,
+) -> Result<Generics, Error> {
+    let mut generics = input.generics.clone();
+    let params = input
+        .generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect::<Vec<_>>();
+    let where_clause = generics.make_where_clause();
+    let mut seen = Vec::new();
+    for variant in variants(input)? {
+        for field in variant.fields {
+            let ty = &field.ty;
+            let idents = idents(quote!(#ty));
+            let key = quote!(#ty).to_string();
+            // Fields of the type being derived are covered by the impl itself
+            // and bounding them would be cyclic. HKT projections such as
+            // `<K as PlugType<Tree<K>>>::T` still need the bound, as they can
+            // resolve to anything
+            let projection =
+                matches!(ty, Type::Path(path) if path.qself.is_some());
+            if idents.iter().any(|ident| params.contains(ident))
+                && (projection || !idents.contains(&input.ident))
+                && !seen.contains(&key)
+            {
+                seen.push(key);
+                where_clause.predicates.push(parse_quote!(#ty: #bound));
+            }
+        }
+    }
+
+    Ok(generics)
+}
+
+/// Add the `__Item` type parameter and bound the delegated field type by
+/// `trait_name<__Item>`.
+fn with_item_param(
+    generics: &Generics,
+    ty: &Type,
+    trait_name: TokenStream2,
+) -> Generics {
+    let mut generics = generics.clone();
+    generics
+        .params
+        .push(GenericParam::Type(parse_quote!(__Item)));
+    generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#ty: ::generic_std::#trait_name<__Item>));
+
+    generics
+}
+
+fn fields(input: &DeriveInput) -> Result<&Fields, Error> {
+    match &input.data {
+        Data::Struct(data) => Ok(&data.fields),
+        _ => Err(Error::new(
+            input.ident.span(),
+            "delegation can only be derived for structs",
+        )),
+    }
+}
+
+/// Find the field to delegate to.
+fn delegate(input: &DeriveInput) -> Result<(Member, Type), Error> {
+    let fields = fields(input)?;
+    let mut marked = fields.iter().enumerate().filter(|(_, field)| {
+        field
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("delegate"))
+    });
+    let (index, field) = match (marked.next(), marked.next()) {
+        (Some(field), None) => field,
+        (Some(_), Some((_, field))) => {
+            return Err(Error::new(
+                field.span(),
+                "only one field can be marked with #[delegate]",
+            ))
+        }
+        (None, _) if fields.len() == 1 => {
+            (0, fields.iter().next().expect("exactly one field"))
+        }
+        (None, _) => {
+            return Err(Error::new(
+                input.ident.span(),
+                "mark the field to delegate to with #[delegate]",
+            ))
+        }
+    };
+    let member = field
+        .ident
+        .clone()
+        .map(Member::Named)
+        .unwrap_or_else(|| Member::Unnamed(index.into()));
+
+    Ok((member, field.ty.clone()))
+}
@@ -0,0 +1,96 @@
+//! Trait impls for the [ahash](https://crates.io/crates/ahash) crate.
+
+use crate::{erased::DynMap, MapFactory};
+use ::ahash::{AHashMap, AHashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::{BuildHasher, Hash},
+};
+
+impl<K, V, S> MapFactory<S> for AHashMap<K, V, S>
+where
+    S: BuildHasher,
+{
+    fn with_hasher(hasher: S) -> Self {
+        AHashMap::<K, V, S>::with_hasher(hasher)
+    }
+
+    fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+        AHashMap::<K, V, S>::with_capacity_and_hasher(capacity, hasher)
+    }
+
+    fn hasher(&self) -> &S {
+        HashMap::<K, V, S>::hasher(self)
+    }
+}
+
+impl<T, S> MapFactory<S> for AHashSet<T, S>
+where
+    S: BuildHasher,
+{
+    fn with_hasher(hasher: S) -> Self {
+        AHashSet::<T, S>::with_hasher(hasher)
+    }
+
+    fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+        AHashSet::<T, S>::with_capacity_and_hasher(capacity, hasher)
+    }
+
+    fn hasher(&self) -> &S {
+        HashSet::<T, S>::hasher(self)
+    }
+}
+
+impl<K, V, S> DynMap<K, V> for AHashMap<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    fn len(&self) -> usize {
+        HashMap::<K, V, S>::len(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        HashMap::<K, V, S>::is_empty(self)
+    }
+
+    fn contains_key(&self, k: &K) -> bool {
+        HashMap::<K, V, S>::contains_key(self, k)
+    }
+
+    fn get(&self, k: &K) -> Option<&V> {
+        HashMap::<K, V, S>::get(self, k)
+    }
+
+    fn get_mut(&mut self, k: &K) -> Option<&mut V> {
+        HashMap::<K, V, S>::get_mut(self, k)
+    }
+
+    fn insert(&mut self, k: K, v: V) -> Option<V> {
+        HashMap::<K, V, S>::insert(self, k, v)
+    }
+
+    fn remove(&mut self, k: &K) -> Option<V> {
+        HashMap::<K, V, S>::remove(self, k)
+    }
+
+    fn clear(&mut self) {
+        HashMap::<K, V, S>::clear(self)
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (&K, &V)> + '_> {
+        Box::new(HashMap::<K, V, S>::iter(self))
+    }
+
+    fn iter_mut(&mut self) -> Box<dyn Iterator<Item = (&K, &mut V)> + '_> {
+        Box::new(HashMap::<K, V, S>::iter_mut(self))
+    }
+
+    fn keys(&self) -> Box<dyn Iterator<Item = &K> + '_> {
+        Box::new(HashMap::<K, V, S>::keys(self))
+    }
+
+    fn values(&self) -> Box<dyn Iterator<Item = &V> + '_> {
+        Box::new(HashMap::<K, V, S>::values(self))
+    }
+}
@@ -0,0 +1,22 @@
+//! Trait impls for the [arrayvec](https://crates.io/crates/arrayvec) crate.
+
+use crate::StringBuilder;
+use ::arrayvec::ArrayString;
+
+impl<const CAP: usize> StringBuilder for ArrayString<CAP> {
+    fn push(&mut self, c: char) {
+        ArrayString::<CAP>::push(self, c)
+    }
+
+    fn push_str(&mut self, s: &str) {
+        ArrayString::<CAP>::push_str(self, s)
+    }
+
+    fn as_str(&self) -> &str {
+        ArrayString::<CAP>::as_str(self)
+    }
+
+    fn clear(&mut self) {
+        ArrayString::<CAP>::clear(self)
+    }
+}
@@ -0,0 +1,96 @@
+//! HKT forms and lock trait impls for the
+//! [async-lock](https://crates.io/crates/async-lock) crate.
+
+use crate::{
+    lock::AsyncLock,
+    plug::{PlugLifetime, PlugType},
+};
+use ::async_lock::{futures::Lock, Mutex, MutexGuard};
+use std::marker::PhantomData;
+
+/// HKT `async_lock::Mutex<T>` with a type slot.
+pub struct H1Mutex;
+
+impl<T> PlugType<T> for H1Mutex {
+    type T = Mutex<T>;
+}
+
+/// HKT `async_lock::MutexGuard<'a, T>` with a lifetime and a type slot.
+pub struct H2MutexGuard;
+
+impl<'a> PlugLifetime<'a> for H2MutexGuard {
+    type T = H1MutexGuard<'a>;
+}
+
+/// HKT `async_lock::MutexGuard<'a, T>` with a type slot.
+pub struct H1MutexGuard<'a>(PhantomData<&'a ()>);
+
+impl<'a, T> PlugType<T> for H1MutexGuard<'a>
+where
+    T: 'a + ?Sized,
+{
+    type T = MutexGuard<'a, T>;
+}
+
+/// HKT `async_lock::MutexGuard<'a, T>` with a lifetime slot.
+pub struct TypedH1MutexGuard<T>(PhantomData<T>)
+where
+    T: ?Sized;
+
+impl<'a, T> PlugLifetime<'a> for TypedH1MutexGuard<T>
+where
+    T: 'a + ?Sized,
+{
+    type T = MutexGuard<'a, T>;
+}
+
+/// HKT `async_lock::futures::Lock<'a, T>` with a lifetime and a type slot.
+pub struct H2Lock;
+
+impl<'a> PlugLifetime<'a> for H2Lock {
+    type T = H1Lock<'a>;
+}
+
+/// HKT `async_lock::futures::Lock<'a, T>` with a type slot.
+pub struct H1Lock<'a>(PhantomData<&'a ()>);
+
+impl<'a, T> PlugType<T> for H1Lock<'a>
+where
+    T: 'a + ?Sized,
+{
+    type T = Lock<'a, T>;
+}
+
+/// HKT `async_lock::futures::Lock<'a, T>` with a lifetime slot.
+pub struct TypedH1Lock<T>(PhantomData<T>)
+where
+    T: ?Sized;
+
+impl<'a, T> PlugLifetime<'a> for TypedH1Lock<T>
+where
+    T: 'a + ?Sized,
+{
+    type T = Lock<'a, T>;
+}
+
+impl<T> AsyncLock<T> for Mutex<T>
+where
+    T: 'static,
+{
+    type H1Guard = TypedH1MutexGuard<T>;
+    type H1LockFuture = TypedH1Lock<T>;
+
+    fn new(x: T) -> Self {
+        Mutex::<T>::new(x)
+    }
+
+    // The `GuardFuture` bound is left out since it would shadow the blanket
+    // impl for the concrete future, preventing its output from being
+    // normalized
+    fn lock<'a>(&'a self) -> <Self::H1LockFuture as PlugLifetime<'a>>::T
+    where
+        Self: 'a,
+    {
+        Mutex::<T>::lock(self)
+    }
+}
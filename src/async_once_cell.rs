@@ -0,0 +1,44 @@
+//! HKT forms and trait impls for the
+//! [async_once_cell](https://crates.io/crates/async_once_cell) crate.
+
+use crate::{
+    boxed::{self, OutputH1SendBoxFuture},
+    lock::AsyncOnceLike,
+    plug::{PlugLifetime, PlugType},
+    reference::TypedH1Reference,
+};
+use ::async_once_cell::OnceCell;
+use std::future::Future;
+
+/// HKT `async_once_cell::OnceCell<T>` with a type slot.
+pub struct H1OnceCell;
+
+impl<T> PlugType<T> for H1OnceCell {
+    type T = OnceCell<T>;
+}
+
+impl<T> AsyncOnceLike<T> for OnceCell<T>
+where
+    T: Send + Sync + 'static,
+{
+    type H1InitFuture = OutputH1SendBoxFuture<TypedH1Reference<T>>;
+
+    fn new() -> Self {
+        OnceCell::<T>::new()
+    }
+
+    fn get(&self) -> Option<&T> {
+        OnceCell::<T>::get(self)
+    }
+
+    fn get_or_init<'a, F>(
+        &'a self,
+        init: F,
+    ) -> <Self::H1InitFuture as PlugLifetime<'a>>::T
+    where
+        Self: 'a,
+        F: Future<Output = T> + Send + 'a,
+    {
+        boxed::box_send_future(OnceCell::<T>::get_or_init(self, init))
+    }
+}
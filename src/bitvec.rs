@@ -0,0 +1,139 @@
+//! Trait impls for the [bitvec](https://crates.io/crates/bitvec) crate.
+
+use crate::{
+    plug::PlugLifetime, Capacity, Sequence, SequenceMut, StreamingIterator,
+    WithCapacity,
+};
+use ::bitvec::{
+    order::BitOrder,
+    slice::{BitSlice, Iter},
+    store::BitStore,
+    vec::BitVec,
+};
+use core::marker::PhantomData;
+
+/// HKT `bitvec::slice::Iter<'a, T, O>` with a lifetime slot.
+///
+/// Items are `BitRef` proxy references rather than `&bool`.
+pub struct TypedH1Iter<T, O>(PhantomData<(T, O)>);
+
+impl<'a, T, O> PlugLifetime<'a> for TypedH1Iter<T, O>
+where
+    T: 'a + BitStore,
+    O: BitOrder,
+{
+    type T = Iter<'a, T, O>;
+}
+
+impl<T, O> WithCapacity for BitVec<T, O>
+where
+    T: BitStore,
+    O: BitOrder,
+{
+    fn with_capacity(capacity: usize) -> Self {
+        BitVec::<T, O>::with_capacity(capacity)
+    }
+}
+
+impl<T, O> Capacity for BitVec<T, O>
+where
+    T: BitStore,
+    O: BitOrder,
+{
+    fn capacity(&self) -> usize {
+        BitVec::<T, O>::capacity(self)
+    }
+}
+
+/// Bits are packed, so `get()` and friends return references to promoted
+/// `bool` constants.
+impl<T, O> Sequence<bool> for BitVec<T, O>
+where
+    T: 'static + BitStore,
+    O: 'static + BitOrder,
+{
+    type H1Iterator = TypedH1Iter<T, O>;
+
+    fn len(&self) -> usize {
+        BitVec::<T, O>::len(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        BitVec::<T, O>::is_empty(self)
+    }
+
+    fn contains(&self, x: &bool) -> bool {
+        if *x {
+            BitSlice::<T, O>::any(self)
+        } else {
+            !BitSlice::<T, O>::all(self)
+        }
+    }
+
+    fn get(&self, index: usize) -> Option<&bool> {
+        if index < BitVec::<T, O>::len(self) {
+            Some(&self[index])
+        } else {
+            None
+        }
+    }
+
+    fn first(&self) -> Option<&bool> {
+        Sequence::<bool>::get(self, 0)
+    }
+
+    fn last(&self) -> Option<&bool> {
+        BitVec::<T, O>::len(self)
+            .checked_sub(1)
+            .and_then(|index| Sequence::<bool>::get(self, index))
+    }
+
+    fn iter<'a>(&'a self) -> <Self::H1Iterator as PlugLifetime<'a>>::T
+    where
+        <Self::H1Iterator as PlugLifetime<'a>>::T: StreamingIterator,
+    {
+        BitSlice::<T, O>::iter(self)
+    }
+}
+
+impl<T, O> SequenceMut<bool> for BitVec<T, O>
+where
+    T: BitStore,
+    O: BitOrder,
+{
+    fn capacity(&self) -> usize {
+        BitVec::<T, O>::capacity(self)
+    }
+
+    fn clear(&mut self) {
+        BitVec::<T, O>::clear(self)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        BitVec::<T, O>::reserve(self, additional)
+    }
+
+    fn reserve_exact(&mut self, additional: usize) {
+        BitVec::<T, O>::reserve_exact(self, additional)
+    }
+
+    fn shrink_to_fit(&mut self) {
+        BitVec::<T, O>::shrink_to_fit(self)
+    }
+
+    fn push(&mut self, x: bool) {
+        BitVec::<T, O>::push(self, x)
+    }
+
+    fn pop(&mut self) -> Option<bool> {
+        BitVec::<T, O>::pop(self)
+    }
+
+    fn insert(&mut self, index: usize, x: bool) {
+        BitVec::<T, O>::insert(self, index, x)
+    }
+
+    fn remove(&mut self, index: usize) -> bool {
+        BitVec::<T, O>::remove(self, index)
+    }
+}
@@ -0,0 +1,67 @@
+//! A module for working with borrowed data.
+
+use crate::{
+    plug::{PlugLifetime, PlugType},
+    CowLike, Stringy,
+};
+use alloc::{
+    borrow::{Cow, ToOwned},
+    string::String,
+};
+use core::marker::PhantomData;
+
+/// HKT `Cow<'a, T>` with a lifetime and a type slot.
+pub struct H2Cow;
+
+impl<'a> PlugLifetime<'a> for H2Cow {
+    type T = H1Cow<'a>;
+}
+
+/// HKT `Cow<'a, T>` with a type slot.
+pub struct H1Cow<'a>(PhantomData<&'a ()>);
+
+impl<'a, T> PlugType<T> for H1Cow<'a>
+where
+    T: 'a + ToOwned + ?Sized,
+{
+    type T = Cow<'a, T>;
+}
+
+impl<'a, T> CowLike<T> for Cow<'a, T>
+where
+    T: Clone,
+{
+    fn from_owned(x: T) -> Self {
+        Cow::Owned(x)
+    }
+
+    fn to_mut(&mut self) -> &mut T {
+        Cow::<T>::to_mut(self)
+    }
+
+    fn into_owned(self) -> T {
+        Cow::<T>::into_owned(self)
+    }
+}
+
+impl Stringy for Cow<'static, str> {
+    #[inline]
+    fn new(s: &str) -> Self {
+        Cow::Owned(String::from(s))
+    }
+
+    #[inline]
+    fn from_string(s: String) -> Self {
+        Cow::Owned(s)
+    }
+
+    #[inline]
+    fn as_str(&self) -> &str {
+        self
+    }
+
+    #[inline]
+    fn into_string(self) -> String {
+        Cow::<str>::into_owned(self)
+    }
+}
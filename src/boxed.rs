@@ -0,0 +1,248 @@
+//! A pointer type for heap allocation, written `Box<T>`.
+
+use crate::{
+    fp::{Applicative, Functor, Monad, Pure, Zip},
+    plug::{PlugLifetime, PlugType},
+    reference::TypedH1MutReference,
+    NewPtr, OwningPtr, Ptr, ReborrowMut, Stringy,
+};
+use alloc::{boxed::Box, string::String};
+use core::{future::Future, marker::PhantomData, ops::DerefMut, pin::Pin};
+
+/// HKT `Box<T>` with a type slot.
+pub struct H1Box;
+
+impl<T> PlugType<T> for H1Box
+where
+    T: ?Sized,
+{
+    type T = Box<T>;
+}
+
+/// HKT `Pin<Box<dyn Future<Output = T> + 'a>>` with a lifetime and a type
+/// slot.
+pub struct H2BoxFuture;
+
+impl<'a> PlugLifetime<'a> for H2BoxFuture {
+    type T = H1BoxFuture<'a>;
+}
+
+/// HKT `Pin<Box<dyn Future<Output = T> + 'a>>` with a type slot.
+pub struct H1BoxFuture<'a>(PhantomData<&'a ()>);
+
+impl<'a, T> PlugType<T> for H1BoxFuture<'a>
+where
+    T: 'a,
+{
+    type T = Pin<Box<dyn Future<Output = T> + 'a>>;
+}
+
+/// HKT `Pin<Box<dyn Future<Output = T> + 'a>>` with a lifetime slot.
+pub struct TypedH1BoxFuture<T>(PhantomData<T>);
+
+impl<'a, T> PlugLifetime<'a> for TypedH1BoxFuture<T>
+where
+    T: 'a,
+{
+    type T = Pin<Box<dyn Future<Output = T> + 'a>>;
+}
+
+/// HKT `Pin<Box<dyn Future<Output = H::T> + 'a>>` with a lifetime slot, for
+/// futures whose output borrows for the same lifetime.
+///
+/// This is the boxed counterpart of a named future form in an async trait.
+/// Implementations can use it with [`box_future`](fn.box_future.html) to
+/// return `async` blocks, trading an allocation per call for not having to
+/// name the future:
+///
+/// ```
+/// use generic_std::{
+///     boxed::{self, OutputH1BoxFuture},
+///     plug::PlugLifetime,
+///     reference::TypedH1Reference,
+/// };
+///
+/// trait Source {
+///     type H1GetFuture: for<'a> PlugLifetime<'a>;
+///
+///     fn get<'a>(&'a self) -> <Self::H1GetFuture as PlugLifetime<'a>>::T;
+/// }
+///
+/// struct Value(String);
+///
+/// impl Source for Value {
+///     type H1GetFuture = OutputH1BoxFuture<TypedH1Reference<str>>;
+///
+///     fn get<'a>(&'a self) -> <Self::H1GetFuture as PlugLifetime<'a>>::T {
+///         boxed::box_future(async move { self.0.as_str() })
+///     }
+/// }
+/// ```
+pub struct OutputH1BoxFuture<H>(PhantomData<H>);
+
+impl<'a, H> PlugLifetime<'a> for OutputH1BoxFuture<H>
+where
+    H: PlugLifetime<'a>,
+    H::T: 'a,
+{
+    type T = Pin<Box<dyn Future<Output = H::T> + 'a>>;
+}
+
+/// Box and pin `future`, erasing its type.
+pub fn box_future<'a, F>(
+    future: F,
+) -> Pin<Box<dyn Future<Output = F::Output> + 'a>>
+where
+    F: Future + 'a,
+{
+    Box::pin(future)
+}
+
+/// HKT `Pin<Box<dyn Future<Output = T> + Send + 'a>>` with a lifetime and a
+/// type slot.
+pub struct H2SendBoxFuture;
+
+impl<'a> PlugLifetime<'a> for H2SendBoxFuture {
+    type T = H1SendBoxFuture<'a>;
+}
+
+/// HKT `Pin<Box<dyn Future<Output = T> + Send + 'a>>` with a type slot.
+pub struct H1SendBoxFuture<'a>(PhantomData<&'a ()>);
+
+impl<'a, T> PlugType<T> for H1SendBoxFuture<'a>
+where
+    T: 'a,
+{
+    type T = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+}
+
+/// HKT `Pin<Box<dyn Future<Output = T> + Send + 'a>>` with a lifetime slot.
+pub struct TypedH1SendBoxFuture<T>(PhantomData<T>);
+
+impl<'a, T> PlugLifetime<'a> for TypedH1SendBoxFuture<T>
+where
+    T: 'a,
+{
+    type T = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+}
+
+/// HKT `Pin<Box<dyn Future<Output = H::T> + Send + 'a>>` with a lifetime
+/// slot, for futures whose output borrows for the same lifetime.
+///
+/// Same as [`OutputH1BoxFuture`](struct.OutputH1BoxFuture.html), but the
+/// futures can be sent to other threads, as required by multi-threaded
+/// executors. Use [`box_send_future`](fn.box_send_future.html) to create
+/// them.
+pub struct OutputH1SendBoxFuture<H>(PhantomData<H>);
+
+impl<'a, H> PlugLifetime<'a> for OutputH1SendBoxFuture<H>
+where
+    H: PlugLifetime<'a>,
+    H::T: 'a,
+{
+    type T = Pin<Box<dyn Future<Output = H::T> + Send + 'a>>;
+}
+
+/// Box and pin `future`, erasing its type but keeping it `Send`.
+pub fn box_send_future<'a, F>(
+    future: F,
+) -> Pin<Box<dyn Future<Output = F::Output> + Send + 'a>>
+where
+    F: Future + Send + 'a,
+{
+    Box::pin(future)
+}
+
+impl<A, B> Functor<A, B> for H1Box {
+    fn fmap<F>(fa: Box<A>, mut f: F) -> Box<B>
+    where
+        F: FnMut(A) -> B,
+    {
+        Box::new(f(*fa))
+    }
+}
+
+impl<A> Pure<A> for H1Box {
+    fn pure(x: A) -> Box<A> {
+        Box::new(x)
+    }
+}
+
+impl<A, B, C> Applicative<A, B, C> for H1Box {
+    fn map2<F>(fa: Box<A>, fb: Box<B>, mut f: F) -> Box<C>
+    where
+        F: FnMut(A, B) -> C,
+    {
+        Box::new(f(*fa, *fb))
+    }
+}
+
+impl<A, B, C> Zip<A, B, C> for H1Box {
+    fn zip_with<F>(fa: Box<A>, fb: Box<B>, mut f: F) -> Box<C>
+    where
+        F: FnMut(A, B) -> C,
+    {
+        Box::new(f(*fa, *fb))
+    }
+}
+
+impl<A, B> Monad<A, B> for H1Box {
+    fn bind<F>(fa: Box<A>, mut f: F) -> Box<B>
+    where
+        F: FnMut(A) -> Box<B>,
+    {
+        f(*fa)
+    }
+}
+
+impl<T> Ptr<T> for Box<T> where T: ?Sized {}
+
+impl<T> NewPtr<T> for Box<T> {
+    fn new(x: T) -> Self {
+        Box::<T>::new(x)
+    }
+}
+
+impl<T> OwningPtr<T> for Box<T> {
+    fn into_inner(this: Self) -> T {
+        *this
+    }
+}
+
+impl<T> ReborrowMut<T> for Box<T>
+where
+    T: 'static + ?Sized,
+{
+    type H1Reborrow = TypedH1MutReference<T>;
+
+    fn reborrow_mut<'a>(
+        &'a mut self,
+    ) -> <Self::H1Reborrow as PlugLifetime<'a>>::T
+    where
+        <Self::H1Reborrow as PlugLifetime<'a>>::T: DerefMut<Target = T>,
+    {
+        &mut **self
+    }
+}
+
+impl Stringy for Box<str> {
+    #[inline]
+    fn new(s: &str) -> Self {
+        Box::from(s)
+    }
+
+    #[inline]
+    fn from_string(s: String) -> Self {
+        String::into_boxed_str(s)
+    }
+
+    #[inline]
+    fn as_str(&self) -> &str {
+        self
+    }
+
+    #[inline]
+    fn into_string(self) -> String {
+        str::into_string(self)
+    }
+}
@@ -0,0 +1,25 @@
+//! Trait impls for the [bumpalo](https://crates.io/crates/bumpalo) crate.
+
+use crate::{plug::PlugLifetime, reference::TypedH1MutReference, Arena};
+use ::bumpalo::Bump;
+use core::ops::DerefMut;
+
+/// Values of any type can be allocated in the same arena. Destructors are
+/// never run.
+impl<T> Arena<T> for Bump
+where
+    T: 'static,
+{
+    type H1Ref = TypedH1MutReference<T>;
+
+    fn new() -> Self {
+        Bump::new()
+    }
+
+    fn alloc<'a>(&'a self, x: T) -> <Self::H1Ref as PlugLifetime<'a>>::T
+    where
+        <Self::H1Ref as PlugLifetime<'a>>::T: DerefMut<Target = T>,
+    {
+        Bump::alloc(self, x)
+    }
+}
@@ -0,0 +1,42 @@
+//! Trait impls for the [bytes](https://crates.io/crates/bytes) crate.
+
+use crate::ByteBuf;
+use ::bytes::BytesMut;
+
+/// Splitting is constant-time as both halves share the same storage.
+impl ByteBuf for BytesMut {
+    #[inline]
+    fn extend_from_slice(&mut self, bytes: &[u8]) {
+        BytesMut::extend_from_slice(self, bytes)
+    }
+
+    #[inline]
+    fn as_slice(&self) -> &[u8] {
+        self
+    }
+
+    #[inline]
+    fn as_mut_slice(&mut self) -> &mut [u8] {
+        self
+    }
+
+    #[inline]
+    fn truncate(&mut self, len: usize) {
+        BytesMut::truncate(self, len)
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        BytesMut::clear(self)
+    }
+
+    #[inline]
+    fn split_to(&mut self, at: usize) -> Self {
+        BytesMut::split_to(self, at)
+    }
+
+    #[inline]
+    fn split_off(&mut self, at: usize) -> Self {
+        BytesMut::split_off(self, at)
+    }
+}
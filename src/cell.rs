@@ -0,0 +1,406 @@
+//! Shareable mutable containers.
+
+#[cfg(feature = "lock")]
+use crate::lock::{
+    CellLike, LazyLike, Lock, LockError, MappableGuard, MappableGuardMut,
+    MaybeAtomic, MaybeAtomicInt, OnceLike, PoisonPolicy, RwLockLike,
+    ScopedLock, UpgradableRwLock,
+};
+use crate::{
+    plug::{PlugLifetime, PlugType},
+    reference::TypedH1MutReference,
+    ReborrowMut,
+};
+use core::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    marker::PhantomData,
+    ops::DerefMut,
+};
+#[cfg(feature = "lock")]
+use core::{
+    cell::{LazyCell, OnceCell},
+    ops::Deref,
+    sync::atomic::Ordering,
+};
+
+/// HKT `std::cell::Cell<T>` with a type slot.
+pub struct H1Cell;
+
+impl<T> PlugType<T> for H1Cell {
+    type T = Cell<T>;
+}
+
+/// HKT `std::cell::RefCell<T>` with a type slot.
+pub struct H1RefCell;
+
+impl<T> PlugType<T> for H1RefCell {
+    type T = RefCell<T>;
+}
+
+/// HKT `std::cell::Ref<'a, T>` with a lifetime and a type slot.
+pub struct H2Ref;
+
+impl<'a> PlugLifetime<'a> for H2Ref {
+    type T = H1Ref<'a>;
+}
+
+/// HKT `std::cell::Ref<'a, T>` with a type slot.
+pub struct H1Ref<'a>(PhantomData<&'a ()>);
+
+impl<'a, T> PlugType<T> for H1Ref<'a>
+where
+    T: 'a + ?Sized,
+{
+    type T = Ref<'a, T>;
+}
+
+/// HKT `std::cell::Ref<'a, T>` with a lifetime slot.
+pub struct TypedH1Ref<T>(PhantomData<T>)
+where
+    T: ?Sized;
+
+impl<'a, T> PlugLifetime<'a> for TypedH1Ref<T>
+where
+    T: 'a + ?Sized,
+{
+    type T = Ref<'a, T>;
+}
+
+/// HKT `std::cell::RefMut<'a, T>` with a lifetime and a type slot.
+pub struct H2RefMut;
+
+impl<'a> PlugLifetime<'a> for H2RefMut {
+    type T = H1RefMut<'a>;
+}
+
+/// HKT `std::cell::RefMut<'a, T>` with a type slot.
+pub struct H1RefMut<'a>(PhantomData<&'a ()>);
+
+impl<'a, T> PlugType<T> for H1RefMut<'a>
+where
+    T: 'a + ?Sized,
+{
+    type T = RefMut<'a, T>;
+}
+
+/// HKT `std::cell::RefMut<'a, T>` with a lifetime slot.
+pub struct TypedH1RefMut<T>(PhantomData<T>)
+where
+    T: ?Sized;
+
+impl<'a, T> PlugLifetime<'a> for TypedH1RefMut<T>
+where
+    T: 'a + ?Sized,
+{
+    type T = RefMut<'a, T>;
+}
+
+/// Locking mutably borrows the cell.
+#[cfg(feature = "lock")]
+impl<T> Lock<T> for RefCell<T>
+where
+    T: 'static,
+{
+    type H1Guard = TypedH1RefMut<T>;
+
+    fn new(x: T) -> Self {
+        RefCell::<T>::new(x)
+    }
+
+    fn lock_with<'a>(
+        &'a self,
+        _: PoisonPolicy,
+    ) -> Result<<Self::H1Guard as PlugLifetime<'a>>::T, LockError>
+    where
+        <Self::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = T>,
+    {
+        RefCell::<T>::try_borrow_mut(self).map_err(|_| LockError::WouldBlock)
+    }
+
+    fn try_lock<'a>(&'a self) -> Option<<Self::H1Guard as PlugLifetime<'a>>::T>
+    where
+        <Self::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = T>,
+    {
+        RefCell::<T>::try_borrow_mut(self).ok()
+    }
+}
+
+#[cfg(feature = "lock")]
+impl<T> ScopedLock<T> for RefCell<T>
+where
+    T: ?Sized,
+{
+    fn with(&self, f: &mut dyn FnMut(&T)) {
+        f(&RefCell::<T>::borrow(self))
+    }
+
+    fn with_mut(&self, f: &mut dyn FnMut(&mut T)) {
+        f(&mut RefCell::<T>::borrow_mut(self))
+    }
+}
+
+#[cfg(feature = "lock")]
+impl<T> RwLockLike<T> for RefCell<T>
+where
+    T: 'static,
+{
+    type H1ReadGuard = TypedH1Ref<T>;
+
+    fn read_with<'a>(
+        &'a self,
+        _: PoisonPolicy,
+    ) -> Result<<Self::H1ReadGuard as PlugLifetime<'a>>::T, LockError>
+    where
+        <Self::H1ReadGuard as PlugLifetime<'a>>::T: Deref<Target = T>,
+    {
+        RefCell::<T>::try_borrow(self).map_err(|_| LockError::WouldBlock)
+    }
+
+    fn try_read<'a>(
+        &'a self,
+    ) -> Option<<Self::H1ReadGuard as PlugLifetime<'a>>::T>
+    where
+        <Self::H1ReadGuard as PlugLifetime<'a>>::T: Deref<Target = T>,
+    {
+        RefCell::<T>::try_borrow(self).ok()
+    }
+}
+
+#[cfg(feature = "lock")]
+impl<T> CellLike<T> for Cell<T> {
+    fn new(x: T) -> Self {
+        Cell::<T>::new(x)
+    }
+
+    fn get(&self) -> T
+    where
+        T: Copy,
+    {
+        Cell::<T>::get(self)
+    }
+
+    fn set(&self, x: T) {
+        Cell::<T>::set(self, x)
+    }
+
+    fn replace(&self, x: T) -> T {
+        Cell::<T>::replace(self, x)
+    }
+
+    fn take(&self) -> T
+    where
+        T: Default,
+    {
+        Cell::<T>::take(self)
+    }
+}
+
+#[cfg(feature = "lock")]
+impl<T> OnceLike<T> for OnceCell<T> {
+    fn new() -> Self {
+        OnceCell::<T>::new()
+    }
+
+    fn get(&self) -> Option<&T> {
+        OnceCell::<T>::get(self)
+    }
+
+    fn set(&self, x: T) -> Result<(), T> {
+        OnceCell::<T>::set(self, x)
+    }
+
+    fn get_or_init<F>(&self, f: F) -> &T
+    where
+        F: FnOnce() -> T,
+    {
+        OnceCell::<T>::get_or_init(self, f)
+    }
+}
+
+#[cfg(feature = "lock")]
+impl<T, F> LazyLike<T> for LazyCell<T, F>
+where
+    F: FnOnce() -> T,
+{
+    fn force(this: &Self) -> &T {
+        LazyCell::<T, F>::force(this)
+    }
+}
+
+#[cfg(feature = "lock")]
+impl MaybeAtomic<usize> for Cell<usize> {
+    #[inline]
+    fn new(x: usize) -> Self {
+        Cell::<usize>::new(x)
+    }
+
+    #[inline]
+    fn load(&self, _: Ordering) -> usize {
+        Cell::<usize>::get(self)
+    }
+
+    #[inline]
+    fn store(&self, x: usize, _: Ordering) {
+        Cell::<usize>::set(self, x)
+    }
+
+    #[inline]
+    fn swap(&self, x: usize, _: Ordering) -> usize {
+        Cell::<usize>::replace(self, x)
+    }
+
+    #[inline]
+    fn compare_exchange(
+        &self,
+        current: usize,
+        new: usize,
+        _: Ordering,
+        _: Ordering,
+    ) -> Result<usize, usize> {
+        let previous = Cell::<usize>::get(self);
+        if previous == current {
+            Cell::<usize>::set(self, new);
+
+            Ok(previous)
+        } else {
+            Err(previous)
+        }
+    }
+}
+
+#[cfg(feature = "lock")]
+impl MaybeAtomicInt<usize> for Cell<usize> {
+    #[inline]
+    fn fetch_add(&self, x: usize, _: Ordering) -> usize {
+        Cell::<usize>::replace(self, Cell::<usize>::get(self).wrapping_add(x))
+    }
+
+    #[inline]
+    fn fetch_sub(&self, x: usize, _: Ordering) -> usize {
+        Cell::<usize>::replace(self, Cell::<usize>::get(self).wrapping_sub(x))
+    }
+}
+
+#[cfg(feature = "lock")]
+impl MaybeAtomic<bool> for Cell<bool> {
+    #[inline]
+    fn new(x: bool) -> Self {
+        Cell::<bool>::new(x)
+    }
+
+    #[inline]
+    fn load(&self, _: Ordering) -> bool {
+        Cell::<bool>::get(self)
+    }
+
+    #[inline]
+    fn store(&self, x: bool, _: Ordering) {
+        Cell::<bool>::set(self, x)
+    }
+
+    #[inline]
+    fn swap(&self, x: bool, _: Ordering) -> bool {
+        Cell::<bool>::replace(self, x)
+    }
+
+    #[inline]
+    fn compare_exchange(
+        &self,
+        current: bool,
+        new: bool,
+        _: Ordering,
+        _: Ordering,
+    ) -> Result<bool, bool> {
+        let previous = Cell::<bool>::get(self);
+        if previous == current {
+            Cell::<bool>::set(self, new);
+
+            Ok(previous)
+        } else {
+            Err(previous)
+        }
+    }
+}
+
+impl<T> ReborrowMut<T> for RefMut<'_, T>
+where
+    T: 'static + ?Sized,
+{
+    type H1Reborrow = TypedH1MutReference<T>;
+
+    fn reborrow_mut<'a>(
+        &'a mut self,
+    ) -> <Self::H1Reborrow as PlugLifetime<'a>>::T
+    where
+        <Self::H1Reborrow as PlugLifetime<'a>>::T: DerefMut<Target = T>,
+    {
+        &mut **self
+    }
+}
+
+/// Upgradable reads are emulated with exclusive access.
+#[cfg(feature = "lock")]
+impl<T> UpgradableRwLock<T> for RefCell<T>
+where
+    T: 'static,
+{
+    type H1UpgradableGuard = Self::H1Guard;
+
+    fn upgradable_read_with<'a>(
+        &'a self,
+        _: PoisonPolicy,
+    ) -> Result<<Self::H1UpgradableGuard as PlugLifetime<'a>>::T, LockError>
+    where
+        <Self::H1UpgradableGuard as PlugLifetime<'a>>::T: Deref<Target = T>,
+    {
+        RefCell::<T>::try_borrow_mut(self).map_err(|_| LockError::WouldBlock)
+    }
+
+    fn upgrade<'a>(
+        guard: <Self::H1UpgradableGuard as PlugLifetime<'a>>::T,
+    ) -> <Self::H1Guard as PlugLifetime<'a>>::T
+    where
+        <Self::H1UpgradableGuard as PlugLifetime<'a>>::T: Deref<Target = T>,
+        <Self::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = T>,
+    {
+        guard
+    }
+}
+
+#[cfg(feature = "lock")]
+impl<T, U> MappableGuard<T, U> for TypedH1Ref<T>
+where
+    T: 'static + ?Sized,
+    U: 'static + ?Sized,
+{
+    type H1Mapped = TypedH1Ref<U>;
+
+    fn map_guard<'a, F>(
+        guard: <Self as PlugLifetime<'a>>::T,
+        f: F,
+    ) -> <Self::H1Mapped as PlugLifetime<'a>>::T
+    where
+        F: FnOnce(&T) -> &U,
+    {
+        Ref::<T>::map(guard, f)
+    }
+}
+
+#[cfg(feature = "lock")]
+impl<T, U> MappableGuardMut<T, U> for TypedH1RefMut<T>
+where
+    T: 'static + ?Sized,
+    U: 'static + ?Sized,
+{
+    type H1Mapped = TypedH1RefMut<U>;
+
+    fn map_guard_mut<'a, F>(
+        guard: <Self as PlugLifetime<'a>>::T,
+        f: F,
+    ) -> <Self::H1Mapped as PlugLifetime<'a>>::T
+    where
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        RefMut::<T>::map(guard, f)
+    }
+}
@@ -0,0 +1,77 @@
+//! Traits for channels.
+//!
+//! Like the [lock traits](../lock/index.html), these make the choice of channel
+//! implementation a type parameter, so pipeline code can be written once for
+//! `std::sync::mpsc`, `crossbeam-channel` or `flume`.
+
+use crate::{plug::PlugLifetime, StreamingIterator};
+use core::{error::Error, fmt};
+
+/// Error returned by [`Receiver::recv`](trait.Receiver.html#tymethod.recv)
+/// when all senders have been dropped and the channel is empty.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RecvError;
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("channel disconnected")
+    }
+}
+
+impl Error for RecvError {}
+
+/// Error returned by
+/// [`Receiver::try_recv`](trait.Receiver.html#tymethod.try_recv).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TryRecvError {
+    /// The channel is currently empty but senders are still alive.
+    Empty,
+    /// All senders have been dropped and the channel is empty.
+    Disconnected,
+}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryRecvError::Empty => f.write_str("channel empty"),
+            TryRecvError::Disconnected => f.write_str("channel disconnected"),
+        }
+    }
+}
+
+impl Error for TryRecvError {}
+
+/// Trait for the sending half of a channel.
+pub trait Sender<T>: Clone {
+    /// Send a value, returning it back if the receiver has been dropped.
+    fn send(&self, x: T) -> Result<(), T>;
+}
+
+/// Trait for the receiving half of a channel.
+///
+/// # Note
+///
+/// `H1Iterator` has the same limitations as
+/// [`Sequence::H1Iterator`](../trait.Sequence.html#associatedtype.H1Iterator),
+/// so implementors must only allow `T: 'static`.
+pub trait Receiver<T> {
+    /// The sending half of the channel.
+    type Sender: Sender<T>;
+    /// HKT blocking iterator with a lifetime slot.
+    type H1Iterator: for<'a> PlugLifetime<'a>;
+
+    /// Create a new unbounded channel.
+    fn channel() -> (Self::Sender, Self)
+    where
+        Self: Sized;
+
+    /// Block until a value is received or all senders are dropped.
+    fn recv(&self) -> Result<T, RecvError>;
+
+    fn try_recv(&self) -> Result<T, TryRecvError>;
+
+    /// Iterate over received values, blocking until all senders are dropped.
+    fn iter<'a>(&'a self) -> <Self::H1Iterator as PlugLifetime<'a>>::T
+    where
+        <Self::H1Iterator as PlugLifetime<'a>>::T: StreamingIterator;
+}
@@ -0,0 +1,823 @@
+//! Collection types, including HKT forms for `std::collections` and
+//! collections generic over the kind of pointer used to link their elements.
+
+use crate::{
+    erased::DynMap,
+    fp::{Monoid, Semigroup},
+    plug::{PlugLifetime, PlugType, H0},
+    stream::AsyncSequenceMut,
+    Capacity, StreamingIterator, WithCapacity,
+};
+#[cfg(feature = "std")]
+use crate::{
+    reference::TypedH1Reference, ConvertRcb, MapFactory, PointerFamily, Rcb,
+    RcbWithWeak, WeakRcb,
+};
+use alloc::{
+    boxed::Box,
+    collections::{
+        btree_map, vec_deque, BTreeMap, BinaryHeap, LinkedList, VecDeque,
+    },
+};
+use core::{
+    future::{self, Future, Ready},
+    marker::PhantomData,
+};
+#[cfg(feature = "std")]
+use std::{
+    collections::{hash_map, HashMap, HashSet},
+    hash::{BuildHasher, Hash},
+    sync::Mutex,
+};
+
+/// HKT `std::collections::VecDeque<T>` with a type slot.
+pub struct H1VecDeque;
+
+impl<T> PlugType<T> for H1VecDeque {
+    type T = VecDeque<T>;
+}
+
+/// HKT `std::collections::LinkedList<T>` with a type slot.
+pub struct H1LinkedList;
+
+impl<T> PlugType<T> for H1LinkedList {
+    type T = LinkedList<T>;
+}
+
+/// HKT `std::collections::BinaryHeap<T>` with a type slot.
+pub struct H1BinaryHeap;
+
+impl<T> PlugType<T> for H1BinaryHeap {
+    type T = BinaryHeap<T>;
+}
+
+/// HKT `std::collections::vec_deque::Iter<'a, T>` with a lifetime slot.
+pub struct TypedH1VecDequeIter<T>(PhantomData<T>);
+
+impl<'a, T> PlugLifetime<'a> for TypedH1VecDequeIter<T>
+where
+    T: 'a,
+{
+    type T = vec_deque::Iter<'a, T>;
+}
+
+impl<T> WithCapacity for VecDeque<T> {
+    fn with_capacity(capacity: usize) -> Self {
+        VecDeque::<T>::with_capacity(capacity)
+    }
+}
+
+impl<T> Capacity for VecDeque<T> {
+    fn capacity(&self) -> usize {
+        VecDeque::<T>::capacity(self)
+    }
+}
+
+impl<T> WithCapacity for BinaryHeap<T>
+where
+    T: Ord,
+{
+    fn with_capacity(capacity: usize) -> Self {
+        BinaryHeap::<T>::with_capacity(capacity)
+    }
+}
+
+impl<T> Capacity for BinaryHeap<T> {
+    fn capacity(&self) -> usize {
+        BinaryHeap::<T>::capacity(self)
+    }
+}
+
+impl<T> crate::Sequence<T> for VecDeque<T>
+where
+    T: 'static,
+{
+    type H1Iterator = TypedH1VecDequeIter<T>;
+
+    fn len(&self) -> usize {
+        VecDeque::<T>::len(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        VecDeque::<T>::is_empty(self)
+    }
+
+    fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        VecDeque::<T>::contains(self, x)
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        VecDeque::<T>::get(self, index)
+    }
+
+    fn first(&self) -> Option<&T> {
+        VecDeque::<T>::front(self)
+    }
+
+    fn last(&self) -> Option<&T> {
+        VecDeque::<T>::back(self)
+    }
+
+    fn iter<'a>(&'a self) -> <Self::H1Iterator as PlugLifetime<'a>>::T
+    where
+        <Self::H1Iterator as PlugLifetime<'a>>::T: StreamingIterator,
+    {
+        VecDeque::<T>::iter(self)
+    }
+}
+
+/// `push` and `pop` act on the back.
+impl<T> crate::SequenceMut<T> for VecDeque<T> {
+    fn capacity(&self) -> usize {
+        VecDeque::<T>::capacity(self)
+    }
+
+    fn clear(&mut self) {
+        VecDeque::<T>::clear(self)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        VecDeque::<T>::reserve(self, additional)
+    }
+
+    fn reserve_exact(&mut self, additional: usize) {
+        VecDeque::<T>::reserve_exact(self, additional)
+    }
+
+    fn shrink_to_fit(&mut self) {
+        VecDeque::<T>::shrink_to_fit(self)
+    }
+
+    fn push(&mut self, x: T) {
+        VecDeque::<T>::push_back(self, x)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        VecDeque::<T>::pop_back(self)
+    }
+
+    fn insert(&mut self, index: usize, x: T) {
+        VecDeque::<T>::insert(self, index, x)
+    }
+
+    fn remove(&mut self, index: usize) -> T {
+        let len = VecDeque::<T>::len(self);
+        VecDeque::<T>::remove(self, index).unwrap_or_else(|| {
+            panic!("removal index (is {}) should be < len (is {})", index, len)
+        })
+    }
+}
+
+/// Elements are always accepted immediately, at the back.
+impl<T> AsyncSequenceMut<T> for VecDeque<T> {
+    type H1PushFuture = H0<Ready<Result<(), T>>>;
+
+    fn push<'a>(
+        &'a mut self,
+        x: T,
+    ) -> <Self::H1PushFuture as PlugLifetime<'a>>::T
+    where
+        <Self::H1PushFuture as PlugLifetime<'a>>::T:
+            Future<Output = Result<(), T>>,
+    {
+        VecDeque::<T>::push_back(self, x);
+
+        future::ready(Ok(()))
+    }
+}
+
+/// A doubly-linked list generic over a [`PointerFamily`], such as
+/// [`RcFamily`] or [`ArcFamily`].
+///
+/// Each node owns the next one through a strong pointer and refers back to
+/// the previous one through a weak pointer, so there are no reference cycles.
+/// Links are guarded by a `Mutex` so that lists built on a thread-safe family
+/// are `Send` and `Sync`.
+///
+/// [`PointerFamily`]: ../trait.PointerFamily.html
+/// [`RcFamily`]: ../rc/struct.RcFamily.html
+/// [`ArcFamily`]: ../sync/struct.ArcFamily.html
+#[cfg(feature = "std")]
+pub struct DoublyLinkedList<T, P>
+where
+    P: PointerFamily<ListNode<T, P>>,
+{
+    head: Option<P::Strong>,
+    tail: Option<P::Strong>,
+    len: usize,
+}
+
+/// A node of a [`DoublyLinkedList`](struct.DoublyLinkedList.html).
+#[cfg(feature = "std")]
+pub struct ListNode<T, P>
+where
+    P: PointerFamily<ListNode<T, P>>,
+{
+    value: T,
+    next: Mutex<Option<P::Strong>>,
+    prev: Mutex<P::Weak>,
+}
+
+#[cfg(feature = "std")]
+impl<T, P> DoublyLinkedList<T, P>
+where
+    P: PointerFamily<ListNode<T, P>>,
+{
+    pub fn new() -> Self {
+        Self {
+            head: None,
+            tail: None,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.head.as_deref().map(|node| &node.value)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.tail.as_deref().map(|node| &node.value)
+    }
+
+    pub fn push_front(&mut self, x: T) {
+        let node = P::Strong::new(ListNode::new(x));
+        match self.head.take() {
+            Some(head) => {
+                *head.prev.lock().unwrap() = P::Strong::downgrade(&node);
+                *node.next.lock().unwrap() = Some(head);
+            }
+            None => self.tail = Some(node.clone()),
+        }
+        self.head = Some(node);
+        self.len += 1;
+    }
+
+    pub fn push_back(&mut self, x: T) {
+        let node = P::Strong::new(ListNode::new(x));
+        match self.tail.take() {
+            Some(tail) => {
+                *node.prev.lock().unwrap() = P::Strong::downgrade(&tail);
+                *tail.next.lock().unwrap() = Some(node.clone());
+            }
+            None => self.head = Some(node.clone()),
+        }
+        self.tail = Some(node);
+        self.len += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        let head = self.head.take()?;
+        let next = head.next.lock().unwrap().take();
+        match next {
+            Some(next) => {
+                *next.prev.lock().unwrap() = P::Weak::new();
+                self.head = Some(next);
+            }
+            None => self.tail = None,
+        }
+        self.len -= 1;
+
+        Some(Self::into_value(head))
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        let tail = self.tail.take()?;
+        let prev = tail.prev.lock().unwrap().upgrade();
+        match prev {
+            Some(prev) => {
+                *prev.next.lock().unwrap() = None;
+                self.tail = Some(prev);
+            }
+            None => self.head = None,
+        }
+        self.len -= 1;
+
+        Some(Self::into_value(tail))
+    }
+
+    /// Iterate over the elements of the list, from front to back.
+    pub fn iter(&self) -> Iter<'_, T, P> {
+        Iter {
+            current: None,
+            next: self.head.clone(),
+            _list: PhantomData,
+        }
+    }
+
+    fn into_value(node: P::Strong) -> T {
+        match P::Strong::try_unwrap(node) {
+            Ok(node) => node.value,
+            Err(_) => unreachable!("unlinked node is still shared"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, P> Default for DoublyLinkedList<T, P>
+where
+    P: PointerFamily<ListNode<T, P>>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, P, Q> ConvertRcb<Q> for DoublyLinkedList<T, P>
+where
+    T: Clone,
+    P: PointerFamily<ListNode<T, P>>,
+    Q: PointerFamily<ListNode<T, Q>>,
+{
+    type Output = DoublyLinkedList<T, Q>;
+
+    fn convert_rcb(&self) -> Self::Output {
+        let mut converted = DoublyLinkedList::new();
+        let mut next = self.head.clone();
+        while let Some(node) = next {
+            converted.push_back(node.value.clone());
+            next = node.next.lock().unwrap().clone();
+        }
+
+        converted
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, P> Drop for DoublyLinkedList<T, P>
+where
+    P: PointerFamily<ListNode<T, P>>,
+{
+    fn drop(&mut self) {
+        // Unlink iteratively, otherwise dropping a long list overflows the
+        // stack
+        while self.pop_front().is_some() {}
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, P> ListNode<T, P>
+where
+    P: PointerFamily<ListNode<T, P>>,
+{
+    fn new(value: T) -> Self {
+        Self {
+            value,
+            next: Mutex::new(None),
+            prev: Mutex::new(P::Weak::new()),
+        }
+    }
+}
+
+/// Streaming iterator over the elements of a
+/// [`DoublyLinkedList`](struct.DoublyLinkedList.html).
+///
+/// Links are behind locks, so items are borrowed from the iterator itself
+/// rather than from the list.
+#[cfg(feature = "std")]
+pub struct Iter<'a, T, P>
+where
+    P: PointerFamily<ListNode<T, P>>,
+{
+    current: Option<P::Strong>,
+    next: Option<P::Strong>,
+    _list: PhantomData<&'a DoublyLinkedList<T, P>>,
+}
+
+#[cfg(feature = "std")]
+impl<'a, T, P> StreamingIterator for Iter<'a, T, P>
+where
+    T: 'static,
+    P: PointerFamily<ListNode<T, P>>,
+{
+    type H1Item = TypedH1Reference<T>;
+
+    fn next(&mut self) -> Option<<Self::H1Item as PlugLifetime<'_>>::T> {
+        self.current = self.next.take();
+        if let Some(current) = &self.current {
+            self.next = current.next.lock().unwrap().clone();
+        }
+
+        self.current.as_deref().map(|node| &node.value)
+    }
+}
+
+/// A handle to a node of a tree generic over a [`PointerFamily`], such as
+/// [`RcFamily`] or [`ArcFamily`]. Every node is also the root of its own
+/// subtree.
+///
+/// Parents own their children through strong pointers and children refer
+/// back to their parent through a weak pointer, so there are no reference
+/// cycles. Cloning a handle is cheap and yields a handle to the same node.
+///
+/// [`PointerFamily`]: ../trait.PointerFamily.html
+/// [`RcFamily`]: ../rc/struct.RcFamily.html
+/// [`ArcFamily`]: ../sync/struct.ArcFamily.html
+#[cfg(feature = "std")]
+pub struct Tree<T, P>
+where
+    P: PointerFamily<TreeNode<T, P>>,
+{
+    node: P::Strong,
+}
+
+/// A node of a [`Tree`](struct.Tree.html).
+#[cfg(feature = "std")]
+pub struct TreeNode<T, P>
+where
+    P: PointerFamily<TreeNode<T, P>>,
+{
+    value: T,
+    parent: Mutex<P::Weak>,
+    children: Mutex<Vec<P::Strong>>,
+}
+
+#[cfg(feature = "std")]
+impl<T, P> Tree<T, P>
+where
+    P: PointerFamily<TreeNode<T, P>>,
+{
+    /// Create a node without parent or children.
+    pub fn new(value: T) -> Self {
+        Self::with_children(value, Vec::new())
+    }
+
+    /// Create a node with the given children, detaching them from their
+    /// previous parents if needed.
+    pub fn with_children(value: T, children: Vec<Tree<T, P>>) -> Self {
+        for child in &children {
+            child.detach();
+        }
+
+        let node = P::Strong::new_cyclic(|this| {
+            for child in &children {
+                *child.node.parent.lock().unwrap() = this.clone();
+            }
+
+            TreeNode {
+                value,
+                parent: Mutex::new(P::Weak::new()),
+                children: Mutex::new(
+                    children.into_iter().map(|x| x.node).collect(),
+                ),
+            }
+        });
+
+        Self { node }
+    }
+
+    pub fn value(&self) -> &T {
+        &self.node.value
+    }
+
+    pub fn parent(&self) -> Option<Self> {
+        let parent = self.node.parent.lock().unwrap().upgrade();
+
+        parent.map(|node| Self { node })
+    }
+
+    pub fn children(&self) -> Vec<Self> {
+        let children = self.node.children.lock().unwrap();
+
+        children
+            .iter()
+            .map(|node| Self { node: node.clone() })
+            .collect()
+    }
+
+    /// Append `child` as the last child of this node, detaching it from its
+    /// previous parent if needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `child` is this node or one of its ancestors.
+    pub fn append(&self, child: Self) {
+        let mut ancestor = Some(self.clone());
+        while let Some(node) = ancestor {
+            assert!(!node.ptr_eq(&child), "cannot append an ancestor");
+            ancestor = node.parent();
+        }
+
+        child.detach();
+        *child.node.parent.lock().unwrap() = P::Strong::downgrade(&self.node);
+        self.node.children.lock().unwrap().push(child.node);
+    }
+
+    /// Remove this node from its parent's children, making it a root.
+    pub fn detach(&self) {
+        if let Some(parent) = self.parent() {
+            parent
+                .node
+                .children
+                .lock()
+                .unwrap()
+                .retain(|node| !Self::node_ptr_eq(node, &self.node));
+        }
+        *self.node.parent.lock().unwrap() = P::Weak::new();
+    }
+
+    /// Returns `true` if both handles refer to the same node.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Self::node_ptr_eq(&self.node, &other.node)
+    }
+
+    fn node_ptr_eq(a: &P::Strong, b: &P::Strong) -> bool {
+        let a: &TreeNode<T, P> = a;
+        let b: &TreeNode<T, P> = b;
+
+        std::ptr::eq(a, b)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, P> Clone for Tree<T, P>
+where
+    P: PointerFamily<TreeNode<T, P>>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            node: self.node.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, P, Q> ConvertRcb<Q> for Tree<T, P>
+where
+    T: Clone,
+    P: PointerFamily<TreeNode<T, P>>,
+    Q: PointerFamily<TreeNode<T, Q>>,
+{
+    type Output = Tree<T, Q>;
+
+    /// Convert the subtree rooted at this node. The parent, if any, is not
+    /// converted.
+    fn convert_rcb(&self) -> Self::Output {
+        Tree::with_children(
+            self.value().clone(),
+            self.children().iter().map(|x| x.convert_rcb()).collect(),
+        )
+    }
+}
+
+/// Union of the keys, combining the values of keys present in both maps.
+#[cfg(feature = "std")]
+impl<K, V, S> Semigroup for HashMap<K, V, S>
+where
+    K: Eq + Hash,
+    V: Semigroup,
+    S: BuildHasher,
+{
+    fn combine(mut self, other: Self) -> Self {
+        for (k, v) in other {
+            match self.entry(k) {
+                hash_map::Entry::Occupied(entry) => {
+                    let (k, x) = entry.remove_entry();
+                    self.insert(k, x.combine(v));
+                }
+                hash_map::Entry::Vacant(entry) => {
+                    entry.insert(v);
+                }
+            }
+        }
+
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> Monoid for HashMap<K, V, S>
+where
+    K: Eq + Hash,
+    V: Semigroup,
+    S: BuildHasher + Default,
+{
+    fn empty() -> Self {
+        HashMap::default()
+    }
+}
+
+/// Union of the keys, combining the values of keys present in both maps.
+impl<K, V> Semigroup for BTreeMap<K, V>
+where
+    K: Ord,
+    V: Semigroup,
+{
+    fn combine(mut self, other: Self) -> Self {
+        for (k, v) in other {
+            match self.entry(k) {
+                btree_map::Entry::Occupied(entry) => {
+                    let (k, x) = entry.remove_entry();
+                    self.insert(k, x.combine(v));
+                }
+                btree_map::Entry::Vacant(entry) => {
+                    entry.insert(v);
+                }
+            }
+        }
+
+        self
+    }
+}
+
+impl<K, V> Monoid for BTreeMap<K, V>
+where
+    K: Ord,
+    V: Semigroup,
+{
+    fn empty() -> Self {
+        BTreeMap::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> MapFactory<S> for HashMap<K, V, S>
+where
+    S: BuildHasher,
+{
+    fn with_hasher(hasher: S) -> Self {
+        HashMap::<K, V, S>::with_hasher(hasher)
+    }
+
+    fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+        HashMap::<K, V, S>::with_capacity_and_hasher(capacity, hasher)
+    }
+
+    fn hasher(&self) -> &S {
+        HashMap::<K, V, S>::hasher(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, S> MapFactory<S> for HashSet<T, S>
+where
+    S: BuildHasher,
+{
+    fn with_hasher(hasher: S) -> Self {
+        HashSet::<T, S>::with_hasher(hasher)
+    }
+
+    fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+        HashSet::<T, S>::with_capacity_and_hasher(capacity, hasher)
+    }
+
+    fn hasher(&self) -> &S {
+        HashSet::<T, S>::hasher(self)
+    }
+}
+
+/// Uses the default hasher.
+#[cfg(feature = "std")]
+impl<K, V, S> WithCapacity for HashMap<K, V, S>
+where
+    S: BuildHasher + Default,
+{
+    fn with_capacity(capacity: usize) -> Self {
+        HashMap::<K, V, S>::with_capacity_and_hasher(capacity, S::default())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> Capacity for HashMap<K, V, S> {
+    fn capacity(&self) -> usize {
+        HashMap::<K, V, S>::capacity(self)
+    }
+}
+
+/// Uses the default hasher.
+#[cfg(feature = "std")]
+impl<T, S> WithCapacity for HashSet<T, S>
+where
+    S: BuildHasher + Default,
+{
+    fn with_capacity(capacity: usize) -> Self {
+        HashSet::<T, S>::with_capacity_and_hasher(capacity, S::default())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, S> Capacity for HashSet<T, S> {
+    fn capacity(&self) -> usize {
+        HashSet::<T, S>::capacity(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> DynMap<K, V> for HashMap<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    fn len(&self) -> usize {
+        HashMap::<K, V, S>::len(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        HashMap::<K, V, S>::is_empty(self)
+    }
+
+    fn contains_key(&self, k: &K) -> bool {
+        HashMap::<K, V, S>::contains_key(self, k)
+    }
+
+    fn get(&self, k: &K) -> Option<&V> {
+        HashMap::<K, V, S>::get(self, k)
+    }
+
+    fn get_mut(&mut self, k: &K) -> Option<&mut V> {
+        HashMap::<K, V, S>::get_mut(self, k)
+    }
+
+    fn insert(&mut self, k: K, v: V) -> Option<V> {
+        HashMap::<K, V, S>::insert(self, k, v)
+    }
+
+    fn remove(&mut self, k: &K) -> Option<V> {
+        HashMap::<K, V, S>::remove(self, k)
+    }
+
+    fn clear(&mut self) {
+        HashMap::<K, V, S>::clear(self)
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (&K, &V)> + '_> {
+        Box::new(HashMap::<K, V, S>::iter(self))
+    }
+
+    fn iter_mut(&mut self) -> Box<dyn Iterator<Item = (&K, &mut V)> + '_> {
+        Box::new(HashMap::<K, V, S>::iter_mut(self))
+    }
+
+    fn keys(&self) -> Box<dyn Iterator<Item = &K> + '_> {
+        Box::new(HashMap::<K, V, S>::keys(self))
+    }
+
+    fn values(&self) -> Box<dyn Iterator<Item = &V> + '_> {
+        Box::new(HashMap::<K, V, S>::values(self))
+    }
+}
+
+/// Iterates in key order.
+impl<K, V> DynMap<K, V> for BTreeMap<K, V>
+where
+    K: Ord,
+{
+    fn len(&self) -> usize {
+        BTreeMap::<K, V>::len(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        BTreeMap::<K, V>::is_empty(self)
+    }
+
+    fn contains_key(&self, k: &K) -> bool {
+        BTreeMap::<K, V>::contains_key(self, k)
+    }
+
+    fn get(&self, k: &K) -> Option<&V> {
+        BTreeMap::<K, V>::get(self, k)
+    }
+
+    fn get_mut(&mut self, k: &K) -> Option<&mut V> {
+        BTreeMap::<K, V>::get_mut(self, k)
+    }
+
+    fn insert(&mut self, k: K, v: V) -> Option<V> {
+        BTreeMap::<K, V>::insert(self, k, v)
+    }
+
+    fn remove(&mut self, k: &K) -> Option<V> {
+        BTreeMap::<K, V>::remove(self, k)
+    }
+
+    fn clear(&mut self) {
+        BTreeMap::<K, V>::clear(self)
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (&K, &V)> + '_> {
+        Box::new(BTreeMap::<K, V>::iter(self))
+    }
+
+    fn iter_mut(&mut self) -> Box<dyn Iterator<Item = (&K, &mut V)> + '_> {
+        Box::new(BTreeMap::<K, V>::iter_mut(self))
+    }
+
+    fn keys(&self) -> Box<dyn Iterator<Item = &K> + '_> {
+        Box::new(BTreeMap::<K, V>::keys(self))
+    }
+
+    fn values(&self) -> Box<dyn Iterator<Item = &V> + '_> {
+        Box::new(BTreeMap::<K, V>::values(self))
+    }
+}
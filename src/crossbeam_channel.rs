@@ -0,0 +1,59 @@
+//! Trait impls for the
+//! [crossbeam-channel](https://crates.io/crates/crossbeam-channel) crate.
+
+use crate::{
+    channel::{self, RecvError, TryRecvError},
+    plug::PlugLifetime,
+    StreamingIterator,
+};
+use ::crossbeam_channel::{Iter, Receiver, Sender};
+use core::marker::PhantomData;
+
+/// HKT `crossbeam_channel::Iter<'a, T>` with a lifetime slot.
+pub struct TypedH1Iter<T>(PhantomData<T>);
+
+impl<'a, T> PlugLifetime<'a> for TypedH1Iter<T>
+where
+    T: 'a,
+{
+    type T = Iter<'a, T>;
+}
+
+impl<T> channel::Sender<T> for Sender<T> {
+    fn send(&self, x: T) -> Result<(), T> {
+        Sender::<T>::send(self, x).map_err(|err| err.0)
+    }
+}
+
+/// Channels created through the trait are unbounded.
+impl<T> channel::Receiver<T> for Receiver<T>
+where
+    T: 'static,
+{
+    type Sender = Sender<T>;
+    type H1Iterator = TypedH1Iter<T>;
+
+    fn channel() -> (Self::Sender, Self) {
+        ::crossbeam_channel::unbounded()
+    }
+
+    fn recv(&self) -> Result<T, RecvError> {
+        Receiver::<T>::recv(self).map_err(|_| RecvError)
+    }
+
+    fn try_recv(&self) -> Result<T, TryRecvError> {
+        Receiver::<T>::try_recv(self).map_err(|err| match err {
+            ::crossbeam_channel::TryRecvError::Empty => TryRecvError::Empty,
+            ::crossbeam_channel::TryRecvError::Disconnected => {
+                TryRecvError::Disconnected
+            }
+        })
+    }
+
+    fn iter<'a>(&'a self) -> <Self::H1Iterator as PlugLifetime<'a>>::T
+    where
+        <Self::H1Iterator as PlugLifetime<'a>>::T: StreamingIterator,
+    {
+        Receiver::<T>::iter(self)
+    }
+}
@@ -0,0 +1,33 @@
+//! Trait impls for types from the
+//! [crossbeam-utils](https://crates.io/crates/crossbeam-utils) crate.
+
+use crate::lock::CellLike;
+use ::crossbeam_utils::atomic::AtomicCell;
+
+impl<T> CellLike<T> for AtomicCell<T> {
+    fn new(x: T) -> Self {
+        AtomicCell::<T>::new(x)
+    }
+
+    fn get(&self) -> T
+    where
+        T: Copy,
+    {
+        AtomicCell::<T>::load(self)
+    }
+
+    fn set(&self, x: T) {
+        AtomicCell::<T>::store(self, x)
+    }
+
+    fn replace(&self, x: T) -> T {
+        AtomicCell::<T>::swap(self, x)
+    }
+
+    fn take(&self) -> T
+    where
+        T: Default,
+    {
+        AtomicCell::<T>::take(self)
+    }
+}
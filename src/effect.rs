@@ -0,0 +1,301 @@
+//! Effect carriers for threading state, reading an environment and writing a
+//! log.
+//!
+//! [`Writer`](struct.Writer.html) is a [`Monad`](../fp/trait.Monad.html)
+//! through [`H1Writer`](struct.H1Writer.html). [`State`](struct.State.html)
+//! and [`Reader`](struct.Reader.html) wrap boxed `'static` closures, so
+//! mapping or chaining them stores the function passed to `map` or `and_then`
+//! in a new closure, which must then be `'static` too.
+//!
+//! This makes `Functor`, `Applicative` and `Monad` impossible to implement for
+//! [`H1State`](struct.H1State.html) and [`H1Reader`](struct.H1Reader.html):
+//! the type classes in [`fp`](../fp/index.html) accept closures of any
+//! lifetime, and implementations can't require them to be `'static`. Both
+//! forms only implement [`Pure`](../fp/trait.Pure.html), with the rest
+//! provided as inherent methods on `State` and `Reader`.
+
+use crate::{
+    fp::{Applicative, Functor, Monad, Monoid, Pure},
+    plug::PlugType,
+};
+use alloc::boxed::Box;
+use core::marker::PhantomData;
+
+/// Computation producing an `A` while threading a state `S`.
+pub struct State<S, A>(Box<dyn FnOnce(S) -> (A, S)>);
+
+impl<S, A> State<S, A>
+where
+    S: 'static,
+    A: 'static,
+{
+    /// Create a computation from a state transition function.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: 'static + FnOnce(S) -> (A, S),
+    {
+        State(Box::new(f))
+    }
+
+    /// Run the computation with the initial state `s`, returning the result
+    /// and the final state.
+    pub fn run(self, s: S) -> (A, S) {
+        (self.0)(s)
+    }
+
+    /// Map the result of the computation with `f`.
+    pub fn map<B, F>(self, f: F) -> State<S, B>
+    where
+        B: 'static,
+        F: 'static + FnOnce(A) -> B,
+    {
+        State::new(move |s| {
+            let (a, s) = self.run(s);
+
+            (f(a), s)
+        })
+    }
+
+    /// Run the computation returned by `f` after this one.
+    pub fn and_then<B, F>(self, f: F) -> State<S, B>
+    where
+        B: 'static,
+        F: 'static + FnOnce(A) -> State<S, B>,
+    {
+        State::new(move |s| {
+            let (a, s) = self.run(s);
+
+            f(a).run(s)
+        })
+    }
+}
+
+impl<S> State<S, S>
+where
+    S: 'static + Clone,
+{
+    /// Computation returning the current state.
+    pub fn get() -> Self {
+        State::new(|s: S| (s.clone(), s))
+    }
+}
+
+impl<S> State<S, ()>
+where
+    S: 'static,
+{
+    /// Computation replacing the state with `s`.
+    pub fn put(s: S) -> Self {
+        State::new(move |_| ((), s))
+    }
+
+    /// Computation updating the state with `f`.
+    pub fn modify<F>(f: F) -> Self
+    where
+        F: 'static + FnOnce(S) -> S,
+    {
+        State::new(move |s| ((), f(s)))
+    }
+}
+
+/// HKT `State<S, A>` with two type slots.
+pub struct H2State;
+
+impl<S> PlugType<S> for H2State {
+    type T = H1State<S>;
+}
+
+/// HKT `State<S, A>` with a result type slot.
+///
+/// Only a [`Pure`](../fp/trait.Pure.html). See the
+/// [module documentation](index.html) for why.
+pub struct H1State<S>(PhantomData<S>);
+
+impl<S, A> PlugType<A> for H1State<S> {
+    type T = State<S, A>;
+}
+
+impl<S, A> Pure<A> for H1State<S>
+where
+    S: 'static,
+    A: 'static,
+{
+    fn pure(x: A) -> State<S, A> {
+        State::new(move |s| (x, s))
+    }
+}
+
+/// Computation producing an `A` from a shared environment `R`.
+pub struct Reader<R, A>(Box<dyn FnOnce(&R) -> A>);
+
+impl<R, A> Reader<R, A>
+where
+    R: 'static,
+    A: 'static,
+{
+    /// Create a computation from a function of the environment.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: 'static + FnOnce(&R) -> A,
+    {
+        Reader(Box::new(f))
+    }
+
+    /// Run the computation in the environment `r`.
+    pub fn run(self, r: &R) -> A {
+        (self.0)(r)
+    }
+
+    /// Map the result of the computation with `f`.
+    pub fn map<B, F>(self, f: F) -> Reader<R, B>
+    where
+        B: 'static,
+        F: 'static + FnOnce(A) -> B,
+    {
+        Reader::new(move |r| f(self.run(r)))
+    }
+
+    /// Run the computation returned by `f` after this one, in the same
+    /// environment.
+    pub fn and_then<B, F>(self, f: F) -> Reader<R, B>
+    where
+        B: 'static,
+        F: 'static + FnOnce(A) -> Reader<R, B>,
+    {
+        Reader::new(move |r| f(self.run(r)).run(r))
+    }
+}
+
+impl<R> Reader<R, R>
+where
+    R: 'static + Clone,
+{
+    /// Computation returning the environment.
+    pub fn ask() -> Self {
+        Reader::new(R::clone)
+    }
+}
+
+/// HKT `Reader<R, A>` with two type slots.
+pub struct H2Reader;
+
+impl<R> PlugType<R> for H2Reader {
+    type T = H1Reader<R>;
+}
+
+/// HKT `Reader<R, A>` with a result type slot.
+///
+/// Only a [`Pure`](../fp/trait.Pure.html). See the
+/// [module documentation](index.html) for why.
+pub struct H1Reader<R>(PhantomData<R>);
+
+impl<R, A> PlugType<A> for H1Reader<R> {
+    type T = Reader<R, A>;
+}
+
+impl<R, A> Pure<A> for H1Reader<R>
+where
+    R: 'static,
+    A: 'static,
+{
+    fn pure(x: A) -> Reader<R, A> {
+        Reader::new(move |_| x)
+    }
+}
+
+/// A value of type `A` paired with a log of type `W`.
+///
+/// Sequencing computations with
+/// [`Monad::bind`](../fp/trait.Monad.html#tymethod.bind) combines their logs
+/// with [`Semigroup`](../fp/trait.Semigroup.html).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Writer<W, A> {
+    /// The result.
+    pub value: A,
+    /// The log.
+    pub log: W,
+}
+
+impl<W> Writer<W, ()> {
+    /// Computation writing `log`.
+    pub fn tell(log: W) -> Self {
+        Writer { value: (), log }
+    }
+}
+
+impl<W, A> Writer<W, A> {
+    /// Split into the result and the log.
+    pub fn run(self) -> (A, W) {
+        (self.value, self.log)
+    }
+}
+
+/// HKT `Writer<W, A>` with two type slots.
+pub struct H2Writer;
+
+impl<W> PlugType<W> for H2Writer {
+    type T = H1Writer<W>;
+}
+
+/// HKT `Writer<W, A>` with a result type slot.
+pub struct H1Writer<W>(PhantomData<W>);
+
+impl<W, A> PlugType<A> for H1Writer<W> {
+    type T = Writer<W, A>;
+}
+
+impl<W, A, B> Functor<A, B> for H1Writer<W> {
+    fn fmap<F>(fa: Writer<W, A>, mut f: F) -> Writer<W, B>
+    where
+        F: FnMut(A) -> B,
+    {
+        Writer {
+            value: f(fa.value),
+            log: fa.log,
+        }
+    }
+}
+
+impl<W, A> Pure<A> for H1Writer<W>
+where
+    W: Monoid,
+{
+    fn pure(x: A) -> Writer<W, A> {
+        Writer {
+            value: x,
+            log: W::empty(),
+        }
+    }
+}
+
+impl<W, A, B, C> Applicative<A, B, C> for H1Writer<W>
+where
+    W: Monoid,
+{
+    fn map2<F>(fa: Writer<W, A>, fb: Writer<W, B>, mut f: F) -> Writer<W, C>
+    where
+        F: FnMut(A, B) -> C,
+    {
+        Writer {
+            value: f(fa.value, fb.value),
+            log: fa.log.combine(fb.log),
+        }
+    }
+}
+
+impl<W, A, B> Monad<A, B> for H1Writer<W>
+where
+    W: Monoid,
+{
+    fn bind<F>(fa: Writer<W, A>, mut f: F) -> Writer<W, B>
+    where
+        F: FnMut(A) -> Writer<W, B>,
+    {
+        let fb = f(fa.value);
+
+        Writer {
+            value: fb.value,
+            log: fa.log.combine(fb.log),
+        }
+    }
+}
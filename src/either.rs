@@ -0,0 +1,195 @@
+//! HKT forms and trait impls for `Either` from the
+//! [either](https://crates.io/crates/either) crate.
+//!
+//! `Either<L, R>` is a `StreamingIterator` through the blanket impl for
+//! `Iterator` when both sides are iterators over the same item type. A
+//! dedicated passthrough impl for streaming iterators would overlap with it,
+//! so lending iterators can't be combined with `Either` directly.
+
+use crate::{
+    fp::Bifunctor,
+    plug::{PlugLifetime, PlugType},
+    Sequence, SequenceMut, StreamingIterator,
+};
+use ::either::Either;
+use core::marker::PhantomData;
+
+/// HKT `Either<L, R>` with two type slots.
+pub struct H2Either;
+
+impl<L> PlugType<L> for H2Either {
+    type T = H1Either<L>;
+}
+
+/// HKT `Either<L, R>` with a right type slot.
+pub struct H1Either<L>(PhantomData<L>);
+
+impl<L, R> PlugType<R> for H1Either<L> {
+    type T = Either<L, R>;
+}
+
+/// HKT `Either<L, R>` with a left type slot.
+pub struct RightH1Either<R>(PhantomData<R>);
+
+impl<L, R> PlugType<L> for RightH1Either<R> {
+    type T = Either<L, R>;
+}
+
+/// HKT `Either<L<'a>, R<'a>>` with a lifetime slot, where `L` and `R` are HKT
+/// forms with a lifetime slot.
+pub struct H1EitherOf<L, R>(PhantomData<(L, R)>);
+
+impl<'a, L, R> PlugLifetime<'a> for H1EitherOf<L, R>
+where
+    L: PlugLifetime<'a>,
+    R: PlugLifetime<'a>,
+{
+    type T = Either<L::T, R::T>;
+}
+
+impl<A, B, C, D> Bifunctor<A, B, C, D> for H2Either {
+    fn bimap<F, G>(fab: Either<A, B>, mut f: F, mut g: G) -> Either<C, D>
+    where
+        F: FnMut(A) -> C,
+        G: FnMut(B) -> D,
+    {
+        match fab {
+            Either::Left(a) => Either::Left(f(a)),
+            Either::Right(b) => Either::Right(g(b)),
+        }
+    }
+}
+
+impl<T, L, R> Sequence<T> for Either<L, R>
+where
+    L: Sequence<T>,
+    R: Sequence<T>,
+    for<'a> <L::H1Iterator as PlugLifetime<'a>>::T: StreamingIterator,
+    for<'a> <R::H1Iterator as PlugLifetime<'a>>::T: StreamingIterator,
+{
+    type H1Iterator = H1EitherOf<L::H1Iterator, R::H1Iterator>;
+
+    fn len(&self) -> usize {
+        match self {
+            Either::Left(x) => x.len(),
+            Either::Right(x) => x.len(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Either::Left(x) => x.is_empty(),
+            Either::Right(x) => x.is_empty(),
+        }
+    }
+
+    fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        match self {
+            Either::Left(this) => this.contains(x),
+            Either::Right(this) => this.contains(x),
+        }
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        match self {
+            Either::Left(x) => x.get(index),
+            Either::Right(x) => x.get(index),
+        }
+    }
+
+    fn first(&self) -> Option<&T> {
+        match self {
+            Either::Left(x) => x.first(),
+            Either::Right(x) => x.first(),
+        }
+    }
+
+    fn last(&self) -> Option<&T> {
+        match self {
+            Either::Left(x) => x.last(),
+            Either::Right(x) => x.last(),
+        }
+    }
+
+    fn iter<'a>(&'a self) -> <Self::H1Iterator as PlugLifetime<'a>>::T
+    where
+        <Self::H1Iterator as PlugLifetime<'a>>::T: StreamingIterator,
+    {
+        match self {
+            Either::Left(x) => Either::Left(x.iter()),
+            Either::Right(x) => Either::Right(x.iter()),
+        }
+    }
+}
+
+impl<T, L, R> SequenceMut<T> for Either<L, R>
+where
+    L: SequenceMut<T>,
+    R: SequenceMut<T>,
+{
+    fn capacity(&self) -> usize {
+        match self {
+            Either::Left(x) => x.capacity(),
+            Either::Right(x) => x.capacity(),
+        }
+    }
+
+    fn clear(&mut self) {
+        match self {
+            Either::Left(x) => x.clear(),
+            Either::Right(x) => x.clear(),
+        }
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        match self {
+            Either::Left(x) => x.reserve(additional),
+            Either::Right(x) => x.reserve(additional),
+        }
+    }
+
+    fn reserve_exact(&mut self, additional: usize) {
+        match self {
+            Either::Left(x) => x.reserve_exact(additional),
+            Either::Right(x) => x.reserve_exact(additional),
+        }
+    }
+
+    fn shrink_to_fit(&mut self) {
+        match self {
+            Either::Left(x) => x.shrink_to_fit(),
+            Either::Right(x) => x.shrink_to_fit(),
+        }
+    }
+
+    fn push(&mut self, x: T) {
+        match self {
+            Either::Left(this) => this.push(x),
+            Either::Right(this) => this.push(x),
+        }
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        match self {
+            Either::Left(x) => x.pop(),
+            Either::Right(x) => x.pop(),
+        }
+    }
+
+    fn insert(&mut self, index: usize, x: T) {
+        match self {
+            Either::Left(this) => this.insert(index, x),
+            Either::Right(this) => this.insert(index, x),
+        }
+    }
+
+    fn remove(&mut self, index: usize) -> T {
+        match self {
+            Either::Left(x) => x.remove(index),
+            Either::Right(x) => x.remove(index),
+        }
+    }
+}
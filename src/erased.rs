@@ -0,0 +1,178 @@
+//! Object-safe counterparts of the collection traits.
+//!
+//! [`Sequence`](../trait.Sequence.html) and friends use HKT iterators and
+//! static constructors, so they can't be used as trait objects. The traits in
+//! this module trade those for boxed iterators, allowing different concrete
+//! collections to be stored behind a single `dyn` type:
+//!
+//! ```
+//! use generic_std::erased::DynSequenceMut;
+//! use std::collections::VecDeque;
+//!
+//! let mut sequences: Vec<Box<dyn DynSequenceMut<u32>>> =
+//!     vec![Box::new(vec![1, 2]), Box::new(VecDeque::from(vec![3]))];
+//! for sequence in &mut sequences {
+//!     sequence.push(10);
+//! }
+//!
+//! let sums: Vec<u32> = sequences.iter().map(|s| s.iter().sum()).collect();
+//! assert_eq!(sums, [13, 13]);
+//! ```
+
+use crate::{Sequence, SequenceMut};
+use alloc::boxed::Box;
+
+/// Object-safe combination of [`Sequence`](../trait.Sequence.html) and
+/// [`SequenceMut`](../trait.SequenceMut.html).
+///
+/// Implemented for all types implementing both traits. Iteration goes through
+/// [`Sequence::get`](../trait.Sequence.html#tymethod.get), so it is only as
+/// fast as indexing.
+pub trait DynSequenceMut<T> {
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq;
+
+    fn get(&self, index: usize) -> Option<&T>;
+
+    fn first(&self) -> Option<&T>;
+
+    fn last(&self) -> Option<&T>;
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_>;
+
+    fn capacity(&self) -> usize;
+
+    fn clear(&mut self);
+
+    fn reserve(&mut self, additional: usize);
+
+    fn reserve_exact(&mut self, additional: usize);
+
+    fn shrink_to_fit(&mut self);
+
+    fn push(&mut self, x: T);
+
+    fn pop(&mut self) -> Option<T>;
+
+    fn insert(&mut self, index: usize, x: T);
+
+    fn remove(&mut self, index: usize) -> T;
+}
+
+impl<T, S> DynSequenceMut<T> for S
+where
+    S: Sequence<T> + SequenceMut<T>,
+{
+    fn len(&self) -> usize {
+        Sequence::<T>::len(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        Sequence::<T>::is_empty(self)
+    }
+
+    fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        Sequence::<T>::contains(self, x)
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        Sequence::<T>::get(self, index)
+    }
+
+    fn first(&self) -> Option<&T> {
+        Sequence::<T>::first(self)
+    }
+
+    fn last(&self) -> Option<&T> {
+        Sequence::<T>::last(self)
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(
+            (0..Sequence::<T>::len(self))
+                .filter_map(move |i| Sequence::<T>::get(self, i)),
+        )
+    }
+
+    fn capacity(&self) -> usize {
+        SequenceMut::<T>::capacity(self)
+    }
+
+    fn clear(&mut self) {
+        SequenceMut::<T>::clear(self)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        SequenceMut::<T>::reserve(self, additional)
+    }
+
+    fn reserve_exact(&mut self, additional: usize) {
+        SequenceMut::<T>::reserve_exact(self, additional)
+    }
+
+    fn shrink_to_fit(&mut self) {
+        SequenceMut::<T>::shrink_to_fit(self)
+    }
+
+    fn push(&mut self, x: T) {
+        SequenceMut::<T>::push(self, x)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        SequenceMut::<T>::pop(self)
+    }
+
+    fn insert(&mut self, index: usize, x: T) {
+        SequenceMut::<T>::insert(self, index, x)
+    }
+
+    fn remove(&mut self, index: usize) -> T {
+        SequenceMut::<T>::remove(self, index)
+    }
+}
+
+/// Object-safe trait for maps from `K` to `V`.
+///
+/// Lookups take a `&K` rather than any borrowed form of the key, as generic
+/// methods can't be called through a trait object.
+pub trait DynMap<K, V> {
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn contains_key(&self, k: &K) -> bool {
+        self.get(k).is_some()
+    }
+
+    fn get(&self, k: &K) -> Option<&V>;
+
+    fn get_mut(&mut self, k: &K) -> Option<&mut V>;
+
+    /// Returns the previous value for `k`, if any.
+    fn insert(&mut self, k: K, v: V) -> Option<V>;
+
+    fn remove(&mut self, k: &K) -> Option<V>;
+
+    fn clear(&mut self);
+
+    /// Iterates over all entries in the map's own order.
+    fn iter(&self) -> Box<dyn Iterator<Item = (&K, &V)> + '_>;
+
+    fn iter_mut(&mut self) -> Box<dyn Iterator<Item = (&K, &mut V)> + '_>;
+
+    fn keys(&self) -> Box<dyn Iterator<Item = &K> + '_>;
+
+    fn values(&self) -> Box<dyn Iterator<Item = &V> + '_>;
+}
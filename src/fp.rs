@@ -0,0 +1,482 @@
+//! Type classes from functional programming over HKT forms.
+//!
+//! These are implemented on HKT forms rather than concrete types. For example,
+//! `Option<T>` is a functor through
+//! [`H1Option`](../option/struct.H1Option.html):
+//!
+//! ```
+//! use generic_std::{fp::Functor, option::H1Option};
+//!
+//! assert_eq!(H1Option::fmap(Some(2), |x| x * 2), Some(4));
+//! ```
+//!
+//! Rust has no way to quantify over all types, so type classes take the types
+//! involved as trait parameters instead of method parameters. Generic code
+//! must then require the type class for each combination of types it uses.
+
+use crate::{
+    option::H1Option,
+    plug::{PlugType, PlugType2},
+    result::ErrH1Result,
+};
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+use core::{
+    iter,
+    ops::{Add, Mul},
+};
+
+/// Type class for HKT forms whose values can be mapped over, such as
+/// containers.
+pub trait Functor<A, B>: PlugType<A> + PlugType<B> {
+    /// Map each `A` in `fa` to a `B` with `f`, preserving the structure.
+    fn fmap<F>(fa: <Self as PlugType<A>>::T, f: F) -> <Self as PlugType<B>>::T
+    where
+        F: FnMut(A) -> B;
+}
+
+/// Type class for HKT forms that can wrap a single value.
+pub trait Pure<A>: PlugType<A> {
+    /// Wrap `x` in the minimal structure, such as `Some(x)` or `vec![x]`.
+    fn pure(x: A) -> <Self as PlugType<A>>::T;
+}
+
+/// Type class for HKT forms whose values can be combined, such as containers.
+pub trait Applicative<A, B, C>:
+    PlugType<A> + PlugType<B> + PlugType<C>
+{
+    /// Combine each `A` in `fa` with each `B` in `fb` using `f`.
+    ///
+    /// For containers of multiple values, such as `Vec`, this is the cartesian
+    /// product.
+    fn map2<F>(
+        fa: <Self as PlugType<A>>::T,
+        fb: <Self as PlugType<B>>::T,
+        f: F,
+    ) -> <Self as PlugType<C>>::T
+    where
+        F: FnMut(A, B) -> C;
+}
+
+/// Type class for HKT forms whose values can be chained, where each step
+/// decides the structure of the next.
+pub trait Monad<A, B>: Functor<A, B> + Pure<A> + Pure<B> {
+    /// Map each `A` in `fa` to a new structure with `f` and flatten the
+    /// results. Also known as `and_then` or `flat_map`.
+    fn bind<F>(fa: <Self as PlugType<A>>::T, f: F) -> <Self as PlugType<B>>::T
+    where
+        F: FnMut(A) -> <Self as PlugType<B>>::T;
+}
+
+/// Natural transformation from the form `Self` to the form `G`, converting
+/// the container without looking at its elements. For example, `Vec<T>` can
+/// be transformed into `Option<T>` by keeping only the first element.
+///
+/// Implementations must commute with `fmap`: mapping then transforming must
+/// give the same result as transforming then mapping.
+pub trait Transform<G, T>: PlugType<T>
+where
+    G: PlugType<T>,
+{
+    /// Convert `fa` into the form `G`.
+    fn transform(fa: <Self as PlugType<T>>::T) -> <G as PlugType<T>>::T;
+}
+
+/// Type class for HKT forms with a notion of choice between two values and an
+/// empty value that never succeeds.
+///
+/// Implementations must satisfy `Self::or(Self::empty(), x) == x`,
+/// `Self::or(x, Self::empty()) == x` and `or` must be associative.
+pub trait Alternative<A>: PlugType<A> {
+    /// The value with no results.
+    fn empty() -> <Self as PlugType<A>>::T;
+
+    /// Choose between `x` and the fallback `y`.
+    fn or(
+        x: <Self as PlugType<A>>::T,
+        y: <Self as PlugType<A>>::T,
+    ) -> <Self as PlugType<A>>::T;
+}
+
+/// Monads that are also [`Alternative`](trait.Alternative.html). Implemented
+/// for every form that is both.
+pub trait MonadPlus<A, B>:
+    Monad<A, B> + Alternative<A> + Alternative<B>
+{
+}
+
+impl<K, A, B> MonadPlus<A, B> for K where
+    K: Monad<A, B> + Alternative<A> + Alternative<B>
+{
+}
+
+/// Choose between all values in `xs`, in order.
+pub fn choice<K, A, I>(xs: I) -> <K as PlugType<A>>::T
+where
+    K: Alternative<A>,
+    I: IntoIterator<Item = <K as PlugType<A>>::T>,
+{
+    xs.into_iter().fold(K::empty(), K::or)
+}
+
+/// Types with an associative operation to combine two values into one.
+///
+/// Implementations must satisfy `a.combine(b).combine(c) ==
+/// a.combine(b.combine(c))`.
+pub trait Semigroup {
+    /// Combine `self` with `other`.
+    fn combine(self, other: Self) -> Self;
+}
+
+/// Semigroups with an identity element.
+///
+/// Implementations must satisfy `Self::empty().combine(a) == a` and
+/// `a.combine(Self::empty()) == a`.
+pub trait Monoid: Semigroup {
+    /// The identity element for `combine`.
+    fn empty() -> Self;
+}
+
+/// Monoid wrapper that combines numbers by addition.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Sum<T>(pub T);
+
+impl<T> Semigroup for Sum<T>
+where
+    T: Add<Output = T>,
+{
+    fn combine(self, other: Self) -> Self {
+        Sum(self.0 + other.0)
+    }
+}
+
+impl<T> Monoid for Sum<T>
+where
+    T: Add<Output = T> + iter::Sum,
+{
+    fn empty() -> Self {
+        Sum(iter::empty::<T>().sum())
+    }
+}
+
+/// Monoid wrapper that combines numbers by multiplication.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Product<T>(pub T);
+
+impl<T> Semigroup for Product<T>
+where
+    T: Mul<Output = T>,
+{
+    fn combine(self, other: Self) -> Self {
+        Product(self.0 * other.0)
+    }
+}
+
+impl<T> Monoid for Product<T>
+where
+    T: Mul<Output = T> + iter::Product,
+{
+    fn empty() -> Self {
+        Product(iter::empty::<T>().product())
+    }
+}
+
+/// Concatenation.
+#[cfg(feature = "alloc")]
+impl Semigroup for String {
+    #[inline]
+    fn combine(mut self, other: Self) -> Self {
+        self.push_str(&other);
+
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Monoid for String {
+    #[inline]
+    fn empty() -> Self {
+        String::new()
+    }
+}
+
+/// Type class for HKT forms whose values can be reduced to a summary, such as
+/// containers.
+///
+/// Implemented for every form whose concrete type is an `IntoIterator` over
+/// the plugged type, which includes all `std` collections, `Option` and
+/// `Result`.
+pub trait Foldable<A>: PlugType<A> {
+    /// Combine the elements of `fa` in order, starting with `init`.
+    fn fold<B, F>(fa: <Self as PlugType<A>>::T, init: B, f: F) -> B
+    where
+        F: FnMut(B, A) -> B;
+
+    /// Map each element of `fa` to a summary with `f` and combine them in
+    /// order, starting with the empty summary.
+    fn fold_map<M, F>(fa: <Self as PlugType<A>>::T, mut f: F) -> M
+    where
+        F: FnMut(A) -> M,
+        M: Monoid,
+    {
+        Self::fold(fa, M::empty(), |m, x| m.combine(f(x)))
+    }
+
+    /// Count the elements of `fa`.
+    fn length(fa: <Self as PlugType<A>>::T) -> usize {
+        Self::fold(fa, 0, |n, _| n + 1)
+    }
+
+    /// Collect the elements of `fa` into a `Vec`, in order.
+    #[cfg(feature = "alloc")]
+    fn to_vec(fa: <Self as PlugType<A>>::T) -> Vec<A> {
+        Self::fold(fa, Vec::new(), |mut vec, x| {
+            vec.push(x);
+
+            vec
+        })
+    }
+}
+
+impl<K, A> Foldable<A> for K
+where
+    K: PlugType<A>,
+    <K as PlugType<A>>::T: IntoIterator<Item = A>,
+{
+    fn fold<B, F>(fa: <Self as PlugType<A>>::T, init: B, f: F) -> B
+    where
+        F: FnMut(B, A) -> B,
+    {
+        fa.into_iter().fold(init, f)
+    }
+
+    fn length(fa: <Self as PlugType<A>>::T) -> usize {
+        fa.into_iter().count()
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_vec(fa: <Self as PlugType<A>>::T) -> Vec<A> {
+        fa.into_iter().collect()
+    }
+}
+
+/// Type class for HKT forms whose elements can be visited with an effect `M`,
+/// collecting the results inside a single `M`. For example, `Vec<A>` can be
+/// traversed with a function `A -> Option<B>` into an `Option<Vec<B>>`.
+pub trait Traversable<M, A, B>: PlugType<A> + PlugType<B>
+where
+    M: PlugType<B> + PlugType<<Self as PlugType<B>>::T>,
+{
+    /// Map each element of `ta` to an effect with `f` and combine the effects
+    /// in order.
+    fn traverse<F>(
+        ta: <Self as PlugType<A>>::T,
+        f: F,
+    ) -> <M as PlugType<<Self as PlugType<B>>::T>>::T
+    where
+        F: FnMut(A) -> <M as PlugType<B>>::T;
+}
+
+/// Turn a structure of effects into an effect of structures, such as
+/// `Vec<Option<B>>` into `Option<Vec<B>>`.
+pub fn sequence<K, M, B>(
+    tmb: <K as PlugType<<M as PlugType<B>>::T>>::T,
+) -> <M as PlugType<<K as PlugType<B>>::T>>::T
+where
+    K: Traversable<M, <M as PlugType<B>>::T, B>,
+    M: PlugType<B> + PlugType<<K as PlugType<B>>::T>,
+{
+    K::traverse(tmb, |mb| mb)
+}
+
+/// Collect a structure of options into an option of the structure, such as
+/// `Vec<Option<T>>` into `Option<Vec<T>>`. Returns `None` if any element is
+/// `None`.
+pub fn collect_option<K, T>(
+    to: <K as PlugType<Option<T>>>::T,
+) -> Option<<K as PlugType<T>>::T>
+where
+    K: Traversable<H1Option, Option<T>, T>,
+{
+    sequence::<K, H1Option, T>(to)
+}
+
+/// Collect a structure of results into a result of the structure, such as
+/// `Vec<Result<T, E>>` into `Result<Vec<T>, E>`. Returns the first error, if
+/// any.
+pub fn collect_result<K, T, E>(
+    tr: <K as PlugType<Result<T, E>>>::T,
+) -> Result<<K as PlugType<T>>::T, E>
+where
+    K: Traversable<ErrH1Result<E>, Result<T, E>, T>,
+{
+    sequence::<K, ErrH1Result<E>, T>(tr)
+}
+
+/// Compose two effectful functions, feeding each result of `f` into `g`. This
+/// is function composition for functions returning a monad, also known as
+/// Kleisli composition.
+pub fn compose_k<K, A, B, C, F, G>(
+    mut f: F,
+    mut g: G,
+) -> impl FnMut(A) -> <K as PlugType<C>>::T
+where
+    K: Monad<B, C>,
+    F: FnMut(A) -> <K as PlugType<B>>::T,
+    G: FnMut(B) -> <K as PlugType<C>>::T,
+{
+    move |a| K::bind(f(a), &mut g)
+}
+
+/// Type class for HKT forms with two type slots whose values can be mapped
+/// over on both sides, such as `Result` and tuples.
+///
+/// Slots are filled left to right, so for `Result<T, E>` the left side is the
+/// success type and the right side is the error type.
+pub trait Bifunctor<A, B, C, D>: PlugType2<A, B> + PlugType2<C, D> {
+    /// Map the left side of `fab` with `f` and the right side with `g`.
+    fn bimap<F, G>(
+        fab: <Self as PlugType2<A, B>>::T,
+        f: F,
+        g: G,
+    ) -> <Self as PlugType2<C, D>>::T
+    where
+        F: FnMut(A) -> C,
+        G: FnMut(B) -> D;
+}
+
+/// Map the left side of `fab` with `f`.
+pub fn map_left<K, A, B, C, F>(
+    fab: <K as PlugType2<A, B>>::T,
+    f: F,
+) -> <K as PlugType2<C, B>>::T
+where
+    K: Bifunctor<A, B, C, B>,
+    F: FnMut(A) -> C,
+{
+    K::bimap(fab, f, |b| b)
+}
+
+/// Map the right side of `fab` with `f`.
+pub fn map_right<K, A, B, D, F>(
+    fab: <K as PlugType2<A, B>>::T,
+    f: F,
+) -> <K as PlugType2<A, D>>::T
+where
+    K: Bifunctor<A, B, A, D>,
+    F: FnMut(B) -> D,
+{
+    K::bimap(fab, |a| a, f)
+}
+
+/// Type class for HKT forms that consume values of the plugged type, such as
+/// predicates and comparators.
+///
+/// Where a [`Functor`](trait.Functor.html) maps the values it produces, a
+/// contravariant functor maps the values it takes as arguments.
+pub trait Contravariant<A, B>: PlugType<A> + PlugType<B> {
+    /// Adapt `fa` to take `B` by converting each argument into an `A` with
+    /// `f`.
+    fn contramap<F>(
+        fa: <Self as PlugType<A>>::T,
+        f: F,
+    ) -> <Self as PlugType<B>>::T
+    where
+        F: 'static + Fn(&B) -> A;
+}
+
+/// Type class for HKT forms with two type slots that consume values of the
+/// left type and produce values of the right type, such as functions.
+pub trait Profunctor<A, B, C, D>: PlugType2<A, B> + PlugType2<C, D> {
+    /// Adapt `fab` by converting its input with `f` and its output with `g`.
+    fn dimap<F, G>(
+        fab: <Self as PlugType2<A, B>>::T,
+        f: F,
+        g: G,
+    ) -> <Self as PlugType2<C, D>>::T
+    where
+        F: 'static + Fn(C) -> A,
+        G: 'static + Fn(B) -> D;
+}
+
+/// Adapt the input of `fab` with `f`.
+pub fn lmap<K, A, B, C, F>(
+    fab: <K as PlugType2<A, B>>::T,
+    f: F,
+) -> <K as PlugType2<C, B>>::T
+where
+    K: Profunctor<A, B, C, B>,
+    F: 'static + Fn(C) -> A,
+{
+    K::dimap(fab, f, |b| b)
+}
+
+/// Adapt the output of `fab` with `f`.
+pub fn rmap<K, A, B, D, F>(
+    fab: <K as PlugType2<A, B>>::T,
+    f: F,
+) -> <K as PlugType2<A, D>>::T
+where
+    K: Profunctor<A, B, A, D>,
+    F: 'static + Fn(B) -> D,
+{
+    K::dimap(fab, |a| a, f)
+}
+
+/// Combine `fa` and `fb` with `f`. Shorthand for
+/// [`Applicative::map2`](trait.Applicative.html#tymethod.map2).
+pub fn map2<K, A, B, C, F>(
+    fa: <K as PlugType<A>>::T,
+    fb: <K as PlugType<B>>::T,
+    f: F,
+) -> <K as PlugType<C>>::T
+where
+    K: Applicative<A, B, C>,
+    F: FnMut(A, B) -> C,
+{
+    K::map2(fa, fb, f)
+}
+
+/// Type class for HKT forms whose values can be combined position by
+/// position.
+///
+/// For single-valued forms such as `Option` this is the same as
+/// [`Applicative::map2`](trait.Applicative.html#tymethod.map2). For `Vec`,
+/// where `map2` combines every pair of elements, `zip_with` combines the
+/// elements at the same index instead.
+pub trait Zip<A, B, C>: PlugType<A> + PlugType<B> + PlugType<C> {
+    /// Combine the elements of `fa` and `fb` at the same position with `f`.
+    fn zip_with<F>(
+        fa: <Self as PlugType<A>>::T,
+        fb: <Self as PlugType<B>>::T,
+        f: F,
+    ) -> <Self as PlugType<C>>::T
+    where
+        F: FnMut(A, B) -> C;
+}
+
+/// Combine the elements of `fa` and `fb` at the same position with `f`.
+/// Shorthand for [`Zip::zip_with`](trait.Zip.html#tymethod.zip_with).
+pub fn zip_with<K, A, B, C, F>(
+    fa: <K as PlugType<A>>::T,
+    fb: <K as PlugType<B>>::T,
+    f: F,
+) -> <K as PlugType<C>>::T
+where
+    K: Zip<A, B, C>,
+    F: FnMut(A, B) -> C,
+{
+    K::zip_with(fa, fb, f)
+}
+
+/// Apply each function in `ff` to each value in `fa`.
+pub fn apply<K, F, A, B>(
+    ff: <K as PlugType<F>>::T,
+    fa: <K as PlugType<A>>::T,
+) -> <K as PlugType<B>>::T
+where
+    K: Applicative<F, A, B>,
+    F: FnMut(A) -> B,
+{
+    K::map2(ff, fa, |mut f, a| f(a))
+}
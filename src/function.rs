@@ -0,0 +1,84 @@
+//! HKT forms for boxed closures.
+
+use crate::{
+    fp::{Contravariant, Profunctor},
+    plug::PlugType,
+};
+use alloc::boxed::Box;
+use core::{cmp::Ordering, marker::PhantomData};
+
+/// HKT `Box<dyn Fn(A) -> B>` with two type slots.
+pub struct H2Fn;
+
+impl<A> PlugType<A> for H2Fn {
+    type T = H1Fn<A>;
+}
+
+/// HKT `Box<dyn Fn(A) -> B>` with an output type slot.
+pub struct H1Fn<A>(PhantomData<A>);
+
+impl<A, B> PlugType<B> for H1Fn<A> {
+    type T = Box<dyn Fn(A) -> B>;
+}
+
+impl<A, B, C, D> Profunctor<A, B, C, D> for H2Fn
+where
+    A: 'static,
+    B: 'static,
+    C: 'static,
+    D: 'static,
+{
+    fn dimap<F, G>(fab: Box<dyn Fn(A) -> B>, f: F, g: G) -> Box<dyn Fn(C) -> D>
+    where
+        F: 'static + Fn(C) -> A,
+        G: 'static + Fn(B) -> D,
+    {
+        Box::new(move |c| g(fab(f(c))))
+    }
+}
+
+/// HKT `Box<dyn Fn(&T) -> bool>` with a type slot.
+pub struct H1Predicate;
+
+impl<T> PlugType<T> for H1Predicate {
+    type T = Box<dyn Fn(&T) -> bool>;
+}
+
+impl<A, B> Contravariant<A, B> for H1Predicate
+where
+    A: 'static,
+    B: 'static,
+{
+    fn contramap<F>(
+        fa: Box<dyn Fn(&A) -> bool>,
+        f: F,
+    ) -> Box<dyn Fn(&B) -> bool>
+    where
+        F: 'static + Fn(&B) -> A,
+    {
+        Box::new(move |b| fa(&f(b)))
+    }
+}
+
+/// HKT `Box<dyn Fn(&T, &T) -> Ordering>` with a type slot.
+pub struct H1Comparator;
+
+impl<T> PlugType<T> for H1Comparator {
+    type T = Box<dyn Fn(&T, &T) -> Ordering>;
+}
+
+impl<A, B> Contravariant<A, B> for H1Comparator
+where
+    A: 'static,
+    B: 'static,
+{
+    fn contramap<F>(
+        fa: Box<dyn Fn(&A, &A) -> Ordering>,
+        f: F,
+    ) -> Box<dyn Fn(&B, &B) -> Ordering>
+    where
+        F: 'static + Fn(&B) -> A,
+    {
+        Box::new(move |x, y| fa(&f(x), &f(y)))
+    }
+}
@@ -0,0 +1,119 @@
+//! Adapters for streams from the
+//! [futures-core](https://crates.io/crates/futures-core) crate.
+
+use crate::{
+    plug::{PlugLifetime, H0},
+    stream::{NextFuture, PollStreamingIterator, StreamingStream},
+};
+use ::futures_core::Stream;
+use core::{
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Streaming stream yielding the items of a `futures_core::Stream`. See
+/// [`from_stream`](fn.from_stream.html).
+pub struct FromStream<S>(S);
+
+/// Wrap `s` as a streaming stream. Items are owned, so they don't borrow from
+/// the stream.
+pub fn from_stream<S>(s: S) -> FromStream<S>
+where
+    S: Stream + Unpin,
+{
+    FromStream(s)
+}
+
+impl<S> FromStream<S> {
+    /// Unwrap the underlying stream.
+    pub fn into_inner(self) -> S {
+        self.0
+    }
+}
+
+impl<S> StreamingStream for FromStream<S>
+where
+    S: Stream + Unpin + 'static,
+{
+    type H1Item = H0<S::Item>;
+    type H1NextFuture = TypedH1Next<S>;
+
+    fn next<'a>(&'a mut self) -> <Self::H1NextFuture as PlugLifetime<'a>>::T
+    where
+        <Self::H1NextFuture as PlugLifetime<'a>>::T:
+            NextFuture<'a, Self::H1Item>,
+    {
+        Next(&mut self.0)
+    }
+}
+
+/// Future resolving to the next item of a stream.
+pub struct Next<'a, S>(&'a mut S);
+
+impl<S> Future for Next<'_, S>
+where
+    S: Stream + Unpin,
+{
+    type Output = Option<S::Item>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        Pin::new(&mut *self.0).poll_next(cx)
+    }
+}
+
+/// HKT `Next<'a, S>` with a lifetime slot.
+pub struct TypedH1Next<S>(PhantomData<S>);
+
+impl<'a, S> PlugLifetime<'a> for TypedH1Next<S>
+where
+    S: 'a,
+{
+    type T = Next<'a, S>;
+}
+
+impl<S> PollStreamingIterator for FromStream<S>
+where
+    S: Stream + Unpin,
+{
+    type H1Item = H0<S::Item>;
+
+    fn poll_next<'a>(
+        self: Pin<&'a mut Self>,
+        cx: &mut Context,
+    ) -> Poll<Option<<Self::H1Item as PlugLifetime<'a>>::T>> {
+        Pin::new(&mut self.get_mut().0).poll_next(cx)
+    }
+}
+
+/// `futures_core::Stream` yielding the owned items of a poll-based streaming
+/// iterator. See [`into_stream`](fn.into_stream.html).
+pub struct IntoStream<P>(P);
+
+/// Wrap a poll-based streaming iterator with owned items as a
+/// `futures_core::Stream`.
+pub fn into_stream<P, T>(p: P) -> IntoStream<P>
+where
+    P: PollStreamingIterator<H1Item = H0<T>> + Unpin,
+{
+    IntoStream(p)
+}
+
+impl<P> IntoStream<P> {
+    /// Unwrap the poll-based streaming iterator.
+    pub fn into_inner(self) -> P {
+        self.0
+    }
+}
+
+impl<P, T> Stream for IntoStream<P>
+where
+    P: PollStreamingIterator<H1Item = H0<T>> + Unpin,
+{
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<T>> {
+        Pin::new(&mut self.get_mut().0).poll_next(cx)
+    }
+}
@@ -0,0 +1,202 @@
+//! Heterogeneous lists and coproducts.
+//!
+//! An [`HCons`](struct.HCons.html) list holds one value of each type in the
+//! list, while a [`Coproduct`](enum.Coproduct.html) holds exactly one value of
+//! any of the types. Lists of HKT forms can be plugged all at once with
+//! [`PlugAll`](trait.PlugAll.html) and [`PlugAny`](trait.PlugAny.html), so a
+//! struct can be generic over any number of independently chosen kinds:
+//!
+//! ```
+//! use generic_std::{
+//!     hlist::{HCons, HList, HNil, PlugAll, Selector},
+//!     option::H1Option,
+//!     vec::H1Vec,
+//! };
+//!
+//! struct Slots<Ks: PlugAll<u32>> {
+//!     slots: Ks::T,
+//! }
+//!
+//! let mut x = Slots::<HCons<H1Vec, HCons<H1Option, HNil>>> {
+//!     slots: HNil.prepend(None).prepend(vec![1]),
+//! };
+//! Selector::<Vec<u32>, _>::get_mut(&mut x.slots).push(2);
+//! *x.slots.get_mut() = Some(3);
+//! assert_eq!(Selector::<Vec<u32>, _>::get(&x.slots), &[1, 2]);
+//! assert_eq!(Selector::<Option<u32>, _>::get(&x.slots), &Some(3));
+//! ```
+
+use crate::plug::PlugType;
+use core::marker::PhantomData;
+
+/// Trait implemented by heterogeneous lists.
+pub trait HList {
+    /// Number of elements in the list.
+    const LEN: usize;
+
+    /// Add `head` to the start of the list.
+    fn prepend<H>(self, head: H) -> HCons<H, Self>
+    where
+        Self: Sized,
+    {
+        HCons { head, tail: self }
+    }
+}
+
+/// The empty heterogeneous list.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HNil;
+
+impl HList for HNil {
+    const LEN: usize = 0;
+}
+
+/// Heterogeneous list with a first element of type `H` followed by the list
+/// `T`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HCons<H, T> {
+    /// The first element.
+    pub head: H,
+    /// The remaining elements.
+    pub tail: T,
+}
+
+impl<H, T> HList for HCons<H, T>
+where
+    T: HList,
+{
+    const LEN: usize = T::LEN + 1;
+}
+
+/// Index of the first element of a list or coproduct. See
+/// [`Selector`](trait.Selector.html).
+pub struct Here;
+
+/// Index of an element after the first in a list or coproduct. See
+/// [`Selector`](trait.Selector.html).
+pub struct There<I>(PhantomData<I>);
+
+/// Access to the element of type `U` of a heterogeneous list. The index `I`
+/// is always inferred.
+pub trait Selector<U, I> {
+    /// Borrow the element of type `U`.
+    fn get(&self) -> &U;
+
+    /// Mutably borrow the element of type `U`.
+    fn get_mut(&mut self) -> &mut U;
+}
+
+impl<U, T> Selector<U, Here> for HCons<U, T> {
+    fn get(&self) -> &U {
+        &self.head
+    }
+
+    fn get_mut(&mut self) -> &mut U {
+        &mut self.head
+    }
+}
+
+impl<U, H, T, I> Selector<U, There<I>> for HCons<H, T>
+where
+    T: Selector<U, I>,
+{
+    fn get(&self) -> &U {
+        self.tail.get()
+    }
+
+    fn get_mut(&mut self) -> &mut U {
+        self.tail.get_mut()
+    }
+}
+
+/// The empty coproduct, which has no values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CNil {}
+
+/// Coproduct holding either a value of type `H` or one of the coproduct `T`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Coproduct<H, T> {
+    /// A value of the first type.
+    Inl(H),
+    /// A value of one of the remaining types.
+    Inr(T),
+}
+
+/// Conversions between a coproduct and its variant of type `U`. The index `I`
+/// is always inferred.
+pub trait Injector<U, I>: Sized {
+    /// Create the coproduct holding `x`.
+    fn inject(x: U) -> Self;
+
+    /// Take the value of type `U`, or return the coproduct unchanged if it
+    /// holds a value of another type.
+    fn uninject(self) -> Result<U, Self>;
+}
+
+impl<U, T> Injector<U, Here> for Coproduct<U, T> {
+    fn inject(x: U) -> Self {
+        Coproduct::Inl(x)
+    }
+
+    fn uninject(self) -> Result<U, Self> {
+        match self {
+            Coproduct::Inl(x) => Ok(x),
+            x => Err(x),
+        }
+    }
+}
+
+impl<U, H, T, I> Injector<U, There<I>> for Coproduct<H, T>
+where
+    T: Injector<U, I>,
+{
+    fn inject(x: U) -> Self {
+        Coproduct::Inr(T::inject(x))
+    }
+
+    fn uninject(self) -> Result<U, Self> {
+        match self {
+            Coproduct::Inr(x) => x.uninject().map_err(Coproduct::Inr),
+            x => Err(x),
+        }
+    }
+}
+
+/// Trait for type-level lists of HKT forms, such as
+/// `HCons<H1Vec, HCons<H1Option, HNil>>`, that plugs `T` into every form and
+/// yields the heterogeneous list of the results.
+pub trait PlugAll<T> {
+    /// The list of plugged types.
+    type T: HList;
+}
+
+impl<T> PlugAll<T> for HNil {
+    type T = HNil;
+}
+
+impl<T, K, Ks> PlugAll<T> for HCons<K, Ks>
+where
+    K: PlugType<T>,
+    Ks: PlugAll<T>,
+{
+    type T = HCons<<K as PlugType<T>>::T, <Ks as PlugAll<T>>::T>;
+}
+
+/// Trait for type-level lists of HKT forms that plugs `T` into every form and
+/// yields the coproduct of the results.
+pub trait PlugAny<T> {
+    /// The coproduct of plugged types.
+    type T;
+}
+
+impl<T> PlugAny<T> for HNil {
+    type T = CNil;
+}
+
+impl<T, K, Ks> PlugAny<T> for HCons<K, Ks>
+where
+    K: PlugType<T>,
+    Ks: PlugAny<T>,
+{
+    type T = Coproduct<<K as PlugType<T>>::T, <Ks as PlugAny<T>>::T>;
+}
@@ -0,0 +1,303 @@
+//! HKT forms for the iterators in `std::iter` and adapters for
+//! [`StreamingIterator`](../trait.StreamingIterator.html).
+//!
+//! Like any form over an `IntoIterator`, these forms are
+//! [`Foldable`](../fp/trait.Foldable.html). Folding an infinite iterator such
+//! as `Repeat` never returns.
+//!
+//! Only iterators whose items can be mapped up front are
+//! [`Functor`](../fp/trait.Functor.html)s. Lazy adapters such as `Map` would
+//! have to store the mapping function, and `fmap` accepts functions that
+//! can't outlive the call, so they have no forms here.
+
+use crate::{
+    fp::{Functor, Pure},
+    plug::{PlugLifetime, PlugType, H0},
+    StreamingIterator,
+};
+use core::iter::{self, Empty, Once, Repeat};
+
+/// HKT `std::iter::Empty<T>` with a type slot.
+pub struct H1Empty;
+
+impl<T> PlugType<T> for H1Empty {
+    type T = Empty<T>;
+}
+
+impl<A, B> Functor<A, B> for H1Empty {
+    fn fmap<F>(_: Empty<A>, _: F) -> Empty<B>
+    where
+        F: FnMut(A) -> B,
+    {
+        iter::empty()
+    }
+}
+
+/// HKT `std::iter::Repeat<T>` with a type slot.
+pub struct H1Repeat;
+
+impl<T> PlugType<T> for H1Repeat {
+    type T = Repeat<T>;
+}
+
+/// Maps the repeated element once, so the result stays infinite.
+impl<A, B> Functor<A, B> for H1Repeat
+where
+    A: Clone,
+    B: Clone,
+{
+    fn fmap<F>(mut fa: Repeat<A>, mut f: F) -> Repeat<B>
+    where
+        F: FnMut(A) -> B,
+    {
+        iter::repeat(f(Iterator::next(&mut fa).unwrap()))
+    }
+}
+
+impl<A> Pure<A> for H1Repeat
+where
+    A: Clone,
+{
+    fn pure(x: A) -> Repeat<A> {
+        iter::repeat(x)
+    }
+}
+
+/// HKT `std::iter::Once<T>` with a type slot.
+///
+/// Not a [`Functor`](../fp/trait.Functor.html), as an exhausted `Once<B>`
+/// can't be built without a `B`. Use
+/// [`H1OptionIntoIter`](../option/struct.H1OptionIntoIter.html) for an
+/// iterator over at most one value that can be mapped.
+pub struct H1Once;
+
+impl<T> PlugType<T> for H1Once {
+    type T = Once<T>;
+}
+
+impl<A> Pure<A> for H1Once {
+    fn pure(x: A) -> Once<A> {
+        iter::once(x)
+    }
+}
+
+/// Adapters for [`StreamingIterator`](../trait.StreamingIterator.html).
+///
+/// Except for `map`, the adapters yield the items of the underlying iterator
+/// unchanged, so items borrowing from the iterator can still be used:
+///
+/// ```
+/// use generic_std::{
+///     iter::StreamingIteratorExt, plug::PlugLifetime, StreamingIterator,
+/// };
+///
+/// fn lengths<I>(lines: I) -> Vec<usize>
+/// where
+///     I: StreamingIterator,
+///     for<'a> <I::H1Item as PlugLifetime<'a>>::T: AsRef<str>,
+/// {
+///     let mut lengths = lines
+///         .skip_while(|line| line.as_ref().starts_with('#'))
+///         .filter(|line| !line.as_ref().is_empty())
+///         .map(|line| line.as_ref().len());
+///
+///     let mut v = Vec::new();
+///     while let Some(n) = lengths.next() {
+///         v.push(n);
+///     }
+///
+///     v
+/// }
+///
+/// let lines = ["# header", "a", "", "bc", "# d"];
+/// assert_eq!(lengths(lines.iter()), [1, 2, 3]);
+/// ```
+///
+/// As every `Iterator` is a `StreamingIterator`, this trait makes calls such
+/// as `map` on iterators ambiguous, so it is best imported only where needed.
+pub trait StreamingIteratorExt: StreamingIterator {
+    /// Map each item to an owned value with `f`.
+    fn map<B, F>(self, f: F) -> Map<Self, F>
+    where
+        Self: Sized,
+        F: for<'a> FnMut(<Self::H1Item as PlugLifetime<'a>>::T) -> B,
+    {
+        Map { iter: self, f }
+    }
+
+    /// Skip items for which `predicate` returns `false`.
+    fn filter<P>(self, predicate: P) -> Filter<Self, P>
+    where
+        Self: Sized,
+        P: for<'a, 'b> FnMut(&'b <Self::H1Item as PlugLifetime<'a>>::T) -> bool,
+    {
+        Filter {
+            iter: self,
+            predicate,
+        }
+    }
+
+    /// Yield at most `n` items.
+    fn take(self, n: usize) -> Take<Self>
+    where
+        Self: Sized,
+    {
+        Take {
+            iter: self,
+            remaining: n,
+        }
+    }
+
+    /// Skip the first `n` items.
+    fn skip(self, n: usize) -> Skip<Self>
+    where
+        Self: Sized,
+    {
+        Skip { iter: self, n }
+    }
+
+    /// Skip items until `predicate` returns `false` for the first time.
+    fn skip_while<P>(self, predicate: P) -> SkipWhile<Self, P>
+    where
+        Self: Sized,
+        P: for<'a, 'b> FnMut(&'b <Self::H1Item as PlugLifetime<'a>>::T) -> bool,
+    {
+        SkipWhile {
+            iter: self,
+            predicate: Some(predicate),
+        }
+    }
+}
+
+impl<I> StreamingIteratorExt for I where I: StreamingIterator {}
+
+/// Streaming iterator mapping the items of another. See
+/// [`StreamingIteratorExt::map`](trait.StreamingIteratorExt.html#method.map).
+pub struct Map<I, F> {
+    iter: I,
+    f: F,
+}
+
+impl<I, F, B> StreamingIterator for Map<I, F>
+where
+    I: StreamingIterator,
+    F: for<'a> FnMut(<I::H1Item as PlugLifetime<'a>>::T) -> B,
+{
+    type H1Item = H0<B>;
+
+    fn next(&mut self) -> Option<B> {
+        self.iter.next().map(&mut self.f)
+    }
+}
+
+/// Streaming iterator skipping items of another. See
+/// [`StreamingIteratorExt::filter`](trait.StreamingIteratorExt.html#method.filter).
+pub struct Filter<I, P> {
+    iter: I,
+    predicate: P,
+}
+
+impl<I, P> StreamingIterator for Filter<I, P>
+where
+    I: StreamingIterator,
+    P: for<'a, 'b> FnMut(&'b <I::H1Item as PlugLifetime<'a>>::T) -> bool,
+{
+    type H1Item = I::H1Item;
+
+    fn next(&mut self) -> Option<<Self::H1Item as PlugLifetime<'_>>::T> {
+        let iter: *mut I = &mut self.iter;
+        loop {
+            // SAFETY: rejected items are dropped before the next call, so
+            // this is the only borrow of the iterator
+            let x = unsafe { &mut *iter }.next()?;
+            if (self.predicate)(&x) {
+                return Some(x);
+            }
+        }
+    }
+}
+
+/// Streaming iterator yielding a limited number of items of another. See
+/// [`StreamingIteratorExt::take`](trait.StreamingIteratorExt.html#method.take).
+pub struct Take<I> {
+    iter: I,
+    remaining: usize,
+}
+
+impl<I> StreamingIterator for Take<I>
+where
+    I: StreamingIterator,
+{
+    type H1Item = I::H1Item;
+
+    fn next(&mut self) -> Option<<Self::H1Item as PlugLifetime<'_>>::T> {
+        if self.remaining == 0 {
+            None
+        } else {
+            self.remaining -= 1;
+
+            self.iter.next()
+        }
+    }
+}
+
+/// Streaming iterator skipping the first items of another. See
+/// [`StreamingIteratorExt::skip`](trait.StreamingIteratorExt.html#method.skip).
+pub struct Skip<I> {
+    iter: I,
+    n: usize,
+}
+
+impl<I> StreamingIterator for Skip<I>
+where
+    I: StreamingIterator,
+{
+    type H1Item = I::H1Item;
+
+    fn next(&mut self) -> Option<<Self::H1Item as PlugLifetime<'_>>::T> {
+        while self.n > 0 {
+            self.n -= 1;
+            self.iter.next()?;
+        }
+
+        self.iter.next()
+    }
+}
+
+/// Streaming iterator skipping the first items of another that match a
+/// predicate. See
+/// [`StreamingIteratorExt::skip_while`](trait.StreamingIteratorExt.html#method.skip_while).
+pub struct SkipWhile<I, P> {
+    iter: I,
+    // `None` once an item has been yielded
+    predicate: Option<P>,
+}
+
+impl<I, P> StreamingIterator for SkipWhile<I, P>
+where
+    I: StreamingIterator,
+    P: for<'a, 'b> FnMut(&'b <I::H1Item as PlugLifetime<'a>>::T) -> bool,
+{
+    type H1Item = I::H1Item;
+
+    fn next(&mut self) -> Option<<Self::H1Item as PlugLifetime<'_>>::T> {
+        let mut predicate = match self.predicate.take() {
+            Some(predicate) => predicate,
+            None => return self.iter.next(),
+        };
+        let iter: *mut I = &mut self.iter;
+        loop {
+            // SAFETY: skipped items are dropped before the next call, so
+            // this is the only borrow of the iterator
+            match unsafe { &mut *iter }.next() {
+                Some(x) if predicate(&x) => {}
+                Some(x) => return Some(x),
+                None => {
+                    self.predicate = Some(predicate);
+
+                    return None;
+                }
+            }
+        }
+    }
+}
@@ -71,25 +71,227 @@
 //!
 //! This crate is highly experimental and many traits have limited
 //! functionality.
+//!
+//! # Cargo Features
+//!
+//! The crate is `no_std`. The HKT machinery, the core traits and the impls for
+//! `core` types are always available, while the rest is gated on:
+//!
+//! - `alloc`: impls for `alloc` types, such as `Box` and `String`.
+//! - `std` (default): impls for types that are only in `std`, such as `Mutex`
+//!   and `HashMap`. Implies `alloc`.
+//!
+//! The larger modules have their own features, all enabled by default, so
+//! that crates only needing part of the trait zoo can opt out of compiling the
+//! rest with `default-features = false`:
+//!
+//! - `collections`: the [`collections`](collections/index.html) module.
+//!   Implies `alloc`.
+//! - `lock`: the [`lock`](lock/index.html) traits and their impls for `cell`
+//!   types.
+//! - `rc`: the [`rc`](rc/index.html) module. Implies `alloc`.
+//! - `sync`: the [`sync`](sync/index.html) module. Implies `alloc` and `lock`.
+//! - `vec`: the [`vec`](vec/index.html) module. Implies `alloc`.
+//!
+//! Optional integrations:
+//!
+//! - `allocator_api`: fallible allocation for reference-counted boxes and
+//!   trait impls for boxes using custom allocators. Requires a nightly
+//!   compiler.
+//! - `ahash`: trait impls for `ahash::AHashMap` and `ahash::AHashSet`.
+//! - `arrayvec`: trait impls for `arrayvec::ArrayString`.
+//! - `async-lock`: HKT forms and lock trait impls for `async_lock::Mutex`.
+//! - `async-once-cell`: HKT forms and trait impls for
+//!   `async_once_cell::OnceCell`.
+//! - `bitvec`: HKT forms and sequence trait impls for `bitvec::vec::BitVec`.
+//! - `bumpalo`: arena trait impls for `bumpalo::Bump`.
+//! - `bytes`: trait impls for `bytes::BytesMut`.
+//! - `crossbeam-channel`: channel trait impls for `crossbeam_channel`.
+//! - `crossbeam-utils`: trait impls for `crossbeam_utils::atomic::AtomicCell`.
+//! - `derive`: derive macros delegating `Sequence`, `SequenceMut` and
+//!   `WithCapacity` to an inner field, and `Clone`, `Debug` and `PartialEq`
+//!   derives for structs parameterized by HKT forms.
+//! - `either`: HKT forms and trait impls for `either::Either`.
+//! - `flume`: channel trait impls for `flume`.
+//! - `futures`: conversions between `futures_core::Stream` and the streaming
+//!   traits.
+//! - `loom`: trait impls for `loom` synchronization primitives and a model
+//!   checking helper in `test_utils`.
+//! - `once_cell`: trait impls for `once_cell` cells.
+//! - `parking_lot`: HKT forms and lock trait impls for `parking_lot` locks.
+//! - `smallvec`: trait impls for `smallvec::SmallVec`.
+//! - `smartstring`: trait impls for `smartstring::SmartString`.
+//! - `test-utils`: reusable conformance tests for trait implementations.
+//! - `tokio`: HKT forms and trait impls for `tokio::sync` locks, cells and
+//!   channels.
+//! - `tokio-stream`: conversions between Tokio streams and `StreamingStream`.
+//! - `triomphe`: trait impls for `triomphe::Arc`.
+//! - `typed-arena`: arena trait impls for `typed_arena::Arena`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+// Derived impls refer to `::generic_std`
+#[cfg(feature = "derive")]
+extern crate self as generic_std;
 
+#[macro_use]
+mod macros;
+
+#[cfg(feature = "ahash")]
+pub mod ahash;
+#[cfg(feature = "arrayvec")]
+pub mod arrayvec;
+#[cfg(feature = "async-lock")]
+pub mod async_lock;
+#[cfg(feature = "async-once-cell")]
+pub mod async_once_cell;
+#[cfg(feature = "bitvec")]
+pub mod bitvec;
+#[cfg(feature = "alloc")]
+pub mod borrow;
+#[cfg(feature = "alloc")]
+pub mod boxed;
+#[cfg(feature = "bumpalo")]
+pub mod bumpalo;
+#[cfg(feature = "bytes")]
+pub mod bytes;
+pub mod cell;
+pub mod channel;
+#[cfg(feature = "collections")]
+pub mod collections;
+#[cfg(feature = "crossbeam-channel")]
+pub mod crossbeam_channel;
+#[cfg(feature = "crossbeam-utils")]
+pub mod crossbeam_utils;
+#[cfg(feature = "alloc")]
+pub mod effect;
+#[cfg(feature = "either")]
+pub mod either;
+#[cfg(feature = "alloc")]
+pub mod erased;
+#[cfg(feature = "flume")]
+pub mod flume;
+pub mod fp;
+#[cfg(feature = "alloc")]
+pub mod function;
+#[cfg(feature = "futures-core")]
+pub mod futures_core;
+pub mod hlist;
+pub mod iter;
+#[cfg(feature = "lock")]
+pub mod lock;
+#[cfg(feature = "loom")]
+pub mod loom;
+#[cfg(feature = "once_cell")]
+pub mod once_cell;
+pub mod optics;
+pub mod option;
+#[cfg(feature = "parking_lot")]
+pub mod parking_lot;
+#[cfg(feature = "std")]
+pub mod path;
 pub mod plug;
+#[cfg(feature = "rc")]
 pub mod rc;
 pub mod reference;
+pub mod result;
 pub mod slice;
+#[cfg(feature = "smallvec")]
+pub mod smallvec;
+#[cfg(feature = "smartstring")]
+pub mod smartstring;
+pub mod stream;
+#[cfg(feature = "alloc")]
+pub mod string;
+#[cfg(feature = "sync")]
 pub mod sync;
+#[cfg(any(
+    all(test, feature = "std", feature = "lock"),
+    feature = "test-utils"
+))]
+pub mod test_utils;
+#[cfg(feature = "tokio")]
+pub mod tokio;
+#[cfg(feature = "tokio-stream")]
+pub mod tokio_stream;
+#[cfg(feature = "triomphe")]
+pub mod triomphe;
+pub mod tuple;
+#[cfg(feature = "typed-arena")]
+pub mod typed_arena;
+pub mod validated;
+#[cfg(feature = "vec")]
 pub mod vec;
 
-#[cfg(test)]
+#[cfg(all(
+    test,
+    feature = "std",
+    feature = "collections",
+    feature = "lock",
+    feature = "rc",
+    feature = "sync",
+    feature = "vec",
+))]
 mod tests;
 
+#[cfg(feature = "derive")]
+pub use generic_std_derive::{
+    HktClone, HktDebug, HktPartialEq, Sequence, SequenceMut, WithCapacity,
+};
+
 use crate::plug::*;
-use std::ops::Deref;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "allocator_api")]
+use core::alloc::AllocError;
+use core::{
+    any::Any,
+    hash::BuildHasher,
+    ops::{Deref, DerefMut},
+    pin::Pin,
+};
 
 /// Trait for structs that can be constructed with a preallocated capacity.
 pub trait WithCapacity {
     fn with_capacity(capacity: usize) -> Self;
 }
 
+/// Trait for structs with a preallocated capacity.
+pub trait Capacity {
+    /// Number of elements that can be held without reallocating.
+    fn capacity(&self) -> usize;
+}
+
+/// Trait for hash maps and sets that can be constructed with any
+/// `BuildHasher`, such as `rustc_hash::FxBuildHasher` or
+/// `ahash::RandomState`, instead of a hard-coded `RandomState`.
+pub trait MapFactory<S>: Sized
+where
+    S: BuildHasher,
+{
+    fn with_hasher(hasher: S) -> Self;
+
+    fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self;
+
+    fn hasher(&self) -> &S;
+}
+
+/// Trait for arenas, which hand out references to allocated values that live
+/// as long as the arena itself.
+pub trait Arena<T> {
+    /// HKT reference to an allocated value with a lifetime slot.
+    type H1Ref: for<'a> PlugLifetime<'a>;
+
+    fn new() -> Self;
+
+    fn alloc<'a>(&'a self, x: T) -> <Self::H1Ref as PlugLifetime<'a>>::T
+    where
+        <Self::H1Ref as PlugLifetime<'a>>::T: DerefMut<Target = T>;
+}
+
 /// Trait for collections that store elements in a linear sequence, allowing
 /// for linear traversal and indexing with an `usize`.
 ///
@@ -129,7 +331,7 @@ pub trait Sequence<T> {
         self.len() == 0
     }
 
-    fn contains<'a>(&'a self, x: &T) -> bool
+    fn contains(&self, x: &T) -> bool
     where
         T: PartialEq;
 
@@ -175,7 +377,7 @@ pub trait StreamingIterator {
     /// HTK item with a lifetime slot.
     type H1Item: for<'a> PlugLifetime<'a>;
 
-    fn next(&mut self) -> Option<<Self::H1Item as PlugLifetime>::T>;
+    fn next(&mut self) -> Option<<Self::H1Item as PlugLifetime<'_>>::T>;
 }
 
 impl<I> StreamingIterator for I
@@ -184,25 +386,402 @@ where
 {
     type H1Item = H0<I::Item>;
 
-    fn next(&mut self) -> Option<<Self::H1Item as PlugLifetime>::T> {
+    fn next(&mut self) -> Option<<Self::H1Item as PlugLifetime<'_>>::T> {
         Iterator::next(self)
     }
 }
 
-/// Trait for reference-counted boxes.
-pub trait Rcb<T>: Clone + Deref<Target = T> {
-    type Weak: WeakRcb<T>;
+/// Trait for read-only handles to a `T`, either owning or borrowing.
+///
+/// This is broader than `Rcb` as handles are not required to be clonable.
+/// Handles that can be constructed from an owned value also implement
+/// [`NewPtr`](trait.NewPtr.html).
+pub trait Ptr<T>: Deref<Target = T>
+where
+    T: ?Sized,
+{
+}
 
+/// Trait for handles that can be constructed from an owned value.
+pub trait NewPtr<T>: Ptr<T> {
     fn new(x: T) -> Self;
+}
+
+/// Trait for uniquely-owned boxes.
+pub trait OwningPtr<T>: NewPtr<T> + DerefMut {
+    fn into_inner(this: Self) -> T;
+}
+
+/// Trait for handles granting exclusive access to a `T` that can produce a
+/// shorter-lived handle, like `&mut *x` does for `&mut T`.
+///
+/// The same restrictions on `T` and the reborrow bounds as in
+/// [`Sequence`](trait.Sequence.html) apply.
+pub trait ReborrowMut<T>
+where
+    T: ?Sized,
+{
+    /// HKT reborrowed handle with a lifetime slot.
+    type H1Reborrow: for<'a> PlugLifetime<'a>;
+
+    fn reborrow_mut<'a>(
+        &'a mut self,
+    ) -> <Self::H1Reborrow as PlugLifetime<'a>>::T
+    where
+        <Self::H1Reborrow as PlugLifetime<'a>>::T: DerefMut<Target = T>;
+}
+
+/// Trait for clone-on-write handles. Reads are cheap and the payload is only
+/// cloned on the first write if it is shared.
+pub trait CowLike<T>: Deref<Target = T>
+where
+    T: Clone,
+{
+    fn from_owned(x: T) -> Self;
+
+    fn to_mut(&mut self) -> &mut T;
+
+    fn into_owned(self) -> T;
+}
+
+/// Trait for owned types paired with the HKT form of their borrowed view, such
+/// as `String` and `&str`, `Vec<T>` and `&[T]` or `PathBuf` and `&Path`.
+pub trait PlugBorrow {
+    /// HKT borrowed view with a lifetime slot.
+    type H1Borrowed: for<'a> PlugLifetime<'a>;
+
+    fn borrow(&self) -> <Self::H1Borrowed as PlugLifetime<'_>>::T;
+
+    fn to_owned(x: <Self::H1Borrowed as PlugLifetime<'_>>::T) -> Self;
+}
+
+/// Trait for HKT forms that store either a `T` or a handle to one, such as
+/// [`H1Identity`](plug/struct.H1Identity.html) for an owned `T` and
+/// [`H1Reference<'a>`](reference/struct.H1Reference.html) for a `&'a T`.
+///
+/// Structs can take the form as a parameter so that the same definition works
+/// as both an owning and a zero-copy variant, without the runtime tag and
+/// `ToOwned` bound of `Cow`.
+pub trait MaybeOwned<T>: PlugType<T> {
+    fn get(x: &<Self as PlugType<T>>::T) -> &T;
+
+    /// Convert into an owned `T`, cloning if borrowed.
+    fn into_owned(x: <Self as PlugType<T>>::T) -> T
+    where
+        T: Clone;
+}
+
+/// Trait for owned string handles, such as `String`, `Box<str>` and
+/// `Rc<str>`.
+///
+/// Data models can be generic over it to leave the choice between unique and
+/// shared strings to the user.
+#[cfg(feature = "alloc")]
+pub trait Stringy: Clone + Deref<Target = str> {
+    /// Copy `s` into a new handle.
+    fn new(s: &str) -> Self;
+
+    /// Convert `s` into a handle, reusing its allocation if possible.
+    fn from_string(s: String) -> Self;
+
+    /// Borrow the contents of the handle.
+    fn as_str(&self) -> &str;
+
+    /// Convert the handle into a `String`, reusing its allocation if
+    /// possible.
+    fn into_string(self) -> String;
+}
+
+/// Trait for growable strings, such as `String`.
+///
+/// Text-building code can be generic over it to leave the small-string
+/// optimization strategy to the caller. Implementations with a fixed capacity,
+/// such as `arrayvec::ArrayString`, panic if it would be exceeded.
+pub trait StringBuilder: Default + Deref<Target = str> {
+    fn push(&mut self, c: char);
+
+    fn push_str(&mut self, s: &str);
+
+    fn as_str(&self) -> &str;
+
+    fn clear(&mut self);
+}
+
+/// Trait for growable byte buffers, such as `Vec<u8>`.
+///
+/// Encoders can be generic over it to leave the choice of buffer to the
+/// caller.
+pub trait ByteBuf: Default + Deref<Target = [u8]> + DerefMut {
+    fn extend_from_slice(&mut self, bytes: &[u8]);
+
+    fn as_slice(&self) -> &[u8];
+
+    fn as_mut_slice(&mut self) -> &mut [u8];
+
+    fn truncate(&mut self, len: usize);
+
+    fn clear(&mut self);
+
+    /// Split the buffer in two at `at`, returning `[0, at)` and leaving
+    /// `[at, len)` in `self`.
+    ///
+    /// This is constant-time for buffers with shared storage such as
+    /// `BytesMut`, but moves the remaining bytes for other buffers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    fn split_to(&mut self, at: usize) -> Self;
+
+    /// Split the buffer in two at `at`, returning `[at, len)` and leaving
+    /// `[0, at)` in `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    fn split_off(&mut self, at: usize) -> Self;
+}
+
+/// Trait for reference-counted boxes.
+///
+/// Weak pointer support is not required. Boxes that support weak pointers
+/// also implement [`RcbWithWeak`](trait.RcbWithWeak.html).
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a reference-counted box",
+    label = "expected a box such as `Rc<T>` or `Arc<T>`"
+)]
+pub trait Rcb<T>: Clone + Deref<Target = T>
+where
+    T: ?Sized,
+{
+    fn new(x: T) -> Self
+    where
+        T: Sized;
+
+    /// Like `new`, but returns an error instead of aborting if the allocation
+    /// fails.
+    ///
+    /// The default implementation calls `new`, so it never returns an error.
+    /// Implementors only need to override it when the box can report
+    /// allocation failures.
+    #[cfg(feature = "allocator_api")]
+    fn try_new(x: T) -> Result<Self, AllocError>
+    where
+        T: Sized,
+    {
+        Ok(Self::new(x))
+    }
+
+    fn try_unwrap(this: Self) -> Result<T, Self>
+    where
+        T: Sized;
+
+    fn unwrap_or_clone(this: Self) -> T
+    where
+        T: Clone,
+    {
+        Self::try_unwrap(this).unwrap_or_else(|this| T::clone(&this))
+    }
 
-    fn try_unwrap(this: Self) -> Result<T, Self>;
+    /// Consume the box, returning a raw pointer to the payload. The reference
+    /// count is not decremented, so the pointer must eventually be turned back
+    /// into a box with `from_raw` to avoid a leak.
+    fn into_raw(this: Self) -> *const T;
+
+    /// Reconstruct a box from a raw pointer.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been returned by `into_raw` on this same box type, and
+    /// each pointer returned by `into_raw` must be passed to `from_raw` at
+    /// most once.
+    unsafe fn from_raw(ptr: *const T) -> Self;
+}
+
+/// Marker trait for reference-counted boxes that can be shared between
+/// threads.
+///
+/// Implemented for every `Rcb` that is `Send + Sync`, so instantiating generic
+/// code that requires it with a single-threaded box is a compile error:
+///
+/// ```compile_fail
+/// use generic_std::SharedRcb;
+/// use std::rc::Rc;
+///
+/// fn share<R: SharedRcb<usize>>(x: R) {}
+///
+/// share(Rc::new(42));
+/// ```
+pub trait SharedRcb<T>: Rcb<T> + Send + Sync
+where
+    T: ?Sized,
+{
+}
+
+impl<T, R> SharedRcb<T> for R
+where
+    T: ?Sized,
+    R: Rcb<T> + Send + Sync,
+{
+}
+
+/// Trait for HKT forms with a type slot that yield reference-counted boxes,
+/// such as [`H1Rc`](rc/struct.H1Rc.html) and
+/// [`H1Arc`](sync/struct.H1Arc.html).
+///
+/// This is implemented for all such forms and can't be implemented manually.
+/// Bounding by it instead of spelling out `<K as PlugType<T>>::T: Rcb<T>`
+/// takes a single bound and gives clearer errors when the wrong form is used:
+///
+/// ```
+/// use generic_std::{rc::H1Rc, RcbKind};
+///
+/// struct Shared<K>
+/// where
+///     K: RcbKind<String>,
+/// {
+///     name: K::Rcb,
+/// }
+///
+/// let shared = Shared::<H1Rc> {
+///     name: "xpotato".to_string().into(),
+/// };
+///
+/// assert_eq!(shared.name.as_str(), "xpotato");
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a kind of reference-counted box for `{T}`",
+    label = "expected a form such as `H1Rc` or `H1Arc`",
+    note = "`<{Self} as PlugType<{T}>>::T` must implement `Rcb<{T}>`"
+)]
+pub trait RcbKind<T>: PlugType<T>
+where
+    T: ?Sized,
+{
+    /// Same as `<Self as PlugType<T>>::T`.
+    type Rcb: Rcb<T>;
+}
+
+// Not recommended so that errors report the missing `RcbKind` with the
+// message above instead of the `Rcb` bound of this impl
+#[diagnostic::do_not_recommend]
+impl<K, T> RcbKind<T> for K
+where
+    K: PlugType<T>,
+    <K as PlugType<T>>::T: Rcb<T>,
+    T: ?Sized,
+{
+    type Rcb = <K as PlugType<T>>::T;
+}
+
+/// Trait for reference-counted boxes that support weak pointers.
+pub trait RcbWithWeak<T>: Rcb<T>
+where
+    T: ?Sized,
+{
+    type Weak: WeakRcb<T, Strong = Self>;
+
+    /// Construct a box while giving `f` a weak pointer to the allocation,
+    /// which can be stored in the payload to create cyclic structures.
+    fn new_cyclic<F>(f: F) -> Self
+    where
+        T: Sized,
+        F: FnOnce(&Self::Weak) -> T;
 
     fn downgrade(this: &Self) -> Self::Weak;
 }
 
+/// Trait for reference-counted boxes that can be constructed already pinned.
+pub trait PinnedRcb<T>: Rcb<T> {
+    fn pin(x: T) -> Pin<Self>;
+}
+
+/// Trait for reference-counted boxes holding a `dyn Any` payload that can be
+/// downcast to a concrete `U`.
+pub trait DowncastRcb<U>: Sized
+where
+    U: Any,
+{
+    type Downcast: Rcb<U>;
+
+    fn downcast(this: Self) -> Result<Self::Downcast, Self>;
+}
+
+/// Trait for families of reference-counted boxes, linking a strong pointer
+/// type to its weak counterpart.
+///
+/// A struct generic over a family can declare both strong and weak fields
+/// from a single type parameter without risking inconsistent pairings such as
+/// `Rc` with `std::sync::Weak`. Like `PlugType`, the family is implemented
+/// for every payload type, so a struct holding multiple payload types simply
+/// requires multiple bounds (e.g. `P: PointerFamily<String> +
+/// PointerFamily<usize>`).
+pub trait PointerFamily<T>
+where
+    T: ?Sized,
+{
+    type Strong: RcbWithWeak<T, Weak = Self::Weak>;
+    type Weak: WeakRcb<T, Strong = Self::Strong>;
+}
+
+/// Trait for values that use the pointer family `Q` after being rebuilt.
+///
+/// This is a deep conversion: payloads are cloned into new allocations. It is
+/// mostly useful to hand data built with a single-threaded family to a thread
+/// pool, e.g. converting from [`RcFamily`](rc/struct.RcFamily.html) to
+/// [`ArcFamily`](sync/struct.ArcFamily.html).
+pub trait ConvertRcb<Q> {
+    type Output;
+
+    fn convert_rcb(&self) -> Self::Output;
+}
+
+/// Rebuild a reference-counted box as another type of box by cloning its
+/// payload.
+pub fn convert_rcb<T, R, S>(x: &R) -> S
+where
+    T: Clone,
+    R: Rcb<T>,
+    S: Rcb<T>,
+{
+    S::new(T::clone(x))
+}
+
+/// Trait for shared, interiorly-mutable handles, such as `Rc<RefCell<T>>`
+/// and `Arc<Mutex<T>>`.
+pub trait SharedMut<T>: Clone {
+    fn new(x: T) -> Self;
+
+    /// Call `f` with exclusive access to the payload.
+    ///
+    /// # Panics
+    ///
+    /// Implementations panic if the payload is already being accessed by the
+    /// current thread or if a lock is poisoned.
+    fn with<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut T) -> R;
+}
+
 /// Trait for weak pointers to reference-counted boxes.
-pub trait WeakRcb<T> {
-    type Strong: Rcb<T>;
+pub trait WeakRcb<T>: Clone
+where
+    T: ?Sized,
+{
+    type Strong: RcbWithWeak<T, Weak = Self>;
+
+    /// Construct a dangling weak pointer that never upgrades.
+    fn new() -> Self
+    where
+        T: Sized;
 
     fn upgrade(&self) -> Option<Self::Strong>;
+
+    /// Number of strong pointers to the allocation, or zero if it has been
+    /// dropped.
+    fn strong_count(&self) -> usize;
+
+    /// Number of weak pointers to the allocation, or zero if there are no
+    /// strong pointers left.
+    fn weak_count(&self) -> usize;
 }
@@ -0,0 +1,609 @@
+//! Traits for locks and other interior mutability primitives.
+//!
+//! Like [`Rcb`](../trait.Rcb.html) does for sharing, these traits make the
+//! choice between single-threaded and multi-threaded interior mutability a
+//! type parameter.
+
+use crate::plug::PlugLifetime;
+use core::{
+    error::Error,
+    fmt,
+    future::Future,
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+#[cfg(all(feature = "std", feature = "sync"))]
+use std::sync::LockResult;
+
+/// Error returned when a lock can't be acquired.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LockError {
+    /// The lock was poisoned by a thread panicking while holding it.
+    Poisoned,
+    /// The lock is held in a way that blocking would never resolve, such as
+    /// an already borrowed `RefCell`.
+    WouldBlock,
+}
+
+impl fmt::Display for LockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LockError::Poisoned => f.write_str("lock poisoned"),
+            LockError::WouldBlock => f.write_str("lock would block"),
+        }
+    }
+}
+
+impl Error for LockError {}
+
+/// What to do when acquiring a poisoned lock. Locks that can't be poisoned,
+/// such as `RefCell`, ignore the policy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum PoisonPolicy {
+    /// Panic.
+    Panic,
+    /// Return [`LockError::Poisoned`](enum.LockError.html#variant.Poisoned).
+    #[default]
+    Propagate,
+    /// Acquire the lock anyway.
+    Ignore,
+}
+
+#[cfg(all(feature = "std", feature = "sync"))]
+impl PoisonPolicy {
+    pub(crate) fn recover<G>(
+        self,
+        result: LockResult<G>,
+    ) -> Result<G, LockError> {
+        match (result, self) {
+            (Ok(guard), _) => Ok(guard),
+            (Err(_), PoisonPolicy::Panic) => panic!("lock poisoned"),
+            (Err(_), PoisonPolicy::Propagate) => Err(LockError::Poisoned),
+            (Err(err), PoisonPolicy::Ignore) => Ok(err.into_inner()),
+        }
+    }
+}
+
+/// Trait for locks granting exclusive access to a `T` through a guard.
+///
+/// # Note
+///
+/// As with [`Sequence`](../trait.Sequence.html), the `H1Guard` bounds can't be
+/// as specific as they should, so implementors must only allow `T: 'static`
+/// and the `DerefMut` bound on the guard is part of the `lock()` declaration.
+pub trait Lock<T> {
+    /// HKT guard with a lifetime slot.
+    type H1Guard: for<'a> PlugLifetime<'a>;
+
+    fn new(x: T) -> Self;
+
+    /// Acquire the lock, handling poisoning according to `policy`.
+    fn lock_with<'a>(
+        &'a self,
+        policy: PoisonPolicy,
+    ) -> Result<<Self::H1Guard as PlugLifetime<'a>>::T, LockError>
+    where
+        <Self::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = T>;
+
+    /// Acquire the lock with the default poison policy, which propagates
+    /// poisoning as an error.
+    fn lock<'a>(
+        &'a self,
+    ) -> Result<<Self::H1Guard as PlugLifetime<'a>>::T, LockError>
+    where
+        <Self::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = T>,
+    {
+        self.lock_with(PoisonPolicy::default())
+    }
+
+    /// Acquire the lock without blocking. Returns `None` if the lock is held
+    /// elsewhere or poisoned.
+    fn try_lock<'a>(&'a self) -> Option<<Self::H1Guard as PlugLifetime<'a>>::T>
+    where
+        <Self::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = T>;
+}
+
+/// Object-safe alternative to [`Lock`](trait.Lock.html) that grants access to
+/// the protected value through callbacks instead of guards.
+///
+/// Callbacks can't return values directly since that would make the trait not
+/// object-safe, so results must be written to captured variables instead.
+///
+/// Implementations for `std` locks panic if the lock is poisoned.
+pub trait ScopedLock<T>
+where
+    T: ?Sized,
+{
+    /// Call `f` with shared access to the protected value.
+    fn with(&self, f: &mut dyn FnMut(&T));
+
+    /// Call `f` with exclusive access to the protected value.
+    fn with_mut(&self, f: &mut dyn FnMut(&mut T));
+}
+
+/// Trait for locks that distinguish between shared and exclusive access.
+///
+/// Exclusive access is granted through the [`Lock`](trait.Lock.html) guard.
+/// The same restrictions on `T` and the guard bounds apply.
+pub trait RwLockLike<T>: Lock<T> {
+    /// HKT read guard with a lifetime slot.
+    type H1ReadGuard: for<'a> PlugLifetime<'a>;
+
+    /// Acquire shared access, handling poisoning according to `policy`.
+    fn read_with<'a>(
+        &'a self,
+        policy: PoisonPolicy,
+    ) -> Result<<Self::H1ReadGuard as PlugLifetime<'a>>::T, LockError>
+    where
+        <Self::H1ReadGuard as PlugLifetime<'a>>::T: Deref<Target = T>;
+
+    /// Acquire shared access with the default poison policy.
+    fn read<'a>(
+        &'a self,
+    ) -> Result<<Self::H1ReadGuard as PlugLifetime<'a>>::T, LockError>
+    where
+        <Self::H1ReadGuard as PlugLifetime<'a>>::T: Deref<Target = T>,
+    {
+        self.read_with(PoisonPolicy::default())
+    }
+
+    /// Acquire shared access without blocking. Returns `None` if the lock is
+    /// held exclusively elsewhere or poisoned.
+    fn try_read<'a>(
+        &'a self,
+    ) -> Option<<Self::H1ReadGuard as PlugLifetime<'a>>::T>
+    where
+        <Self::H1ReadGuard as PlugLifetime<'a>>::T: Deref<Target = T>;
+
+    /// Same as [`Lock::lock_with`](trait.Lock.html#tymethod.lock_with).
+    fn write_with<'a>(
+        &'a self,
+        policy: PoisonPolicy,
+    ) -> Result<<Self::H1Guard as PlugLifetime<'a>>::T, LockError>
+    where
+        <Self::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = T>,
+    {
+        self.lock_with(policy)
+    }
+
+    /// Same as [`Lock::lock`](trait.Lock.html#method.lock).
+    fn write<'a>(
+        &'a self,
+    ) -> Result<<Self::H1Guard as PlugLifetime<'a>>::T, LockError>
+    where
+        <Self::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = T>,
+    {
+        self.lock()
+    }
+
+    /// Same as [`Lock::try_lock`](trait.Lock.html#tymethod.try_lock).
+    fn try_write<'a>(&'a self) -> Option<<Self::H1Guard as PlugLifetime<'a>>::T>
+    where
+        <Self::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = T>,
+    {
+        self.try_lock()
+    }
+}
+
+/// Trait for reader-writer locks supporting upgradable reads: shared access
+/// that can be atomically upgraded to exclusive access. Only one upgradable
+/// read can be held at a time, but it can coexist with plain reads.
+///
+/// Locks without native support, such as `std::sync::RwLock`, emulate
+/// upgradable reads by acquiring exclusive access from the start. Upgrading is
+/// then a no-op, but upgradable reads also exclude plain reads.
+pub trait UpgradableRwLock<T>: RwLockLike<T> {
+    /// HKT upgradable read guard with a lifetime slot.
+    type H1UpgradableGuard: for<'a> PlugLifetime<'a>;
+
+    /// Acquire upgradable shared access, handling poisoning according to
+    /// `policy`.
+    fn upgradable_read_with<'a>(
+        &'a self,
+        policy: PoisonPolicy,
+    ) -> Result<<Self::H1UpgradableGuard as PlugLifetime<'a>>::T, LockError>
+    where
+        <Self::H1UpgradableGuard as PlugLifetime<'a>>::T: Deref<Target = T>;
+
+    /// Acquire upgradable shared access with the default poison policy.
+    fn upgradable_read<'a>(
+        &'a self,
+    ) -> Result<<Self::H1UpgradableGuard as PlugLifetime<'a>>::T, LockError>
+    where
+        <Self::H1UpgradableGuard as PlugLifetime<'a>>::T: Deref<Target = T>,
+    {
+        self.upgradable_read_with(PoisonPolicy::default())
+    }
+
+    /// Atomically upgrade to exclusive access, blocking until all plain reads
+    /// are released.
+    fn upgrade<'a>(
+        guard: <Self::H1UpgradableGuard as PlugLifetime<'a>>::T,
+    ) -> <Self::H1Guard as PlugLifetime<'a>>::T
+    where
+        <Self::H1UpgradableGuard as PlugLifetime<'a>>::T: Deref<Target = T>,
+        <Self::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = T>;
+}
+
+/// Trait for HKT shared guard forms with a lifetime slot whose guards can be
+/// mapped to a component of the protected value, like `Ref::map`.
+///
+/// `std` lock guards can't be mapped on stable Rust.
+pub trait MappableGuard<T, U>: for<'a> PlugLifetime<'a>
+where
+    T: ?Sized,
+    U: ?Sized,
+{
+    /// HKT mapped guard with a lifetime slot.
+    type H1Mapped: for<'a> PlugLifetime<'a>;
+
+    fn map_guard<'a, F>(
+        guard: <Self as PlugLifetime<'a>>::T,
+        f: F,
+    ) -> <Self::H1Mapped as PlugLifetime<'a>>::T
+    where
+        F: FnOnce(&T) -> &U;
+}
+
+/// Trait for HKT exclusive guard forms with a lifetime slot whose guards can be
+/// mapped to a component of the protected value, like `RefMut::map`.
+///
+/// `std` lock guards can't be mapped on stable Rust.
+pub trait MappableGuardMut<T, U>: for<'a> PlugLifetime<'a>
+where
+    T: ?Sized,
+    U: ?Sized,
+{
+    /// HKT mapped guard with a lifetime slot.
+    type H1Mapped: for<'a> PlugLifetime<'a>;
+
+    fn map_guard_mut<'a, F>(
+        guard: <Self as PlugLifetime<'a>>::T,
+        f: F,
+    ) -> <Self::H1Mapped as PlugLifetime<'a>>::T
+    where
+        F: FnOnce(&mut T) -> &mut U;
+}
+
+/// Trait for condition variables used together with a [`Lock`](trait.Lock.html)
+/// `L`, such as `Condvar` with `Mutex`.
+///
+/// As with any condition variable, wakeups may be spurious so the condition
+/// must be checked again after waiting.
+pub trait CondvarLike<T, L>
+where
+    L: Lock<T>,
+{
+    fn new() -> Self;
+
+    /// Release the lock held by `guard` and block until notified, then
+    /// reacquire it, handling poisoning according to `policy`.
+    fn wait_with<'a>(
+        &self,
+        guard: <L::H1Guard as PlugLifetime<'a>>::T,
+        policy: PoisonPolicy,
+    ) -> Result<<L::H1Guard as PlugLifetime<'a>>::T, LockError>
+    where
+        <L::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = T>;
+
+    /// Same as `wait_with()` with the default poison policy.
+    fn wait<'a>(
+        &self,
+        guard: <L::H1Guard as PlugLifetime<'a>>::T,
+    ) -> Result<<L::H1Guard as PlugLifetime<'a>>::T, LockError>
+    where
+        <L::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = T>,
+    {
+        self.wait_with(guard, PoisonPolicy::default())
+    }
+
+    /// Wake up one blocked thread, if any.
+    fn notify_one(&self);
+
+    /// Wake up all blocked threads.
+    fn notify_all(&self);
+}
+
+/// Trait for by-value interior mutability, such as `Cell`.
+///
+/// Implementations for `std` locks panic if the lock is poisoned.
+pub trait CellLike<T> {
+    fn new(x: T) -> Self;
+
+    fn get(&self) -> T
+    where
+        T: Copy;
+
+    fn set(&self, x: T);
+
+    /// Replace the contained value with `x`, returning the old value.
+    fn replace(&self, x: T) -> T;
+
+    /// Replace the contained value with `T::default()`, returning the old
+    /// value.
+    fn take(&self) -> T
+    where
+        T: Default,
+    {
+        self.replace(T::default())
+    }
+}
+
+/// Trait for asynchronous locks granting exclusive access to a `T` through a
+/// guard.
+///
+/// `lock()` returns a future declared through a `PlugLifetime` slot so that it
+/// can borrow the lock without boxing, if the implementation allows. The same
+/// restrictions on `T` and the guard bounds as in [`Lock`](trait.Lock.html)
+/// apply.
+///
+/// The future resolves to the `H1Guard` plugged with the same lifetime.
+/// Unfortunately this can't be stated in the `lock()` bounds since callers
+/// wouldn't be able to prove it for every lifetime, so the bounds only require
+/// a [`GuardFuture`](trait.GuardFuture.html).
+pub trait AsyncLock<T> {
+    /// HKT guard with a lifetime slot.
+    type H1Guard: for<'a> PlugLifetime<'a>;
+
+    /// HKT future resolving to the guard, with a lifetime slot.
+    type H1LockFuture: for<'a> PlugLifetime<'a>;
+
+    fn new(x: T) -> Self;
+
+    fn lock<'a>(&'a self) -> <Self::H1LockFuture as PlugLifetime<'a>>::T
+    where
+        <Self::H1LockFuture as PlugLifetime<'a>>::T: GuardFuture<T>;
+}
+
+/// Futures resolving to a guard granting exclusive access to a `T`.
+///
+/// Using this trait instead of `Future<Output = G>` allows the guard bounds of
+/// [`AsyncLock`](trait.AsyncLock.html) to be stated in HRTB bounds.
+pub trait GuardFuture<T>: Future<Output = Self::Guard>
+where
+    T: ?Sized,
+{
+    type Guard: DerefMut<Target = T>;
+}
+
+impl<T, F> GuardFuture<T> for F
+where
+    T: ?Sized,
+    F: Future,
+    F::Output: DerefMut<Target = T>,
+{
+    type Guard = F::Output;
+}
+
+/// Trait for async locks that distinguish between shared and exclusive access.
+///
+/// Exclusive access is granted through the [`AsyncLock`](trait.AsyncLock.html)
+/// guard. The same restrictions on the future bounds apply, so `read()` only
+/// requires a [`ReadGuardFuture`](trait.ReadGuardFuture.html).
+pub trait AsyncRwLockLike<T>: AsyncLock<T> {
+    /// HKT read guard with a lifetime slot.
+    type H1ReadGuard: for<'a> PlugLifetime<'a>;
+
+    /// HKT future resolving to the read guard, with a lifetime slot.
+    type H1ReadFuture: for<'a> PlugLifetime<'a>;
+
+    fn read<'a>(&'a self) -> <Self::H1ReadFuture as PlugLifetime<'a>>::T
+    where
+        <Self::H1ReadFuture as PlugLifetime<'a>>::T: ReadGuardFuture<T>;
+}
+
+/// Futures resolving to a guard granting shared access to a `T`.
+///
+/// Shared counterpart of [`GuardFuture`](trait.GuardFuture.html).
+pub trait ReadGuardFuture<T>: Future<Output = Self::Guard>
+where
+    T: ?Sized,
+{
+    type Guard: Deref<Target = T>;
+}
+
+impl<T, F> ReadGuardFuture<T> for F
+where
+    T: ?Sized,
+    F: Future,
+    F::Output: Deref<Target = T>,
+{
+    type Guard = F::Output;
+}
+
+/// Trait for cells that can be written to only once, such as `OnceCell`.
+pub trait OnceLike<T> {
+    /// Create an uninitialized cell.
+    fn new() -> Self;
+
+    /// Get the value, if the cell was initialized.
+    fn get(&self) -> Option<&T>;
+
+    /// Initialize the cell with `x`. Returns `Err(x)` if the cell was already
+    /// initialized.
+    fn set(&self, x: T) -> Result<(), T>;
+
+    /// Get the value, initializing the cell with `f` first if needed.
+    fn get_or_init<F>(&self, f: F) -> &T
+    where
+        F: FnOnce() -> T;
+}
+
+/// Trait for cells that can be written to only once, with an async
+/// initializer, such as `tokio::sync::OnceCell`.
+///
+/// The returned future resolves to a reference to the value, stated with the
+/// [`InitFuture`](trait.InitFuture.html) helper trait for the same reasons as
+/// in [`AsyncLock`](trait.AsyncLock.html). As `H1InitFuture` can't depend on
+/// the type of the initializer, implementations may need to box it, so it must
+/// be `Send`.
+///
+/// There is no `set()`, as not all implementations can initialize the cell
+/// without awaiting.
+pub trait AsyncOnceLike<T> {
+    /// HKT future resolving to a reference to the value, with a lifetime
+    /// slot.
+    type H1InitFuture: for<'a> PlugLifetime<'a>;
+
+    /// Create an uninitialized cell.
+    fn new() -> Self;
+
+    /// Get the value, if the cell was initialized.
+    fn get(&self) -> Option<&T>;
+
+    /// Get the value, awaiting `init` first if needed.
+    fn get_or_init<'a, F>(
+        &'a self,
+        init: F,
+    ) -> <Self::H1InitFuture as PlugLifetime<'a>>::T
+    where
+        T: 'a,
+        F: Future<Output = T> + Send + 'a,
+        <Self::H1InitFuture as PlugLifetime<'a>>::T: InitFuture<'a, T>;
+}
+
+/// Futures resolving to a `&'a T`.
+///
+/// Using this trait instead of `Future<Output = &'a T>` allows the bounds of
+/// [`AsyncOnceLike`](trait.AsyncOnceLike.html) to be stated in HRTB bounds.
+pub trait InitFuture<'a, T>: Future<Output = &'a T>
+where
+    T: 'a + ?Sized,
+{
+}
+
+impl<'a, T, F> InitFuture<'a, T> for F
+where
+    T: 'a + ?Sized,
+    F: Future<Output = &'a T>,
+{
+}
+
+/// Trait for values initialized on first access, such as `LazyLock`.
+///
+/// Unlike [`OnceLike`](trait.OnceLike.html), the initialization function is
+/// fixed on construction.
+pub trait LazyLike<T>: Deref<Target = T> {
+    /// Get the value, initializing it first if needed.
+    fn force(this: &Self) -> &T;
+}
+
+/// Trait for simple counters and flags that may or may not be atomic, such as
+/// `Cell<usize>` and `AtomicUsize`.
+///
+/// Orderings follow the semantics of the `std` atomics and are ignored by
+/// single-threaded implementations.
+pub trait MaybeAtomic<T>
+where
+    T: Copy,
+{
+    fn new(x: T) -> Self;
+
+    fn load(&self, order: Ordering) -> T;
+
+    fn store(&self, x: T, order: Ordering);
+
+    /// Store `x`, returning the previous value.
+    fn swap(&self, x: T, order: Ordering) -> T;
+
+    /// Store `new` if the current value is `current`. Returns the previous
+    /// value, wrapped in `Ok` if it was replaced and in `Err` otherwise.
+    fn compare_exchange(
+        &self,
+        current: T,
+        new: T,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<T, T>;
+}
+
+/// Trait for integer [`MaybeAtomic`](trait.MaybeAtomic.html)s. Arithmetic wraps
+/// around on overflow.
+pub trait MaybeAtomicInt<T>: MaybeAtomic<T>
+where
+    T: Copy,
+{
+    /// Add `x`, returning the previous value.
+    fn fetch_add(&self, x: T, order: Ordering) -> T;
+
+    /// Subtract `x`, returning the previous value.
+    fn fetch_sub(&self, x: T, order: Ordering) -> T;
+}
+
+impl MaybeAtomic<usize> for AtomicUsize {
+    #[inline]
+    fn new(x: usize) -> Self {
+        AtomicUsize::new(x)
+    }
+
+    #[inline]
+    fn load(&self, order: Ordering) -> usize {
+        AtomicUsize::load(self, order)
+    }
+
+    #[inline]
+    fn store(&self, x: usize, order: Ordering) {
+        AtomicUsize::store(self, x, order)
+    }
+
+    #[inline]
+    fn swap(&self, x: usize, order: Ordering) -> usize {
+        AtomicUsize::swap(self, x, order)
+    }
+
+    #[inline]
+    fn compare_exchange(
+        &self,
+        current: usize,
+        new: usize,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<usize, usize> {
+        AtomicUsize::compare_exchange(self, current, new, success, failure)
+    }
+}
+
+impl MaybeAtomicInt<usize> for AtomicUsize {
+    #[inline]
+    fn fetch_add(&self, x: usize, order: Ordering) -> usize {
+        AtomicUsize::fetch_add(self, x, order)
+    }
+
+    #[inline]
+    fn fetch_sub(&self, x: usize, order: Ordering) -> usize {
+        AtomicUsize::fetch_sub(self, x, order)
+    }
+}
+
+impl MaybeAtomic<bool> for AtomicBool {
+    #[inline]
+    fn new(x: bool) -> Self {
+        AtomicBool::new(x)
+    }
+
+    #[inline]
+    fn load(&self, order: Ordering) -> bool {
+        AtomicBool::load(self, order)
+    }
+
+    #[inline]
+    fn store(&self, x: bool, order: Ordering) {
+        AtomicBool::store(self, x, order)
+    }
+
+    #[inline]
+    fn swap(&self, x: bool, order: Ordering) -> bool {
+        AtomicBool::swap(self, x, order)
+    }
+
+    #[inline]
+    fn compare_exchange(
+        &self,
+        current: bool,
+        new: bool,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<bool, bool> {
+        AtomicBool::compare_exchange(self, current, new, success, failure)
+    }
+}
@@ -0,0 +1,32 @@
+//! Trait impls for the synchronization primitives of the
+//! [loom](https://crates.io/crates/loom) crate, mirroring the ones for `std`
+//! types so that generic code can be model-checked.
+
+use crate::SharedMut;
+use ::loom::sync::{Arc, Mutex, RwLock};
+
+impl<T> SharedMut<T> for Arc<Mutex<T>> {
+    fn new(x: T) -> Self {
+        Arc::new(Mutex::new(x))
+    }
+
+    fn with<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        f(&mut self.lock().unwrap())
+    }
+}
+
+impl<T> SharedMut<T> for Arc<RwLock<T>> {
+    fn new(x: T) -> Self {
+        Arc::new(RwLock::new(x))
+    }
+
+    fn with<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        f(&mut self.write().unwrap())
+    }
+}
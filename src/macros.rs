@@ -0,0 +1,570 @@
+//! Macros for declaring traits with HKT-based async methods and the named
+//! futures they return, and for delegating trait impls to an inner field.
+
+/// Declare a trait with async methods, or implement one, without boxing the
+/// returned futures.
+///
+/// Each method is written like an `async fn` with a single lifetime, followed
+/// by `as` and the name of the associated HKT form for the returned future.
+/// In traits, the macro declares the form as an associated type and adds the
+/// `Future` bound to the method. Implementations set the form after `=`,
+/// either to an existing form or to a new one declared with
+/// `struct <Form> for <Future>`, and return a value of the future type it
+/// plugs to:
+///
+/// ```
+/// use generic_std::hkt_async_trait;
+/// use std::future::{self, Ready};
+///
+/// hkt_async_trait! {
+///     pub trait Lookup {
+///         async fn lookup<'a>(&'a self, index: usize) -> Option<&'a str>
+///             as H1LookupFuture;
+///     }
+/// }
+///
+/// struct Names(Vec<String>);
+///
+/// hkt_async_trait! {
+///     impl Lookup for Names {
+///         async fn lookup<'a>(&'a self, index: usize) -> Option<&'a str>
+///             as H1LookupFuture =
+///                 pub struct H1ReadyStr for Ready<Option<&'a str>>
+///         {
+///             future::ready(self.0.get(index).map(String::as_str))
+///         }
+///     }
+/// }
+/// ```
+///
+/// Async blocks can't be named, so implementations must return a named
+/// future, which can be declared with
+/// [`define_h1_future!`](macro.define_h1_future.html). Callers generic over
+/// the trait must bound the future for every lifetime they use, as with any
+/// method declared this way.
+#[macro_export]
+macro_rules! hkt_async_trait {
+    (
+        $(#[$attr:meta])*
+        $vis:vis trait $name:ident {
+            $(
+                $(#[$fn_attr:meta])*
+                async fn $fn:ident<$lt:lifetime>($($args:tt)*) -> $out:ty
+                    as $form:ident;
+            )*
+        }
+    ) => {
+        $(#[$attr])*
+        $vis trait $name {
+            $(
+                #[doc = concat!(
+                    "HKT future returned by `",
+                    stringify!($fn),
+                    "`, with a lifetime slot."
+                )]
+                type $form: for<'hkt> $crate::plug::PlugLifetime<'hkt>;
+
+                $(#[$fn_attr])*
+                fn $fn<$lt>(
+                    $($args)*
+                ) -> <Self::$form as $crate::plug::PlugLifetime<$lt>>::T
+                where
+                    <Self::$form as $crate::plug::PlugLifetime<$lt>>::T:
+                        ::core::future::Future<Output = $out>;
+            )*
+        }
+    };
+    (impl $name:ident for $ty:ty { $($methods:tt)* }) => {
+        $crate::hkt_async_trait!(@impl [$name $ty] [] [] $($methods)*);
+    };
+    (
+        @impl [$name:ident $ty:ty] [$($items:tt)*] [$($forms:tt)*]
+        async fn $fn:ident<$lt:lifetime>($($args:tt)*) -> $out:ty
+            as $form:ident = $form_vis:vis struct $new_form:ident for $fut:ty
+        $body:block
+        $($rest:tt)*
+    ) => {
+        $crate::hkt_async_trait!(
+            @impl [$name $ty]
+            [
+                $($items)*
+                type $form = $new_form;
+
+                fn $fn<$lt>(
+                    $($args)*
+                ) -> <Self::$form as $crate::plug::PlugLifetime<$lt>>::T
+                where
+                    <Self::$form as $crate::plug::PlugLifetime<$lt>>::T:
+                        ::core::future::Future<Output = $out>,
+                $body
+            ]
+            [
+                $($forms)*
+                #[doc = concat!(
+                    "HKT future returned by `",
+                    stringify!($fn),
+                    "`, with a lifetime slot."
+                )]
+                $form_vis struct $new_form;
+
+                impl<$lt> $crate::plug::PlugLifetime<$lt> for $new_form {
+                    type T = $fut;
+                }
+            ]
+            $($rest)*
+        );
+    };
+    (
+        @impl [$name:ident $ty:ty] [$($items:tt)*] [$($forms:tt)*]
+        async fn $fn:ident<$lt:lifetime>($($args:tt)*) -> $out:ty
+            as $form:ident = $form_ty:ty
+        $body:block
+        $($rest:tt)*
+    ) => {
+        $crate::hkt_async_trait!(
+            @impl [$name $ty]
+            [
+                $($items)*
+                type $form = $form_ty;
+
+                fn $fn<$lt>(
+                    $($args)*
+                ) -> <Self::$form as $crate::plug::PlugLifetime<$lt>>::T
+                where
+                    <Self::$form as $crate::plug::PlugLifetime<$lt>>::T:
+                        ::core::future::Future<Output = $out>,
+                $body
+            ]
+            [$($forms)*]
+            $($rest)*
+        );
+    };
+    (@impl [$name:ident $ty:ty] [$($items:tt)*] [$($forms:tt)*]) => {
+        $($forms)*
+
+        impl $name for $ty {
+            $($items)*
+        }
+    };
+}
+
+/// Declare a struct with a single lifetime parameter along with its HKT form,
+/// named after `as`.
+///
+/// This is mostly useful for named futures returned by async trait methods,
+/// such as the ones declared with
+/// [`hkt_async_trait!`](macro.hkt_async_trait.html):
+///
+/// ```
+/// use generic_std::{define_h1_future, plug::PlugLifetime};
+/// use std::{
+///     future::Future,
+///     pin::Pin,
+///     task::{Context, Poll},
+/// };
+///
+/// define_h1_future! {
+///     /// Future resolving to a reference immediately.
+///     pub struct RefFuture<'a>(&'a usize) as H1RefFuture;
+/// }
+///
+/// impl<'a> Future for RefFuture<'a> {
+///     type Output = &'a usize;
+///
+///     fn poll(self: Pin<&mut Self>, _: &mut Context) -> Poll<&'a usize> {
+///         Poll::Ready(self.0)
+///     }
+/// }
+///
+/// fn plugged<'a>(x: &'a usize) -> <H1RefFuture as PlugLifetime<'a>>::T {
+///     RefFuture(x)
+/// }
+/// ```
+#[macro_export]
+macro_rules! define_h1_future {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident<$lt:lifetime>($($fields:tt)*) as $form:ident;
+    ) => {
+        $(#[$attr])*
+        $vis struct $name<$lt>($($fields)*);
+
+        $crate::define_h1_future!(@form $vis $name $form);
+    };
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident<$lt:lifetime> { $($fields:tt)* } as $form:ident;
+    ) => {
+        $(#[$attr])*
+        $vis struct $name<$lt> { $($fields)* }
+
+        $crate::define_h1_future!(@form $vis $name $form);
+    };
+    (@form $vis:vis $name:ident $form:ident) => {
+        #[doc = concat!("HKT `", stringify!($name), "<'a>` with a lifetime slot.")]
+        $vis struct $form;
+
+        impl<'a> $crate::plug::PlugLifetime<'a> for $form {
+            type T = $name<'a>;
+        }
+    };
+}
+
+/// Implement traits from this crate for a wrapper type by delegating to one of
+/// its fields.
+///
+/// The wrapper is followed by `=>`, the field, its type, and the traits to
+/// implement. Supported traits are `Sequence`, `SequenceMut`, `WithCapacity`,
+/// `Capacity`, `MapFactory`, `Lock`, `ScopedLock`, `RwLockLike` and `Rcb`.
+/// Generic wrappers can declare plain type parameters:
+///
+/// ```
+/// use generic_std::{delegate_generic_std, lock::Lock};
+/// use std::sync::Mutex;
+///
+/// struct Tracked<T>(Mutex<T>);
+///
+/// delegate_generic_std!(Tracked<T> => self.0: Mutex<T>; Lock);
+///
+/// let tracked = <Tracked<usize> as Lock<_>>::new(1);
+/// *tracked.lock().unwrap() += 1;
+///
+/// assert_eq!(*tracked.lock().unwrap(), 2);
+/// ```
+///
+/// Traits that construct or consume the wrapper, such as `Lock::new` or
+/// `Rcb::try_unwrap`, require the field to be the only one in the wrapper.
+/// `Rcb` also requires the wrapper to implement `Clone` and `Deref` itself.
+#[macro_export]
+macro_rules! delegate_generic_std {
+    (
+        $name:ident $(<$($gen:ident),+ $(,)?>)? => self.$field:tt: $inner:ty;
+        $($trait:ident),+ $(,)?
+    ) => {
+        $crate::delegate_generic_std!(
+            @each [$($($gen),+)?] $name $field $inner; $($trait),+
+        );
+    };
+    (
+        @each $gen:tt $name:ident $field:tt $inner:ty; $($trait:ident),+
+    ) => {
+        $(
+            $crate::delegate_generic_std!(
+                @impl $trait $gen $name $field $inner
+            );
+        )+
+    };
+    (@impl Sequence [$($gen:ident),*] $name:ident $field:tt $inner:ty) => {
+        impl<__T, $($gen),*> $crate::Sequence<__T> for $name<$($gen),*>
+        where
+            $inner: $crate::Sequence<__T>,
+        {
+            type H1Iterator = <$inner as $crate::Sequence<__T>>::H1Iterator;
+
+            fn len(&self) -> usize {
+                <$inner as $crate::Sequence<__T>>::len(&self.$field)
+            }
+
+            fn is_empty(&self) -> bool {
+                <$inner as $crate::Sequence<__T>>::is_empty(&self.$field)
+            }
+
+            fn contains(&self, x: &__T) -> bool
+            where
+                __T: PartialEq,
+            {
+                <$inner as $crate::Sequence<__T>>::contains(&self.$field, x)
+            }
+
+            fn get(&self, index: usize) -> Option<&__T> {
+                <$inner as $crate::Sequence<__T>>::get(&self.$field, index)
+            }
+
+            fn first(&self) -> Option<&__T> {
+                <$inner as $crate::Sequence<__T>>::first(&self.$field)
+            }
+
+            fn last(&self) -> Option<&__T> {
+                <$inner as $crate::Sequence<__T>>::last(&self.$field)
+            }
+
+            fn iter<'a>(
+                &'a self,
+            ) -> <Self::H1Iterator as $crate::plug::PlugLifetime<'a>>::T
+            where
+                <Self::H1Iterator as $crate::plug::PlugLifetime<'a>>::T:
+                    $crate::StreamingIterator,
+            {
+                <$inner as $crate::Sequence<__T>>::iter(&self.$field)
+            }
+        }
+    };
+    (@impl SequenceMut [$($gen:ident),*] $name:ident $field:tt $inner:ty) => {
+        impl<__T, $($gen),*> $crate::SequenceMut<__T> for $name<$($gen),*>
+        where
+            $inner: $crate::SequenceMut<__T>,
+        {
+            fn capacity(&self) -> usize {
+                <$inner as $crate::SequenceMut<__T>>::capacity(&self.$field)
+            }
+
+            fn clear(&mut self) {
+                <$inner as $crate::SequenceMut<__T>>::clear(&mut self.$field)
+            }
+
+            fn reserve(&mut self, additional: usize) {
+                <$inner as $crate::SequenceMut<__T>>::reserve(
+                    &mut self.$field,
+                    additional,
+                )
+            }
+
+            fn reserve_exact(&mut self, additional: usize) {
+                <$inner as $crate::SequenceMut<__T>>::reserve_exact(
+                    &mut self.$field,
+                    additional,
+                )
+            }
+
+            fn shrink_to_fit(&mut self) {
+                <$inner as $crate::SequenceMut<__T>>::shrink_to_fit(
+                    &mut self.$field,
+                )
+            }
+
+            fn push(&mut self, x: __T) {
+                <$inner as $crate::SequenceMut<__T>>::push(&mut self.$field, x)
+            }
+
+            fn pop(&mut self) -> Option<__T> {
+                <$inner as $crate::SequenceMut<__T>>::pop(&mut self.$field)
+            }
+
+            fn insert(&mut self, index: usize, x: __T) {
+                <$inner as $crate::SequenceMut<__T>>::insert(
+                    &mut self.$field,
+                    index,
+                    x,
+                )
+            }
+
+            fn remove(&mut self, index: usize) -> __T {
+                <$inner as $crate::SequenceMut<__T>>::remove(
+                    &mut self.$field,
+                    index,
+                )
+            }
+        }
+    };
+    (@impl WithCapacity [$($gen:ident),*] $name:ident $field:tt $inner:ty) => {
+        impl<$($gen),*> $crate::WithCapacity for $name<$($gen),*>
+        where
+            $inner: $crate::WithCapacity,
+        {
+            fn with_capacity(capacity: usize) -> Self {
+                Self {
+                    $field: <$inner as $crate::WithCapacity>::with_capacity(
+                        capacity,
+                    ),
+                }
+            }
+        }
+    };
+    (@impl Capacity [$($gen:ident),*] $name:ident $field:tt $inner:ty) => {
+        impl<$($gen),*> $crate::Capacity for $name<$($gen),*>
+        where
+            $inner: $crate::Capacity,
+        {
+            fn capacity(&self) -> usize {
+                <$inner as $crate::Capacity>::capacity(&self.$field)
+            }
+        }
+    };
+    (@impl MapFactory [$($gen:ident),*] $name:ident $field:tt $inner:ty) => {
+        impl<__S, $($gen),*> $crate::MapFactory<__S> for $name<$($gen),*>
+        where
+            $inner: $crate::MapFactory<__S>,
+            __S: ::core::hash::BuildHasher,
+        {
+            fn with_hasher(hasher: __S) -> Self {
+                Self {
+                    $field: <$inner as $crate::MapFactory<__S>>::with_hasher(
+                        hasher,
+                    ),
+                }
+            }
+
+            fn with_capacity_and_hasher(capacity: usize, hasher: __S) -> Self {
+                Self {
+                    $field: <$inner as $crate::MapFactory<
+                        __S,
+                    >>::with_capacity_and_hasher(
+                        capacity, hasher
+                    ),
+                }
+            }
+
+            fn hasher(&self) -> &__S {
+                <$inner as $crate::MapFactory<__S>>::hasher(&self.$field)
+            }
+        }
+    };
+    (@impl Lock [$($gen:ident),*] $name:ident $field:tt $inner:ty) => {
+        impl<__T, $($gen),*> $crate::lock::Lock<__T> for $name<$($gen),*>
+        where
+            $inner: $crate::lock::Lock<__T>,
+        {
+            type H1Guard = <$inner as $crate::lock::Lock<__T>>::H1Guard;
+
+            fn new(x: __T) -> Self {
+                Self {
+                    $field: <$inner as $crate::lock::Lock<__T>>::new(x),
+                }
+            }
+
+            fn lock_with<'a>(
+                &'a self,
+                policy: $crate::lock::PoisonPolicy,
+            ) -> Result<
+                <Self::H1Guard as $crate::plug::PlugLifetime<'a>>::T,
+                $crate::lock::LockError,
+            >
+            where
+                <Self::H1Guard as $crate::plug::PlugLifetime<'a>>::T:
+                    ::core::ops::DerefMut<Target = __T>,
+            {
+                <$inner as $crate::lock::Lock<__T>>::lock_with(
+                    &self.$field,
+                    policy,
+                )
+            }
+
+            fn try_lock<'a>(
+                &'a self,
+            ) -> Option<<Self::H1Guard as $crate::plug::PlugLifetime<'a>>::T>
+            where
+                <Self::H1Guard as $crate::plug::PlugLifetime<'a>>::T:
+                    ::core::ops::DerefMut<Target = __T>,
+            {
+                <$inner as $crate::lock::Lock<__T>>::try_lock(&self.$field)
+            }
+        }
+    };
+    (@impl ScopedLock [$($gen:ident),*] $name:ident $field:tt $inner:ty) => {
+        impl<__T, $($gen),*> $crate::lock::ScopedLock<__T> for $name<$($gen),*>
+        where
+            __T: ?Sized,
+            $inner: $crate::lock::ScopedLock<__T>,
+        {
+            fn with(&self, f: &mut dyn FnMut(&__T)) {
+                <$inner as $crate::lock::ScopedLock<__T>>::with(&self.$field, f)
+            }
+
+            fn with_mut(&self, f: &mut dyn FnMut(&mut __T)) {
+                <$inner as $crate::lock::ScopedLock<__T>>::with_mut(
+                    &self.$field,
+                    f,
+                )
+            }
+        }
+    };
+    (@impl RwLockLike [$($gen:ident),*] $name:ident $field:tt $inner:ty) => {
+        impl<__T, $($gen),*> $crate::lock::RwLockLike<__T> for $name<$($gen),*>
+        where
+            $inner: $crate::lock::RwLockLike<__T>,
+            Self: $crate::lock::Lock<__T>,
+        {
+            type H1ReadGuard =
+                <$inner as $crate::lock::RwLockLike<__T>>::H1ReadGuard;
+
+            fn read_with<'a>(
+                &'a self,
+                policy: $crate::lock::PoisonPolicy,
+            ) -> Result<
+                <Self::H1ReadGuard as $crate::plug::PlugLifetime<'a>>::T,
+                $crate::lock::LockError,
+            >
+            where
+                <Self::H1ReadGuard as $crate::plug::PlugLifetime<'a>>::T:
+                    ::core::ops::Deref<Target = __T>,
+            {
+                <$inner as $crate::lock::RwLockLike<__T>>::read_with(
+                    &self.$field,
+                    policy,
+                )
+            }
+
+            fn try_read<'a>(
+                &'a self,
+            ) -> Option<<Self::H1ReadGuard as $crate::plug::PlugLifetime<'a>>::T>
+            where
+                <Self::H1ReadGuard as $crate::plug::PlugLifetime<'a>>::T:
+                    ::core::ops::Deref<Target = __T>,
+            {
+                <$inner as $crate::lock::RwLockLike<__T>>::try_read(
+                    &self.$field,
+                )
+            }
+        }
+    };
+    (@impl Rcb [$($gen:ident),*] $name:ident $field:tt $inner:ty) => {
+        impl<__T, $($gen),*> $crate::Rcb<__T> for $name<$($gen),*>
+        where
+            __T: ?Sized,
+            $inner: $crate::Rcb<__T>,
+            Self: Clone + ::core::ops::Deref<Target = __T>,
+        {
+            fn new(x: __T) -> Self
+            where
+                __T: Sized,
+            {
+                Self {
+                    $field: <$inner as $crate::Rcb<__T>>::new(x),
+                }
+            }
+
+            $crate::__delegate_rcb_try_new!($field $inner);
+
+            fn try_unwrap(this: Self) -> Result<__T, Self>
+            where
+                __T: Sized,
+            {
+                <$inner as $crate::Rcb<__T>>::try_unwrap(this.$field)
+                    .map_err(|x| Self { $field: x })
+            }
+
+            fn into_raw(this: Self) -> *const __T {
+                <$inner as $crate::Rcb<__T>>::into_raw(this.$field)
+            }
+
+            unsafe fn from_raw(ptr: *const __T) -> Self {
+                Self {
+                    $field: <$inner as $crate::Rcb<__T>>::from_raw(ptr),
+                }
+            }
+        }
+    };
+}
+
+// `cfg` attributes in macro output are evaluated in the calling crate, so the
+// feature check must happen here instead
+#[cfg(feature = "allocator_api")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __delegate_rcb_try_new {
+    ($field:tt $inner:ty) => {
+        fn try_new(x: __T) -> Result<Self, ::core::alloc::AllocError>
+        where
+            __T: Sized,
+        {
+            <$inner as $crate::Rcb<__T>>::try_new(x).map(|x| Self { $field: x })
+        }
+    };
+}
+
+#[cfg(not(feature = "allocator_api"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __delegate_rcb_try_new {
+    ($field:tt $inner:ty) => {};
+}
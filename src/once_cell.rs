@@ -0,0 +1,65 @@
+//! Trait impls for types from the
+//! [once_cell](https://crates.io/crates/once_cell) crate.
+
+use crate::lock::{LazyLike, OnceLike};
+use ::once_cell::{sync, unsync};
+
+impl<T> OnceLike<T> for unsync::OnceCell<T> {
+    fn new() -> Self {
+        unsync::OnceCell::<T>::new()
+    }
+
+    fn get(&self) -> Option<&T> {
+        unsync::OnceCell::<T>::get(self)
+    }
+
+    fn set(&self, x: T) -> Result<(), T> {
+        unsync::OnceCell::<T>::set(self, x)
+    }
+
+    fn get_or_init<F>(&self, f: F) -> &T
+    where
+        F: FnOnce() -> T,
+    {
+        unsync::OnceCell::<T>::get_or_init(self, f)
+    }
+}
+
+impl<T> OnceLike<T> for sync::OnceCell<T> {
+    fn new() -> Self {
+        sync::OnceCell::<T>::new()
+    }
+
+    fn get(&self) -> Option<&T> {
+        sync::OnceCell::<T>::get(self)
+    }
+
+    fn set(&self, x: T) -> Result<(), T> {
+        sync::OnceCell::<T>::set(self, x)
+    }
+
+    fn get_or_init<F>(&self, f: F) -> &T
+    where
+        F: FnOnce() -> T,
+    {
+        sync::OnceCell::<T>::get_or_init(self, f)
+    }
+}
+
+impl<T, F> LazyLike<T> for unsync::Lazy<T, F>
+where
+    F: FnOnce() -> T,
+{
+    fn force(this: &Self) -> &T {
+        unsync::Lazy::<T, F>::force(this)
+    }
+}
+
+impl<T, F> LazyLike<T> for sync::Lazy<T, F>
+where
+    F: FnOnce() -> T,
+{
+    fn force(this: &Self) -> &T {
+        sync::Lazy::<T, F>::force(this)
+    }
+}
@@ -0,0 +1,292 @@
+//! Composable accessors for parts of a structure.
+//!
+//! A [`Lens`](trait.Lens.html) focuses on a part that is always present, such
+//! as a struct field. A [`Prism`](trait.Prism.html) focuses on a part that may
+//! be absent, such as an enum variant. Optics compose with
+//! [`compose`](fn.compose.html), which makes it possible to reach into fields
+//! whose types are chosen by a kind parameter:
+//!
+//! ```
+//! use generic_std::{
+//!     boxed::H1Box,
+//!     optics::{self, Lens},
+//!     plug::{H1Identity, PlugType},
+//! };
+//!
+//! struct User<K: PlugType<String>> {
+//!     name: K::T,
+//! }
+//!
+//! fn rename<K, L>(user: &mut User<K>, inner: L, name: &str)
+//! where
+//!     K: PlugType<String>,
+//!     L: Lens<K::T, String>,
+//! {
+//!     let name_lens = optics::lens::<User<K>, K::T>(|u| &u.name, |u| &mut u.name);
+//!     optics::compose(name_lens, inner).set(user, name.to_string());
+//! }
+//!
+//! let mut user = User::<H1Box> { name: Box::new("a".to_string()) };
+//! rename(&mut user, optics::boxed(), "b");
+//! assert_eq!(*user.name, "b");
+//!
+//! let mut user = User::<H1Identity> { name: "a".to_string() };
+//! rename(&mut user, optics::id(), "c");
+//! assert_eq!(user.name, "c");
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::{marker::PhantomData, mem};
+
+/// Optic focusing on a part `A` that is always present in `S`.
+pub trait Lens<S, A> {
+    /// Borrow the focused part.
+    fn get<'a>(&self, s: &'a S) -> &'a A
+    where
+        Self: 'a;
+
+    /// Mutably borrow the focused part.
+    fn get_mut<'a>(&self, s: &'a mut S) -> &'a mut A
+    where
+        Self: 'a;
+
+    /// Replace the focused part with `a`, returning the old value.
+    fn set(&self, s: &mut S, a: A) -> A {
+        mem::replace(self.get_mut(s), a)
+    }
+
+    /// Update the focused part in place with `f`.
+    fn modify<F>(&self, s: &mut S, f: F)
+    where
+        F: FnOnce(&mut A),
+    {
+        f(self.get_mut(s))
+    }
+}
+
+/// Optic focusing on a part `A` that may be absent from `S`, but that can
+/// always be used to build an `S`.
+pub trait Prism<S, A> {
+    /// Borrow the focused part, if present.
+    fn preview<'a>(&self, s: &'a S) -> Option<&'a A>
+    where
+        Self: 'a;
+
+    /// Mutably borrow the focused part, if present.
+    fn preview_mut<'a>(&self, s: &'a mut S) -> Option<&'a mut A>
+    where
+        Self: 'a;
+
+    /// Build an `S` from the focused part.
+    fn review(&self, a: A) -> S;
+
+    /// Update the focused part in place with `f`, if present. Returns whether
+    /// it was present.
+    fn modify<F>(&self, s: &mut S, f: F) -> bool
+    where
+        F: FnOnce(&mut A),
+    {
+        self.preview_mut(s).map(f).is_some()
+    }
+}
+
+/// Lens built from a pair of accessor functions. See [`lens`](fn.lens.html).
+pub struct FnLens<S, A> {
+    get: for<'a> fn(&'a S) -> &'a A,
+    get_mut: for<'a> fn(&'a mut S) -> &'a mut A,
+}
+
+impl<S, A> Clone for FnLens<S, A> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S, A> Copy for FnLens<S, A> {}
+
+impl<S, A> Lens<S, A> for FnLens<S, A> {
+    fn get<'a>(&self, s: &'a S) -> &'a A
+    where
+        Self: 'a,
+    {
+        (self.get)(s)
+    }
+
+    fn get_mut<'a>(&self, s: &'a mut S) -> &'a mut A
+    where
+        Self: 'a,
+    {
+        (self.get_mut)(s)
+    }
+}
+
+/// Prism built from accessor and constructor functions. See
+/// [`prism`](fn.prism.html).
+pub struct FnPrism<S, A> {
+    preview: for<'a> fn(&'a S) -> Option<&'a A>,
+    preview_mut: for<'a> fn(&'a mut S) -> Option<&'a mut A>,
+    review: fn(A) -> S,
+}
+
+impl<S, A> Clone for FnPrism<S, A> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S, A> Copy for FnPrism<S, A> {}
+
+impl<S, A> Prism<S, A> for FnPrism<S, A> {
+    fn preview<'a>(&self, s: &'a S) -> Option<&'a A>
+    where
+        Self: 'a,
+    {
+        (self.preview)(s)
+    }
+
+    fn preview_mut<'a>(&self, s: &'a mut S) -> Option<&'a mut A>
+    where
+        Self: 'a,
+    {
+        (self.preview_mut)(s)
+    }
+
+    fn review(&self, a: A) -> S {
+        (self.review)(a)
+    }
+}
+
+/// Composition of an optic from `S` to `A` with an optic from `A` to `B`. See
+/// [`compose`](fn.compose.html).
+pub struct Compose<O, P, A> {
+    outer: O,
+    inner: P,
+    _phantom: PhantomData<fn() -> A>,
+}
+
+impl<O, P, A> Clone for Compose<O, P, A>
+where
+    O: Clone,
+    P: Clone,
+{
+    fn clone(&self) -> Self {
+        Compose {
+            outer: self.outer.clone(),
+            inner: self.inner.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<O, P, A> Copy for Compose<O, P, A>
+where
+    O: Copy,
+    P: Copy,
+{
+}
+
+impl<S, A, B, O, P> Lens<S, B> for Compose<O, P, A>
+where
+    O: Lens<S, A>,
+    P: Lens<A, B>,
+{
+    fn get<'a>(&self, s: &'a S) -> &'a B
+    where
+        Self: 'a,
+    {
+        self.inner.get(self.outer.get(s))
+    }
+
+    fn get_mut<'a>(&self, s: &'a mut S) -> &'a mut B
+    where
+        Self: 'a,
+    {
+        self.inner.get_mut(self.outer.get_mut(s))
+    }
+}
+
+impl<S, A, B, O, P> Prism<S, B> for Compose<O, P, A>
+where
+    O: Prism<S, A>,
+    P: Prism<A, B>,
+{
+    fn preview<'a>(&self, s: &'a S) -> Option<&'a B>
+    where
+        Self: 'a,
+    {
+        self.outer.preview(s).and_then(|a| self.inner.preview(a))
+    }
+
+    fn preview_mut<'a>(&self, s: &'a mut S) -> Option<&'a mut B>
+    where
+        Self: 'a,
+    {
+        self.outer
+            .preview_mut(s)
+            .and_then(|a| self.inner.preview_mut(a))
+    }
+
+    fn review(&self, b: B) -> S {
+        self.outer.review(self.inner.review(b))
+    }
+}
+
+/// Create a lens from a pair of accessor functions.
+pub fn lens<S, A>(
+    get: for<'a> fn(&'a S) -> &'a A,
+    get_mut: for<'a> fn(&'a mut S) -> &'a mut A,
+) -> FnLens<S, A> {
+    FnLens { get, get_mut }
+}
+
+/// Create a prism from a pair of accessor functions and a constructor.
+pub fn prism<S, A>(
+    preview: for<'a> fn(&'a S) -> Option<&'a A>,
+    preview_mut: for<'a> fn(&'a mut S) -> Option<&'a mut A>,
+    review: fn(A) -> S,
+) -> FnPrism<S, A> {
+    FnPrism {
+        preview,
+        preview_mut,
+        review,
+    }
+}
+
+/// Compose an optic from `S` to `A` with an optic from `A` to `B`. The result
+/// is a lens if both are lenses and a prism if both are prisms.
+pub fn compose<O, P, A>(outer: O, inner: P) -> Compose<O, P, A> {
+    Compose {
+        outer,
+        inner,
+        _phantom: PhantomData,
+    }
+}
+
+/// Lens focusing on the whole structure. This is the lens for the
+/// [`H1Identity`](../plug/struct.H1Identity.html) form.
+pub fn id<T>() -> FnLens<T, T> {
+    lens(|x| x, |x| x)
+}
+
+/// Lens focusing on the contents of a `Box`. This is the lens for the
+/// [`H1Box`](../boxed/struct.H1Box.html) form.
+#[cfg(feature = "alloc")]
+pub fn boxed<T>() -> FnLens<Box<T>, T> {
+    lens(|x| &**x, |x| &mut **x)
+}
+
+/// Prism focusing on the value of `Some`.
+pub fn some<T>() -> FnPrism<Option<T>, T> {
+    prism(Option::as_ref, Option::as_mut, Some)
+}
+
+/// Prism focusing on the value of `Ok`.
+pub fn ok<T, E>() -> FnPrism<Result<T, E>, T> {
+    prism(|x| x.as_ref().ok(), |x| x.as_mut().ok(), Ok)
+}
+
+/// Prism focusing on the value of `Err`.
+pub fn err<T, E>() -> FnPrism<Result<T, E>, E> {
+    prism(|x| x.as_ref().err(), |x| x.as_mut().err(), Err)
+}
@@ -0,0 +1,142 @@
+//! Optional values.
+
+#[cfg(feature = "vec")]
+use crate::{fp::Transform, vec::H1Vec};
+use crate::{
+    fp::{
+        Alternative, Applicative, Functor, Monad, Monoid, Pure, Semigroup,
+        Traversable, Zip,
+    },
+    plug::PlugType,
+};
+#[cfg(feature = "vec")]
+use alloc::vec::Vec;
+use core::option::IntoIter;
+
+/// HKT `Option<T>` with a type slot.
+pub struct H1Option;
+
+impl<T> PlugType<T> for H1Option {
+    type T = Option<T>;
+}
+
+impl<A, B> Functor<A, B> for H1Option {
+    fn fmap<F>(fa: Option<A>, f: F) -> Option<B>
+    where
+        F: FnMut(A) -> B,
+    {
+        fa.map(f)
+    }
+}
+
+impl<A> Pure<A> for H1Option {
+    fn pure(x: A) -> Option<A> {
+        Some(x)
+    }
+}
+
+impl<A, B, C> Applicative<A, B, C> for H1Option {
+    fn map2<F>(fa: Option<A>, fb: Option<B>, mut f: F) -> Option<C>
+    where
+        F: FnMut(A, B) -> C,
+    {
+        Some(f(fa?, fb?))
+    }
+}
+
+impl<A, B> Monad<A, B> for H1Option {
+    fn bind<F>(fa: Option<A>, f: F) -> Option<B>
+    where
+        F: FnMut(A) -> Option<B>,
+    {
+        fa.and_then(f)
+    }
+}
+
+impl<M, A, B> Traversable<M, A, B> for H1Option
+where
+    M: Pure<Option<B>> + Functor<B, Option<B>>,
+{
+    fn traverse<F>(ta: Option<A>, mut f: F) -> <M as PlugType<Option<B>>>::T
+    where
+        F: FnMut(A) -> <M as PlugType<B>>::T,
+    {
+        match ta {
+            Some(a) => M::fmap(f(a), Some),
+            None => M::pure(None),
+        }
+    }
+}
+
+impl<A, B, C> Zip<A, B, C> for H1Option {
+    fn zip_with<F>(fa: Option<A>, fb: Option<B>, mut f: F) -> Option<C>
+    where
+        F: FnMut(A, B) -> C,
+    {
+        Some(f(fa?, fb?))
+    }
+}
+
+/// HKT `std::option::IntoIter<T>` with a type slot.
+pub struct H1OptionIntoIter;
+
+impl<T> PlugType<T> for H1OptionIntoIter {
+    type T = IntoIter<T>;
+}
+
+impl<A, B> Functor<A, B> for H1OptionIntoIter {
+    fn fmap<F>(mut fa: IntoIter<A>, f: F) -> IntoIter<B>
+    where
+        F: FnMut(A) -> B,
+    {
+        Iterator::next(&mut fa).map(f).into_iter()
+    }
+}
+
+impl<A> Pure<A> for H1OptionIntoIter {
+    fn pure(x: A) -> IntoIter<A> {
+        Some(x).into_iter()
+    }
+}
+
+#[cfg(feature = "vec")]
+impl<T> Transform<H1Vec, T> for H1Option {
+    fn transform(fa: Option<T>) -> Vec<T> {
+        fa.into_iter().collect()
+    }
+}
+
+/// Choice keeps the first `Some`.
+impl<A> Alternative<A> for H1Option {
+    fn empty() -> Option<A> {
+        None
+    }
+
+    fn or(x: Option<A>, y: Option<A>) -> Option<A> {
+        x.or(y)
+    }
+}
+
+/// Combines the contained values if both are `Some`, otherwise returns
+/// whichever is `Some`.
+impl<T> Semigroup for Option<T>
+where
+    T: Semigroup,
+{
+    fn combine(self, other: Self) -> Self {
+        match (self, other) {
+            (Some(x), Some(y)) => Some(x.combine(y)),
+            (x, None) => x,
+            (None, y) => y,
+        }
+    }
+}
+
+impl<T> Monoid for Option<T>
+where
+    T: Semigroup,
+{
+    fn empty() -> Self {
+        None
+    }
+}
@@ -0,0 +1,515 @@
+//! HKT forms and lock trait impls for the
+//! [parking_lot](https://crates.io/crates/parking_lot) crate.
+//!
+//! `parking_lot` locks can't be poisoned, so poison policies are ignored.
+
+use crate::{
+    lock::{
+        CondvarLike, Lock, LockError, MappableGuard, MappableGuardMut,
+        PoisonPolicy, RwLockLike, ScopedLock, UpgradableRwLock,
+    },
+    plug::{PlugLifetime, PlugType},
+};
+use ::parking_lot::{
+    Condvar, MappedMutexGuard, MappedRwLockReadGuard, MappedRwLockWriteGuard,
+    Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockUpgradableReadGuard,
+    RwLockWriteGuard,
+};
+use std::{
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+/// HKT `parking_lot::Mutex<T>` with a type slot.
+pub struct H1Mutex;
+
+impl<T> PlugType<T> for H1Mutex {
+    type T = Mutex<T>;
+}
+
+/// HKT `parking_lot::RwLock<T>` with a type slot.
+pub struct H1RwLock;
+
+impl<T> PlugType<T> for H1RwLock {
+    type T = RwLock<T>;
+}
+
+/// HKT `parking_lot::MutexGuard<'a, T>` with a lifetime and a type slot.
+pub struct H2MutexGuard;
+
+impl<'a> PlugLifetime<'a> for H2MutexGuard {
+    type T = H1MutexGuard<'a>;
+}
+
+/// HKT `parking_lot::MutexGuard<'a, T>` with a type slot.
+pub struct H1MutexGuard<'a>(PhantomData<&'a ()>);
+
+impl<'a, T> PlugType<T> for H1MutexGuard<'a>
+where
+    T: 'a + ?Sized,
+{
+    type T = MutexGuard<'a, T>;
+}
+
+/// HKT `parking_lot::MutexGuard<'a, T>` with a lifetime slot.
+pub struct TypedH1MutexGuard<T>(PhantomData<T>)
+where
+    T: ?Sized;
+
+impl<'a, T> PlugLifetime<'a> for TypedH1MutexGuard<T>
+where
+    T: 'a + ?Sized,
+{
+    type T = MutexGuard<'a, T>;
+}
+
+/// HKT `parking_lot::RwLockReadGuard<'a, T>` with a lifetime and a type slot.
+pub struct H2RwLockReadGuard;
+
+impl<'a> PlugLifetime<'a> for H2RwLockReadGuard {
+    type T = H1RwLockReadGuard<'a>;
+}
+
+/// HKT `parking_lot::RwLockReadGuard<'a, T>` with a type slot.
+pub struct H1RwLockReadGuard<'a>(PhantomData<&'a ()>);
+
+impl<'a, T> PlugType<T> for H1RwLockReadGuard<'a>
+where
+    T: 'a + ?Sized,
+{
+    type T = RwLockReadGuard<'a, T>;
+}
+
+/// HKT `parking_lot::RwLockReadGuard<'a, T>` with a lifetime slot.
+pub struct TypedH1RwLockReadGuard<T>(PhantomData<T>)
+where
+    T: ?Sized;
+
+impl<'a, T> PlugLifetime<'a> for TypedH1RwLockReadGuard<T>
+where
+    T: 'a + ?Sized,
+{
+    type T = RwLockReadGuard<'a, T>;
+}
+
+/// HKT `parking_lot::RwLockWriteGuard<'a, T>` with a lifetime and a type slot.
+pub struct H2RwLockWriteGuard;
+
+impl<'a> PlugLifetime<'a> for H2RwLockWriteGuard {
+    type T = H1RwLockWriteGuard<'a>;
+}
+
+/// HKT `parking_lot::RwLockWriteGuard<'a, T>` with a type slot.
+pub struct H1RwLockWriteGuard<'a>(PhantomData<&'a ()>);
+
+impl<'a, T> PlugType<T> for H1RwLockWriteGuard<'a>
+where
+    T: 'a + ?Sized,
+{
+    type T = RwLockWriteGuard<'a, T>;
+}
+
+/// HKT `parking_lot::RwLockWriteGuard<'a, T>` with a lifetime slot.
+pub struct TypedH1RwLockWriteGuard<T>(PhantomData<T>)
+where
+    T: ?Sized;
+
+impl<'a, T> PlugLifetime<'a> for TypedH1RwLockWriteGuard<T>
+where
+    T: 'a + ?Sized,
+{
+    type T = RwLockWriteGuard<'a, T>;
+}
+
+/// HKT `parking_lot::RwLockUpgradableReadGuard<'a, T>` with a lifetime and a
+/// type slot.
+pub struct H2RwLockUpgradableReadGuard;
+
+impl<'a> PlugLifetime<'a> for H2RwLockUpgradableReadGuard {
+    type T = H1RwLockUpgradableReadGuard<'a>;
+}
+
+/// HKT `parking_lot::RwLockUpgradableReadGuard<'a, T>` with a type slot.
+pub struct H1RwLockUpgradableReadGuard<'a>(PhantomData<&'a ()>);
+
+impl<'a, T> PlugType<T> for H1RwLockUpgradableReadGuard<'a>
+where
+    T: 'a + ?Sized,
+{
+    type T = RwLockUpgradableReadGuard<'a, T>;
+}
+
+/// HKT `parking_lot::RwLockUpgradableReadGuard<'a, T>` with a lifetime slot.
+pub struct TypedH1RwLockUpgradableReadGuard<T>(PhantomData<T>)
+where
+    T: ?Sized;
+
+impl<'a, T> PlugLifetime<'a> for TypedH1RwLockUpgradableReadGuard<T>
+where
+    T: 'a + ?Sized,
+{
+    type T = RwLockUpgradableReadGuard<'a, T>;
+}
+
+/// HKT `parking_lot::MappedMutexGuard<'a, T>` with a lifetime and a type slot.
+pub struct H2MappedMutexGuard;
+
+impl<'a> PlugLifetime<'a> for H2MappedMutexGuard {
+    type T = H1MappedMutexGuard<'a>;
+}
+
+/// HKT `parking_lot::MappedMutexGuard<'a, T>` with a type slot.
+pub struct H1MappedMutexGuard<'a>(PhantomData<&'a ()>);
+
+impl<'a, T> PlugType<T> for H1MappedMutexGuard<'a>
+where
+    T: 'a + ?Sized,
+{
+    type T = MappedMutexGuard<'a, T>;
+}
+
+/// HKT `parking_lot::MappedMutexGuard<'a, T>` with a lifetime slot.
+pub struct TypedH1MappedMutexGuard<T>(PhantomData<T>)
+where
+    T: ?Sized;
+
+impl<'a, T> PlugLifetime<'a> for TypedH1MappedMutexGuard<T>
+where
+    T: 'a + ?Sized,
+{
+    type T = MappedMutexGuard<'a, T>;
+}
+
+/// HKT `parking_lot::MappedRwLockReadGuard<'a, T>` with a lifetime and a
+/// type slot.
+pub struct H2MappedRwLockReadGuard;
+
+impl<'a> PlugLifetime<'a> for H2MappedRwLockReadGuard {
+    type T = H1MappedRwLockReadGuard<'a>;
+}
+
+/// HKT `parking_lot::MappedRwLockReadGuard<'a, T>` with a type slot.
+pub struct H1MappedRwLockReadGuard<'a>(PhantomData<&'a ()>);
+
+impl<'a, T> PlugType<T> for H1MappedRwLockReadGuard<'a>
+where
+    T: 'a + ?Sized,
+{
+    type T = MappedRwLockReadGuard<'a, T>;
+}
+
+/// HKT `parking_lot::MappedRwLockReadGuard<'a, T>` with a lifetime slot.
+pub struct TypedH1MappedRwLockReadGuard<T>(PhantomData<T>)
+where
+    T: ?Sized;
+
+impl<'a, T> PlugLifetime<'a> for TypedH1MappedRwLockReadGuard<T>
+where
+    T: 'a + ?Sized,
+{
+    type T = MappedRwLockReadGuard<'a, T>;
+}
+
+/// HKT `parking_lot::MappedRwLockWriteGuard<'a, T>` with a lifetime and a
+/// type slot.
+pub struct H2MappedRwLockWriteGuard;
+
+impl<'a> PlugLifetime<'a> for H2MappedRwLockWriteGuard {
+    type T = H1MappedRwLockWriteGuard<'a>;
+}
+
+/// HKT `parking_lot::MappedRwLockWriteGuard<'a, T>` with a type slot.
+pub struct H1MappedRwLockWriteGuard<'a>(PhantomData<&'a ()>);
+
+impl<'a, T> PlugType<T> for H1MappedRwLockWriteGuard<'a>
+where
+    T: 'a + ?Sized,
+{
+    type T = MappedRwLockWriteGuard<'a, T>;
+}
+
+/// HKT `parking_lot::MappedRwLockWriteGuard<'a, T>` with a lifetime slot.
+pub struct TypedH1MappedRwLockWriteGuard<T>(PhantomData<T>)
+where
+    T: ?Sized;
+
+impl<'a, T> PlugLifetime<'a> for TypedH1MappedRwLockWriteGuard<T>
+where
+    T: 'a + ?Sized,
+{
+    type T = MappedRwLockWriteGuard<'a, T>;
+}
+
+impl<T> Lock<T> for Mutex<T>
+where
+    T: 'static,
+{
+    type H1Guard = TypedH1MutexGuard<T>;
+
+    fn new(x: T) -> Self {
+        Mutex::<T>::new(x)
+    }
+
+    fn lock_with<'a>(
+        &'a self,
+        _: PoisonPolicy,
+    ) -> Result<<Self::H1Guard as PlugLifetime<'a>>::T, LockError>
+    where
+        <Self::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = T>,
+    {
+        Ok(Mutex::<T>::lock(self))
+    }
+
+    fn try_lock<'a>(&'a self) -> Option<<Self::H1Guard as PlugLifetime<'a>>::T>
+    where
+        <Self::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = T>,
+    {
+        Mutex::<T>::try_lock(self)
+    }
+}
+
+/// Locking grants write access.
+impl<T> Lock<T> for RwLock<T>
+where
+    T: 'static,
+{
+    type H1Guard = TypedH1RwLockWriteGuard<T>;
+
+    fn new(x: T) -> Self {
+        RwLock::<T>::new(x)
+    }
+
+    fn lock_with<'a>(
+        &'a self,
+        _: PoisonPolicy,
+    ) -> Result<<Self::H1Guard as PlugLifetime<'a>>::T, LockError>
+    where
+        <Self::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = T>,
+    {
+        Ok(RwLock::<T>::write(self))
+    }
+
+    fn try_lock<'a>(&'a self) -> Option<<Self::H1Guard as PlugLifetime<'a>>::T>
+    where
+        <Self::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = T>,
+    {
+        RwLock::<T>::try_write(self)
+    }
+}
+
+impl<T> RwLockLike<T> for RwLock<T>
+where
+    T: 'static,
+{
+    type H1ReadGuard = TypedH1RwLockReadGuard<T>;
+
+    fn read_with<'a>(
+        &'a self,
+        _: PoisonPolicy,
+    ) -> Result<<Self::H1ReadGuard as PlugLifetime<'a>>::T, LockError>
+    where
+        <Self::H1ReadGuard as PlugLifetime<'a>>::T: Deref<Target = T>,
+    {
+        Ok(RwLock::<T>::read(self))
+    }
+
+    fn try_read<'a>(
+        &'a self,
+    ) -> Option<<Self::H1ReadGuard as PlugLifetime<'a>>::T>
+    where
+        <Self::H1ReadGuard as PlugLifetime<'a>>::T: Deref<Target = T>,
+    {
+        RwLock::<T>::try_read(self)
+    }
+}
+
+impl<T> ScopedLock<T> for Mutex<T>
+where
+    T: ?Sized,
+{
+    fn with(&self, f: &mut dyn FnMut(&T)) {
+        f(&Mutex::<T>::lock(self))
+    }
+
+    fn with_mut(&self, f: &mut dyn FnMut(&mut T)) {
+        f(&mut Mutex::<T>::lock(self))
+    }
+}
+
+impl<T> ScopedLock<T> for RwLock<T>
+where
+    T: ?Sized,
+{
+    fn with(&self, f: &mut dyn FnMut(&T)) {
+        f(&RwLock::<T>::read(self))
+    }
+
+    fn with_mut(&self, f: &mut dyn FnMut(&mut T)) {
+        f(&mut RwLock::<T>::write(self))
+    }
+}
+
+impl<T> UpgradableRwLock<T> for RwLock<T>
+where
+    T: 'static,
+{
+    type H1UpgradableGuard = TypedH1RwLockUpgradableReadGuard<T>;
+
+    fn upgradable_read_with<'a>(
+        &'a self,
+        _: PoisonPolicy,
+    ) -> Result<<Self::H1UpgradableGuard as PlugLifetime<'a>>::T, LockError>
+    where
+        <Self::H1UpgradableGuard as PlugLifetime<'a>>::T: Deref<Target = T>,
+    {
+        Ok(RwLock::<T>::upgradable_read(self))
+    }
+
+    fn upgrade<'a>(
+        guard: <Self::H1UpgradableGuard as PlugLifetime<'a>>::T,
+    ) -> <Self::H1Guard as PlugLifetime<'a>>::T
+    where
+        <Self::H1UpgradableGuard as PlugLifetime<'a>>::T: Deref<Target = T>,
+        <Self::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = T>,
+    {
+        RwLockUpgradableReadGuard::<T>::upgrade(guard)
+    }
+}
+
+impl<T, U> MappableGuardMut<T, U> for TypedH1MutexGuard<T>
+where
+    T: 'static + ?Sized,
+    U: 'static + ?Sized,
+{
+    type H1Mapped = TypedH1MappedMutexGuard<U>;
+
+    fn map_guard_mut<'a, F>(
+        guard: <Self as PlugLifetime<'a>>::T,
+        f: F,
+    ) -> <Self::H1Mapped as PlugLifetime<'a>>::T
+    where
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        MutexGuard::<T>::map(guard, f)
+    }
+}
+
+impl<T, U> MappableGuardMut<T, U> for TypedH1MappedMutexGuard<T>
+where
+    T: 'static + ?Sized,
+    U: 'static + ?Sized,
+{
+    type H1Mapped = TypedH1MappedMutexGuard<U>;
+
+    fn map_guard_mut<'a, F>(
+        guard: <Self as PlugLifetime<'a>>::T,
+        f: F,
+    ) -> <Self::H1Mapped as PlugLifetime<'a>>::T
+    where
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        MappedMutexGuard::<T>::map(guard, f)
+    }
+}
+
+impl<T, U> MappableGuard<T, U> for TypedH1RwLockReadGuard<T>
+where
+    T: 'static + ?Sized,
+    U: 'static + ?Sized,
+{
+    type H1Mapped = TypedH1MappedRwLockReadGuard<U>;
+
+    fn map_guard<'a, F>(
+        guard: <Self as PlugLifetime<'a>>::T,
+        f: F,
+    ) -> <Self::H1Mapped as PlugLifetime<'a>>::T
+    where
+        F: FnOnce(&T) -> &U,
+    {
+        RwLockReadGuard::<T>::map(guard, f)
+    }
+}
+
+impl<T, U> MappableGuard<T, U> for TypedH1MappedRwLockReadGuard<T>
+where
+    T: 'static + ?Sized,
+    U: 'static + ?Sized,
+{
+    type H1Mapped = TypedH1MappedRwLockReadGuard<U>;
+
+    fn map_guard<'a, F>(
+        guard: <Self as PlugLifetime<'a>>::T,
+        f: F,
+    ) -> <Self::H1Mapped as PlugLifetime<'a>>::T
+    where
+        F: FnOnce(&T) -> &U,
+    {
+        MappedRwLockReadGuard::<T>::map(guard, f)
+    }
+}
+
+impl<T, U> MappableGuardMut<T, U> for TypedH1RwLockWriteGuard<T>
+where
+    T: 'static + ?Sized,
+    U: 'static + ?Sized,
+{
+    type H1Mapped = TypedH1MappedRwLockWriteGuard<U>;
+
+    fn map_guard_mut<'a, F>(
+        guard: <Self as PlugLifetime<'a>>::T,
+        f: F,
+    ) -> <Self::H1Mapped as PlugLifetime<'a>>::T
+    where
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        RwLockWriteGuard::<T>::map(guard, f)
+    }
+}
+
+impl<T, U> MappableGuardMut<T, U> for TypedH1MappedRwLockWriteGuard<T>
+where
+    T: 'static + ?Sized,
+    U: 'static + ?Sized,
+{
+    type H1Mapped = TypedH1MappedRwLockWriteGuard<U>;
+
+    fn map_guard_mut<'a, F>(
+        guard: <Self as PlugLifetime<'a>>::T,
+        f: F,
+    ) -> <Self::H1Mapped as PlugLifetime<'a>>::T
+    where
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        MappedRwLockWriteGuard::<T>::map(guard, f)
+    }
+}
+
+impl<T> CondvarLike<T, Mutex<T>> for Condvar
+where
+    T: 'static,
+{
+    fn new() -> Self {
+        Condvar::new()
+    }
+
+    fn wait_with<'a>(
+        &self,
+        mut guard: MutexGuard<'a, T>,
+        _: PoisonPolicy,
+    ) -> Result<MutexGuard<'a, T>, LockError>
+    where
+        MutexGuard<'a, T>: DerefMut<Target = T>,
+    {
+        Condvar::wait(self, &mut guard);
+
+        Ok(guard)
+    }
+
+    fn notify_one(&self) {
+        Condvar::notify_one(self);
+    }
+
+    fn notify_all(&self) {
+        Condvar::notify_all(self);
+    }
+}
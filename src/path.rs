@@ -0,0 +1,18 @@
+//! Cross-platform path manipulation.
+
+use crate::{reference::TypedH1Reference, PlugBorrow};
+use std::path::{Path, PathBuf};
+
+impl PlugBorrow for PathBuf {
+    type H1Borrowed = TypedH1Reference<Path>;
+
+    #[inline]
+    fn borrow(&self) -> &Path {
+        PathBuf::as_path(self)
+    }
+
+    #[inline]
+    fn to_owned(x: &Path) -> Self {
+        Path::to_path_buf(x)
+    }
+}
@@ -29,8 +29,10 @@
 //! also referred to as slots, and `<t>` is the name of the concrete type. For
 //! example, `Cow` has two HKT forms:
 //!
-//! - `H2Cow` which implements `PlugLifetime`, yielding `H1Cow<'a>`
-//! - `H1Cow<'a>` which implements `PlugType`, yielding a concrete `Cow<'a, T>`
+//! - [`H2Cow`](../borrow/struct.H2Cow.html) which implements `PlugLifetime`,
+//!   yielding `H1Cow<'a>`
+//! - [`H1Cow<'a>`](../borrow/struct.H1Cow.html) which implements `PlugType`,
+//!   yielding a concrete `Cow<'a, T>`
 //!
 //! The generic arguments are always filled from left to right, lifetimes
 //! first. In some cases it might be useful to plug those out of order. In
@@ -44,15 +46,29 @@
 //! streaming iterators and similar constructs. [`H0`](struct.H0.html) is a
 //! type wrapper for exactly this case.
 
-use std::marker::PhantomData;
+use crate::{
+    fp::{Applicative, Functor, Monad, Pure, Zip},
+    MaybeOwned,
+};
+use core::marker::PhantomData;
 
 /// Trait enabling a lifetime to plugged to HKT forms.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not an HKT form with a lifetime slot",
+    label = "expected a form such as `TypedH1Reference<T>`",
+    note = "concrete types can be wrapped in `H0` to ignore the lifetime"
+)]
 pub trait PlugLifetime<'a> {
     /// The resulting type after plugging the lifetime parameter `'a`.
     type T;
 }
 
 /// Trait enabling a type to be plugged to HKT forms.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not an HKT form with a type slot for `{T}`",
+    label = "expected a form such as `H1Vec` or `H1Rc`",
+    note = "concrete types can be wrapped in `H0` to ignore the type"
+)]
 pub trait PlugType<T>
 where
     T: ?Sized,
@@ -61,6 +77,23 @@ where
     type T;
 }
 
+/// Trait enabling two types to be plugged to HKT forms at once.
+///
+/// Implemented for all HKT forms with two type slots, such as
+/// [`H2Result`](../result/struct.H2Result.html).
+pub trait PlugType2<A, B> {
+    /// The resulting type after plugging the type parameters `A` and `B`.
+    type T;
+}
+
+impl<K, A, B> PlugType2<A, B> for K
+where
+    K: PlugType<A>,
+    <K as PlugType<A>>::T: PlugType<B>,
+{
+    type T = <<K as PlugType<A>>::T as PlugType<B>>::T;
+}
+
 /// Type-level wrapper that yields `T` unmodified when `PlugLifetime` or
 /// `PlugType` are applied.
 pub struct H0<T>(PhantomData<T>);
@@ -72,3 +105,62 @@ impl<'dummy, T> PlugLifetime<'dummy> for H0<T> {
 impl<Dummy, T> PlugType<Dummy> for H0<T> {
     type T = T;
 }
+
+/// HKT identity with a type slot, yielding the plugged type unmodified.
+pub struct H1Identity;
+
+impl<T> PlugType<T> for H1Identity {
+    type T = T;
+}
+
+impl<A, B> Functor<A, B> for H1Identity {
+    fn fmap<F>(fa: A, mut f: F) -> B
+    where
+        F: FnMut(A) -> B,
+    {
+        f(fa)
+    }
+}
+
+impl<A> Pure<A> for H1Identity {
+    fn pure(x: A) -> A {
+        x
+    }
+}
+
+impl<A, B, C> Applicative<A, B, C> for H1Identity {
+    fn map2<F>(fa: A, fb: B, mut f: F) -> C
+    where
+        F: FnMut(A, B) -> C,
+    {
+        f(fa, fb)
+    }
+}
+
+impl<A, B, C> Zip<A, B, C> for H1Identity {
+    fn zip_with<F>(fa: A, fb: B, mut f: F) -> C
+    where
+        F: FnMut(A, B) -> C,
+    {
+        f(fa, fb)
+    }
+}
+
+impl<A, B> Monad<A, B> for H1Identity {
+    fn bind<F>(fa: A, mut f: F) -> B
+    where
+        F: FnMut(A) -> B,
+    {
+        f(fa)
+    }
+}
+
+impl<T> MaybeOwned<T> for H1Identity {
+    fn get(x: &T) -> &T {
+        x
+    }
+
+    fn into_owned(x: T) -> T {
+        x
+    }
+}
@@ -1,42 +1,273 @@
 //! Single-threaded reference-counting pointers.
 
-use crate::{plug::PlugType, Rcb, WeakRcb};
-use std::rc::{Rc, Weak};
+#[cfg(feature = "sync")]
+use crate::{fp::Transform, sync::H1Arc};
+use crate::{
+    plug::PlugType, ConvertRcb, CowLike, DowncastRcb, NewPtr, PinnedRcb,
+    PointerFamily, Ptr, Rcb, RcbWithWeak, SharedMut, Stringy, WeakRcb,
+};
+#[cfg(feature = "sync")]
+use alloc::sync::Arc;
+use alloc::{
+    rc::{Rc, Weak},
+    string::String,
+};
+#[cfg(feature = "allocator_api")]
+use core::alloc::{AllocError, Allocator};
+use core::{any::Any, cell::RefCell, pin::Pin};
 
 /// HKT `std::rc::Rc<T>` with a type slot.
 pub struct H1Rc;
 
-impl<T> PlugType<T> for H1Rc {
+impl<T> PlugType<T> for H1Rc
+where
+    T: ?Sized,
+{
     type T = Rc<T>;
 }
 
 /// HKT `std::rc::Weak<T>` with a type slot.
 pub struct H1Weak;
 
-impl<T> PlugType<T> for H1Weak {
+impl<T> PlugType<T> for H1Weak
+where
+    T: ?Sized,
+{
     type T = Weak<T>;
 }
 
-impl<T> Rcb<T> for Rc<T> {
+/// Pointer family of `std::rc::Rc<T>` and `std::rc::Weak<T>`.
+pub struct RcFamily;
+
+impl<T> PointerFamily<T> for RcFamily
+where
+    T: ?Sized,
+{
+    type Strong = Rc<T>;
     type Weak = Weak<T>;
+}
+
+impl<T, Q> ConvertRcb<Q> for Rc<T>
+where
+    T: Clone,
+    Q: PointerFamily<T>,
+{
+    type Output = Q::Strong;
+
+    fn convert_rcb(&self) -> Self::Output {
+        Q::Strong::new(T::clone(self))
+    }
+}
+
+impl<T> Ptr<T> for Rc<T> where T: ?Sized {}
 
+impl<T> NewPtr<T> for Rc<T> {
     fn new(x: T) -> Self {
         Rc::<T>::new(x)
     }
+}
 
-    fn try_unwrap(this: Self) -> Result<T, Self> {
+#[cfg(not(feature = "allocator_api"))]
+impl<T> Rcb<T> for Rc<T>
+where
+    T: ?Sized,
+{
+    fn new(x: T) -> Self
+    where
+        T: Sized,
+    {
+        Rc::<T>::new(x)
+    }
+
+    fn try_unwrap(this: Self) -> Result<T, Self>
+    where
+        T: Sized,
+    {
         Rc::<T>::try_unwrap(this)
     }
 
+    fn unwrap_or_clone(this: Self) -> T
+    where
+        T: Clone,
+    {
+        Rc::<T>::unwrap_or_clone(this)
+    }
+
+    fn into_raw(this: Self) -> *const T {
+        Rc::<T>::into_raw(this)
+    }
+
+    unsafe fn from_raw(ptr: *const T) -> Self {
+        Rc::<T>::from_raw(ptr)
+    }
+}
+
+/// Allocators must implement `Default` as `Rcb` constructors don't take an
+/// allocator argument.
+#[cfg(feature = "allocator_api")]
+impl<T, A> Rcb<T> for Rc<T, A>
+where
+    T: ?Sized,
+    A: Allocator + Clone + Default,
+{
+    fn new(x: T) -> Self
+    where
+        T: Sized,
+    {
+        Rc::<T, A>::new_in(x, A::default())
+    }
+
+    fn try_new(x: T) -> Result<Self, AllocError>
+    where
+        T: Sized,
+    {
+        Rc::<T, A>::try_new_in(x, A::default())
+    }
+
+    fn try_unwrap(this: Self) -> Result<T, Self>
+    where
+        T: Sized,
+    {
+        Rc::<T, A>::try_unwrap(this)
+    }
+
+    fn unwrap_or_clone(this: Self) -> T
+    where
+        T: Clone,
+    {
+        Rc::<T, A>::unwrap_or_clone(this)
+    }
+
+    fn into_raw(this: Self) -> *const T {
+        Rc::<T, A>::into_raw_with_allocator(this).0
+    }
+
+    unsafe fn from_raw(ptr: *const T) -> Self {
+        Rc::<T, A>::from_raw_in(ptr, A::default())
+    }
+}
+
+impl<T> RcbWithWeak<T> for Rc<T>
+where
+    T: ?Sized,
+{
+    type Weak = Weak<T>;
+
+    fn new_cyclic<F>(f: F) -> Self
+    where
+        T: Sized,
+        F: FnOnce(&Self::Weak) -> T,
+    {
+        Rc::<T>::new_cyclic(f)
+    }
+
     fn downgrade(this: &Self) -> Self::Weak {
         Rc::<T>::downgrade(this)
     }
 }
 
-impl<T> WeakRcb<T> for Weak<T> {
+impl<T> PinnedRcb<T> for Rc<T> {
+    fn pin(x: T) -> Pin<Self> {
+        Rc::<T>::pin(x)
+    }
+}
+
+impl<U> DowncastRcb<U> for Rc<dyn Any>
+where
+    U: Any,
+{
+    type Downcast = Rc<U>;
+
+    fn downcast(this: Self) -> Result<Self::Downcast, Self> {
+        Rc::<dyn Any>::downcast(this)
+    }
+}
+
+impl<T> SharedMut<T> for Rc<RefCell<T>> {
+    fn new(x: T) -> Self {
+        Rc::new(RefCell::new(x))
+    }
+
+    fn with<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        f(&mut self.borrow_mut())
+    }
+}
+
+impl<T> WeakRcb<T> for Weak<T>
+where
+    T: ?Sized,
+{
     type Strong = Rc<T>;
 
+    fn new() -> Self
+    where
+        T: Sized,
+    {
+        Weak::<T>::new()
+    }
+
     fn upgrade(&self) -> Option<Self::Strong> {
         Weak::<T>::upgrade(self)
     }
+
+    fn strong_count(&self) -> usize {
+        Weak::<T>::strong_count(self)
+    }
+
+    fn weak_count(&self) -> usize {
+        Weak::<T>::weak_count(self)
+    }
+}
+
+impl<T> CowLike<T> for Rc<T>
+where
+    T: Clone,
+{
+    fn from_owned(x: T) -> Self {
+        Rc::<T>::new(x)
+    }
+
+    fn to_mut(&mut self) -> &mut T {
+        Rc::<T>::make_mut(self)
+    }
+
+    fn into_owned(self) -> T {
+        Rc::<T>::unwrap_or_clone(self)
+    }
+}
+
+impl Stringy for Rc<str> {
+    #[inline]
+    fn new(s: &str) -> Self {
+        Rc::from(s)
+    }
+
+    #[inline]
+    fn from_string(s: String) -> Self {
+        Rc::from(s)
+    }
+
+    #[inline]
+    fn as_str(&self) -> &str {
+        self
+    }
+
+    #[inline]
+    fn into_string(self) -> String {
+        String::from(&*self)
+    }
+}
+
+/// Moves the value out of the `Rc` if it is unique, otherwise clones it.
+#[cfg(feature = "sync")]
+impl<T> Transform<H1Arc, T> for H1Rc
+where
+    T: Clone,
+{
+    fn transform(fa: Rc<T>) -> Arc<T> {
+        Arc::new(Rc::unwrap_or_clone(fa))
+    }
 }
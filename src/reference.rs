@@ -1,7 +1,10 @@
 //! HKT forms for references.
 
-use crate::plug::{PlugLifetime, PlugType};
-use std::marker::PhantomData;
+use crate::{
+    plug::{PlugLifetime, PlugType},
+    MaybeOwned, Ptr, ReborrowMut,
+};
+use core::{marker::PhantomData, ops::DerefMut};
 
 /// HKT `&'a T` with a lifetime and a type slot.
 pub struct H2Reference;
@@ -31,3 +34,66 @@ where
 {
     type T = &'a T;
 }
+
+/// HKT `&'a mut T` with a lifetime and a type slot.
+pub struct H2MutReference;
+
+impl<'a> PlugLifetime<'a> for H2MutReference {
+    type T = H1MutReference<'a>;
+}
+
+/// HKT `&'a mut T` with a type slot.
+pub struct H1MutReference<'a>(PhantomData<&'a ()>);
+
+impl<'a, T> PlugType<T> for H1MutReference<'a>
+where
+    T: 'a + ?Sized,
+{
+    type T = &'a mut T;
+}
+
+/// HKT `&'a mut T` with a lifetime slot.
+pub struct TypedH1MutReference<T>(PhantomData<T>)
+where
+    T: ?Sized;
+
+impl<'a, T> PlugLifetime<'a> for TypedH1MutReference<T>
+where
+    T: 'a + ?Sized,
+{
+    type T = &'a mut T;
+}
+
+impl<T> Ptr<T> for &T where T: ?Sized {}
+
+impl<T> ReborrowMut<T> for &mut T
+where
+    T: 'static + ?Sized,
+{
+    type H1Reborrow = TypedH1MutReference<T>;
+
+    fn reborrow_mut<'a>(
+        &'a mut self,
+    ) -> <Self::H1Reborrow as PlugLifetime<'a>>::T
+    where
+        <Self::H1Reborrow as PlugLifetime<'a>>::T: DerefMut<Target = T>,
+    {
+        &mut **self
+    }
+}
+
+impl<'a, T> MaybeOwned<T> for H1Reference<'a>
+where
+    T: 'a,
+{
+    fn get<'b>(x: &'b &'a T) -> &'b T {
+        x
+    }
+
+    fn into_owned(x: &'a T) -> T
+    where
+        T: Clone,
+    {
+        x.clone()
+    }
+}
@@ -0,0 +1,109 @@
+//! Error handling with the `Result` type.
+
+use crate::{
+    fp::{
+        Applicative, Bifunctor, Functor, Monad, Pure, Transform, Traversable,
+        Zip,
+    },
+    option::H1Option,
+    plug::PlugType,
+};
+use core::marker::PhantomData;
+
+/// HKT `Result<T, E>` with two type slots.
+pub struct H2Result;
+
+impl<T> PlugType<T> for H2Result {
+    type T = H1Result<T>;
+}
+
+/// HKT `Result<T, E>` with an error type slot.
+pub struct H1Result<T>(PhantomData<T>);
+
+impl<T, E> PlugType<E> for H1Result<T> {
+    type T = Result<T, E>;
+}
+
+/// HKT `Result<T, E>` with a success type slot.
+pub struct ErrH1Result<E>(PhantomData<E>);
+
+impl<T, E> PlugType<T> for ErrH1Result<E> {
+    type T = Result<T, E>;
+}
+
+impl<A, B, E> Functor<A, B> for ErrH1Result<E> {
+    fn fmap<F>(fa: Result<A, E>, f: F) -> Result<B, E>
+    where
+        F: FnMut(A) -> B,
+    {
+        fa.map(f)
+    }
+}
+
+impl<A, E> Pure<A> for ErrH1Result<E> {
+    fn pure(x: A) -> Result<A, E> {
+        Ok(x)
+    }
+}
+
+impl<A, B, C, E> Applicative<A, B, C> for ErrH1Result<E> {
+    fn map2<F>(fa: Result<A, E>, fb: Result<B, E>, mut f: F) -> Result<C, E>
+    where
+        F: FnMut(A, B) -> C,
+    {
+        Ok(f(fa?, fb?))
+    }
+}
+
+impl<A, B, C, E> Zip<A, B, C> for ErrH1Result<E> {
+    fn zip_with<F>(fa: Result<A, E>, fb: Result<B, E>, mut f: F) -> Result<C, E>
+    where
+        F: FnMut(A, B) -> C,
+    {
+        Ok(f(fa?, fb?))
+    }
+}
+
+impl<A, B, E> Monad<A, B> for ErrH1Result<E> {
+    fn bind<F>(fa: Result<A, E>, f: F) -> Result<B, E>
+    where
+        F: FnMut(A) -> Result<B, E>,
+    {
+        fa.and_then(f)
+    }
+}
+
+impl<M, A, B, E> Traversable<M, A, B> for ErrH1Result<E>
+where
+    M: Pure<Result<B, E>> + Functor<B, Result<B, E>>,
+{
+    fn traverse<F>(
+        ta: Result<A, E>,
+        mut f: F,
+    ) -> <M as PlugType<Result<B, E>>>::T
+    where
+        F: FnMut(A) -> <M as PlugType<B>>::T,
+    {
+        match ta {
+            Ok(a) => M::fmap(f(a), Ok),
+            Err(err) => M::pure(Err(err)),
+        }
+    }
+}
+
+impl<A, B, C, D> Bifunctor<A, B, C, D> for H2Result {
+    fn bimap<F, G>(fab: Result<A, B>, f: F, g: G) -> Result<C, D>
+    where
+        F: FnMut(A) -> C,
+        G: FnMut(B) -> D,
+    {
+        fab.map(f).map_err(g)
+    }
+}
+
+/// Discards the error.
+impl<T, E> Transform<H1Option, T> for ErrH1Result<E> {
+    fn transform(fa: Result<T, E>) -> Option<T> {
+        fa.ok()
+    }
+}
@@ -1,8 +1,8 @@
 //! A dynamically-sized view into a contiguous sequence, [T].
 
 use crate::plug::PlugLifetime;
-use std::marker::PhantomData;
-use std::slice::Iter;
+use core::marker::PhantomData;
+use core::slice::Iter;
 
 /// HTK `&'a [T]` iterator with a lifetime slot
 pub struct TypedH1Iter<T>(PhantomData<T>);
@@ -0,0 +1,37 @@
+//! Trait impls for the [smallvec](https://crates.io/crates/smallvec) crate.
+
+use crate::ByteBuf;
+use ::smallvec::{Array, SmallVec};
+
+impl<A> ByteBuf for SmallVec<A>
+where
+    A: Array<Item = u8>,
+{
+    fn extend_from_slice(&mut self, bytes: &[u8]) {
+        SmallVec::<A>::extend_from_slice(self, bytes)
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        SmallVec::<A>::as_slice(self)
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [u8] {
+        SmallVec::<A>::as_mut_slice(self)
+    }
+
+    fn truncate(&mut self, len: usize) {
+        SmallVec::<A>::truncate(self, len)
+    }
+
+    fn clear(&mut self) {
+        SmallVec::<A>::clear(self)
+    }
+
+    fn split_to(&mut self, at: usize) -> Self {
+        SmallVec::<A>::drain(self, ..at).collect()
+    }
+
+    fn split_off(&mut self, at: usize) -> Self {
+        SmallVec::<A>::drain(self, at..).collect()
+    }
+}
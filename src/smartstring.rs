@@ -0,0 +1,26 @@
+//! Trait impls for the
+//! [smartstring](https://crates.io/crates/smartstring) crate.
+
+use crate::StringBuilder;
+use ::smartstring::{SmartString, SmartStringMode};
+
+impl<Mode> StringBuilder for SmartString<Mode>
+where
+    Mode: SmartStringMode,
+{
+    fn push(&mut self, c: char) {
+        SmartString::<Mode>::push(self, c)
+    }
+
+    fn push_str(&mut self, s: &str) {
+        SmartString::<Mode>::push_str(self, s)
+    }
+
+    fn as_str(&self) -> &str {
+        SmartString::<Mode>::as_str(self)
+    }
+
+    fn clear(&mut self) {
+        SmartString::<Mode>::clear(self)
+    }
+}
@@ -0,0 +1,657 @@
+//! Asynchronous streaming iterators.
+//!
+//! [`StreamingStream`](trait.StreamingStream.html) is the async counterpart of
+//! [`StreamingIterator`](../trait.StreamingIterator.html): `next()` returns a
+//! future resolving to an item that may borrow from the stream itself.
+
+use crate::{
+    plug::{PlugLifetime, H0},
+    SequenceMut, StreamingIterator,
+};
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::{
+    future::{self, Future, Ready},
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Trait for async iterators that can return elements borrowed from
+/// themselves.
+///
+/// The future returned by `next()` resolves to `Option` of the `H1Item`
+/// plugged with the same lifetime. As in
+/// [`AsyncLock`](../lock/trait.AsyncLock.html), this is stated with a helper
+/// trait, [`NextFuture`](trait.NextFuture.html), so that generic callers can
+/// require it for every lifetime:
+///
+/// ```
+/// use generic_std::{
+///     plug::PlugLifetime,
+///     stream::{NextFuture, StreamingStream},
+/// };
+///
+/// async fn count<S>(stream: &mut S) -> usize
+/// where
+///     S: StreamingStream,
+///     for<'a> <S::H1NextFuture as PlugLifetime<'a>>::T:
+///         NextFuture<'a, S::H1Item>,
+/// {
+///     let mut n = 0;
+///     while stream.next().await.is_some() {
+///         n += 1;
+///     }
+///
+///     n
+/// }
+/// ```
+pub trait StreamingStream {
+    /// HKT item with a lifetime slot.
+    type H1Item: for<'a> PlugLifetime<'a>;
+
+    /// HKT future resolving to the next item, with a lifetime slot.
+    type H1NextFuture: for<'a> PlugLifetime<'a>;
+
+    fn next<'a>(&'a mut self) -> <Self::H1NextFuture as PlugLifetime<'a>>::T
+    where
+        <Self::H1NextFuture as PlugLifetime<'a>>::T:
+            NextFuture<'a, Self::H1Item>;
+}
+
+/// Futures resolving to `Option` of the HKT item `H` plugged with `'a`.
+///
+/// Using this trait instead of `Future<Output = Option<H::T>>` allows the
+/// item bounds of [`StreamingStream`](trait.StreamingStream.html) to be
+/// stated in HRTB bounds.
+pub trait NextFuture<'a, H>:
+    Future<Output = Option<<H as PlugLifetime<'a>>::T>>
+where
+    H: PlugLifetime<'a>,
+{
+}
+
+impl<'a, H, F> NextFuture<'a, H> for F
+where
+    H: PlugLifetime<'a>,
+    F: Future<Output = Option<<H as PlugLifetime<'a>>::T>>,
+{
+}
+
+/// HKT `std::future::Ready<Option<H::T>>` with a lifetime slot, for streams
+/// whose items are available immediately.
+pub struct ItemH1Ready<H>(PhantomData<H>);
+
+impl<'a, H> PlugLifetime<'a> for ItemH1Ready<H>
+where
+    H: PlugLifetime<'a>,
+{
+    type T = Ready<Option<H::T>>;
+}
+
+/// HKT `Pin<Box<dyn Future<Output = Option<H::T>> + 'a>>` with a lifetime
+/// slot, for streams with boxed `next()` futures. See
+/// [`StreamingStreamExt::boxed`](trait.StreamingStreamExt.html#method.boxed).
+#[cfg(feature = "alloc")]
+pub struct ItemH1BoxFuture<H>(PhantomData<H>);
+
+#[cfg(feature = "alloc")]
+impl<'a, H> PlugLifetime<'a> for ItemH1BoxFuture<H>
+where
+    H: PlugLifetime<'a>,
+    H::T: 'a,
+{
+    type T = Pin<Box<dyn Future<Output = Option<H::T>> + 'a>>;
+}
+
+/// Stream yielding the items of a streaming iterator. See
+/// [`iter`](fn.iter.html).
+pub struct Iter<I>(I);
+
+/// Convert a streaming iterator into a stream whose items are always ready.
+pub fn iter<I>(i: I) -> Iter<I>
+where
+    I: StreamingIterator,
+{
+    Iter(i)
+}
+
+impl<I> StreamingStream for Iter<I>
+where
+    I: StreamingIterator,
+{
+    type H1Item = I::H1Item;
+    type H1NextFuture = ItemH1Ready<I::H1Item>;
+
+    fn next<'a>(&'a mut self) -> <Self::H1NextFuture as PlugLifetime<'a>>::T
+    where
+        <Self::H1NextFuture as PlugLifetime<'a>>::T:
+            NextFuture<'a, Self::H1Item>,
+    {
+        future::ready(self.0.next())
+    }
+}
+
+/// Trait for collections and channels that accept elements asynchronously,
+/// such as the sending half of a bounded channel. This is the async
+/// counterpart of [`SequenceMut::push`](../trait.SequenceMut.html).
+pub trait AsyncSequenceMut<T> {
+    /// HKT future resolving once the element is accepted, with a lifetime
+    /// slot.
+    type H1PushFuture: for<'a> PlugLifetime<'a>;
+
+    /// Push `x`. The future resolves to `Err(x)` if the element can never be
+    /// accepted, such as when the receiving half of a channel was dropped.
+    fn push<'a>(
+        &'a mut self,
+        x: T,
+    ) -> <Self::H1PushFuture as PlugLifetime<'a>>::T
+    where
+        <Self::H1PushFuture as PlugLifetime<'a>>::T:
+            Future<Output = Result<(), T>>;
+}
+
+/// Push every item of `stream` into `sink`, in order. Stops at the first
+/// element that can't be pushed and returns it.
+pub async fn forward<S, C, T>(mut stream: S, sink: &mut C) -> Result<(), T>
+where
+    S: StreamingStream<H1Item = H0<T>>,
+    for<'a> <S::H1NextFuture as PlugLifetime<'a>>::T: NextFuture<'a, H0<T>>,
+    C: AsyncSequenceMut<T>,
+    for<'a> <C::H1PushFuture as PlugLifetime<'a>>::T:
+        Future<Output = Result<(), T>>,
+{
+    while let Some(x) = stream.next().await {
+        sink.push(x).await?;
+    }
+
+    Ok(())
+}
+
+/// Poll-based variant of [`StreamingStream`](trait.StreamingStream.html), for
+/// integrating with executors and runtimes at the poll level.
+///
+/// Any `Unpin` implementation can be used as a `StreamingStream` with
+/// [`from_poll`](fn.from_poll.html). The opposite direction would require the
+/// stream to store a future borrowing from itself, so it is only provided for
+/// streams whose items are always ready, such as [`Iter`](struct.Iter.html).
+pub trait PollStreamingIterator {
+    /// HKT item with a lifetime slot.
+    type H1Item: for<'a> PlugLifetime<'a>;
+
+    /// Attempt to get the next item. Returns `Poll::Pending` and arranges for
+    /// the task in `cx` to be woken if no item is ready yet.
+    fn poll_next<'a>(
+        self: Pin<&'a mut Self>,
+        cx: &mut Context,
+    ) -> Poll<Option<<Self::H1Item as PlugLifetime<'a>>::T>>;
+}
+
+impl<I> PollStreamingIterator for Iter<I>
+where
+    I: StreamingIterator + Unpin,
+{
+    type H1Item = I::H1Item;
+
+    fn poll_next<'a>(
+        self: Pin<&'a mut Self>,
+        _: &mut Context,
+    ) -> Poll<Option<<Self::H1Item as PlugLifetime<'a>>::T>> {
+        Poll::Ready(self.get_mut().0.next())
+    }
+}
+
+/// Streaming stream driven by a poll-based streaming iterator. See
+/// [`from_poll`](fn.from_poll.html).
+pub struct FromPoll<P>(P);
+
+/// Convert a poll-based streaming iterator into a streaming stream.
+pub fn from_poll<P>(p: P) -> FromPoll<P>
+where
+    P: PollStreamingIterator + Unpin,
+{
+    FromPoll(p)
+}
+
+impl<P> FromPoll<P> {
+    /// Unwrap the poll-based streaming iterator.
+    pub fn into_inner(self) -> P {
+        self.0
+    }
+}
+
+impl<P> StreamingStream for FromPoll<P>
+where
+    P: PollStreamingIterator + Unpin + 'static,
+{
+    type H1Item = P::H1Item;
+    type H1NextFuture = TypedH1PollNext<P>;
+
+    fn next<'a>(&'a mut self) -> <Self::H1NextFuture as PlugLifetime<'a>>::T
+    where
+        <Self::H1NextFuture as PlugLifetime<'a>>::T:
+            NextFuture<'a, Self::H1Item>,
+    {
+        PollNext {
+            stream: &mut self.0,
+            done: false,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// Future resolving to the next item of a poll-based streaming iterator.
+///
+/// # Panics
+///
+/// Panics if polled after completion.
+pub struct PollNext<'a, P> {
+    // A raw pointer since every poll needs the full `'a` borrow for the item
+    // to live as long as `'a`
+    stream: *mut P,
+    done: bool,
+    _phantom: PhantomData<&'a mut P>,
+}
+
+// SAFETY: the pointer is only used as the `&'a mut P` it was created from,
+// which is `Send` if `P` is
+unsafe impl<'a, P> Send for PollNext<'a, P> where P: Send {}
+
+impl<'a, P> Future for PollNext<'a, P>
+where
+    P: PollStreamingIterator + Unpin + 'a,
+{
+    type Output = Option<<P::H1Item as PlugLifetime<'a>>::T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        assert!(!self.done, "`PollNext` polled after completion");
+        // SAFETY: the pointer comes from a `&'a mut P`. Borrows from pending
+        // polls end immediately and no new borrows are created after an item
+        // is returned
+        let stream = unsafe { &mut *self.stream };
+        let poll = Pin::new(stream).poll_next(cx);
+        self.done = poll.is_ready();
+
+        poll
+    }
+}
+
+/// HKT `PollNext<'a, P>` with a lifetime slot.
+pub struct TypedH1PollNext<P>(PhantomData<P>);
+
+impl<'a, P> PlugLifetime<'a> for TypedH1PollNext<P>
+where
+    P: 'a,
+{
+    type T = PollNext<'a, P>;
+}
+
+/// Adapters for [`StreamingStream`](trait.StreamingStream.html).
+///
+/// The adapters poll the futures returned by the underlying stream in place,
+/// so those futures must be `Unpin`.
+pub trait StreamingStreamExt: StreamingStream {
+    /// Map each item to an owned value with `f`.
+    fn map<B, F>(self, f: F) -> Map<Self, F>
+    where
+        Self: Sized,
+        F: for<'a> FnMut(<Self::H1Item as PlugLifetime<'a>>::T) -> B,
+    {
+        Map { stream: self, f }
+    }
+
+    /// Skip items for which `predicate` returns `false`.
+    fn filter<P>(self, predicate: P) -> Filter<Self, P>
+    where
+        Self: Sized,
+        P: for<'a, 'b> FnMut(&'b <Self::H1Item as PlugLifetime<'a>>::T) -> bool,
+    {
+        Filter {
+            stream: self,
+            predicate,
+        }
+    }
+
+    /// Yield at most `n` items.
+    fn take(self, n: usize) -> Take<Self>
+    where
+        Self: Sized,
+    {
+        Take {
+            stream: self,
+            remaining: n,
+        }
+    }
+
+    /// Box the futures returned by `next()`.
+    ///
+    /// Boxed streams with the same item form have the same future form, so
+    /// they can be used as trait objects:
+    ///
+    /// ```
+    /// use generic_std::{
+    ///     plug::H0,
+    ///     stream::{self, ItemH1BoxFuture, StreamingStream, StreamingStreamExt},
+    /// };
+    ///
+    /// type DynStream =
+    ///     dyn StreamingStream<H1Item = H0<u32>, H1NextFuture = ItemH1BoxFuture<H0<u32>>>;
+    ///
+    /// let streams: Vec<Box<DynStream>> = vec![
+    ///     Box::new(stream::iter(vec![1, 2].into_iter()).boxed()),
+    ///     Box::new(stream::iter(vec![3].into_iter()).map(|x| x * 2).boxed()),
+    /// ];
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn boxed(self) -> Boxed<Self>
+    where
+        Self: Sized,
+    {
+        Boxed(self)
+    }
+}
+
+impl<S> StreamingStreamExt for S where S: StreamingStream {}
+
+/// Push every item of `stream` into a new collection, in order.
+pub async fn collect<S, C, T>(mut stream: S) -> C
+where
+    S: StreamingStream<H1Item = H0<T>>,
+    for<'a> <S::H1NextFuture as PlugLifetime<'a>>::T: NextFuture<'a, H0<T>>,
+    C: SequenceMut<T> + Default,
+{
+    let mut collection = C::default();
+    while let Some(x) = stream.next().await {
+        collection.push(x);
+    }
+
+    collection
+}
+
+/// Stream mapping the items of another. See
+/// [`StreamingStreamExt::map`](trait.StreamingStreamExt.html#method.map).
+pub struct Map<S, F> {
+    stream: S,
+    f: F,
+}
+
+impl<S, F, B> StreamingStream for Map<S, F>
+where
+    S: StreamingStream + 'static,
+    for<'a> <S::H1NextFuture as PlugLifetime<'a>>::T:
+        NextFuture<'a, S::H1Item> + Unpin,
+    F: for<'a> FnMut(<S::H1Item as PlugLifetime<'a>>::T) -> B + 'static,
+{
+    type H1Item = H0<B>;
+    type H1NextFuture = TypedH1MapFuture<S, F>;
+
+    fn next<'a>(&'a mut self) -> <Self::H1NextFuture as PlugLifetime<'a>>::T
+    where
+        <Self::H1NextFuture as PlugLifetime<'a>>::T:
+            NextFuture<'a, Self::H1Item>,
+    {
+        MapFuture {
+            future: self.stream.next(),
+            f: &mut self.f,
+        }
+    }
+}
+
+/// Future resolving to the next item of a [`Map`](struct.Map.html).
+pub struct MapFuture<'a, S, F>
+where
+    S: StreamingStream,
+    S::H1NextFuture: PlugLifetime<'a>,
+{
+    future: <S::H1NextFuture as PlugLifetime<'a>>::T,
+    f: &'a mut F,
+}
+
+impl<'a, S, F, B> Future for MapFuture<'a, S, F>
+where
+    S: StreamingStream,
+    <S::H1NextFuture as PlugLifetime<'a>>::T: NextFuture<'a, S::H1Item> + Unpin,
+    F: FnMut(<S::H1Item as PlugLifetime<'a>>::T) -> B,
+{
+    type Output = Option<B>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = &mut *self;
+        match Pin::new(&mut this.future).poll(cx) {
+            Poll::Ready(x) => Poll::Ready(x.map(&mut *this.f)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// HKT `MapFuture<'a, S, F>` with a lifetime slot.
+pub struct TypedH1MapFuture<S, F>(PhantomData<(S, F)>);
+
+impl<'a, S, F> PlugLifetime<'a> for TypedH1MapFuture<S, F>
+where
+    S: StreamingStream + 'a,
+    F: 'a,
+{
+    type T = MapFuture<'a, S, F>;
+}
+
+/// Stream skipping items of another. See
+/// [`StreamingStreamExt::filter`](trait.StreamingStreamExt.html#method.filter).
+pub struct Filter<S, P> {
+    stream: S,
+    predicate: P,
+}
+
+impl<S, P> StreamingStream for Filter<S, P>
+where
+    S: StreamingStream + 'static,
+    for<'a> <S::H1NextFuture as PlugLifetime<'a>>::T:
+        NextFuture<'a, S::H1Item> + Unpin,
+    P: for<'a, 'b> FnMut(&'b <S::H1Item as PlugLifetime<'a>>::T) -> bool
+        + 'static,
+{
+    type H1Item = S::H1Item;
+    type H1NextFuture = TypedH1FilterFuture<S, P>;
+
+    fn next<'a>(&'a mut self) -> <Self::H1NextFuture as PlugLifetime<'a>>::T
+    where
+        <Self::H1NextFuture as PlugLifetime<'a>>::T:
+            NextFuture<'a, Self::H1Item>,
+    {
+        FilterFuture {
+            stream: &mut self.stream,
+            predicate: &mut self.predicate,
+            future: None,
+            done: false,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// Future resolving to the next item of a [`Filter`](struct.Filter.html).
+///
+/// # Panics
+///
+/// Panics if polled after completion.
+pub struct FilterFuture<'a, S, P>
+where
+    S: StreamingStream,
+    S::H1NextFuture: PlugLifetime<'a>,
+{
+    // A raw pointer so that a new future can be started after a rejected item
+    // without holding two mutable borrows. Only one future borrowing the
+    // stream exists at a time
+    stream: *mut S,
+    predicate: &'a mut P,
+    future: Option<<S::H1NextFuture as PlugLifetime<'a>>::T>,
+    done: bool,
+    _phantom: PhantomData<&'a mut S>,
+}
+
+// SAFETY: the pointer is only used as the `&'a mut S` it was created from,
+// which is `Send` if `S` is. The other fields are required to be `Send` as
+// well
+unsafe impl<'a, S, P> Send for FilterFuture<'a, S, P>
+where
+    S: StreamingStream + Send,
+    S::H1NextFuture: PlugLifetime<'a>,
+    <S::H1NextFuture as PlugLifetime<'a>>::T: Send,
+    P: Send,
+{
+}
+
+impl<'a, S, P> Future for FilterFuture<'a, S, P>
+where
+    S: StreamingStream + 'a,
+    <S::H1NextFuture as PlugLifetime<'a>>::T: NextFuture<'a, S::H1Item> + Unpin,
+    P: for<'b> FnMut(&'b <S::H1Item as PlugLifetime<'a>>::T) -> bool,
+{
+    type Output = Option<<S::H1Item as PlugLifetime<'a>>::T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = &mut *self;
+        assert!(!this.done, "`FilterFuture` polled after completion");
+        loop {
+            let future = match &mut this.future {
+                Some(future) => future,
+                None => {
+                    // SAFETY: the pointer comes from a `&'a mut S` and the
+                    // previous future and its item, if any, were dropped, so
+                    // this is the only borrow of the stream. No new borrows
+                    // are created after an item is returned
+                    let stream = unsafe { &mut *this.stream };
+                    this.future.get_or_insert(stream.next())
+                }
+            };
+            match Pin::new(future).poll(cx) {
+                Poll::Ready(Some(x)) if !(this.predicate)(&x) => {
+                    drop(x);
+                    this.future = None;
+                }
+                Poll::Ready(x) => {
+                    this.future = None;
+                    this.done = true;
+
+                    return Poll::Ready(x);
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// HKT `FilterFuture<'a, S, P>` with a lifetime slot.
+pub struct TypedH1FilterFuture<S, P>(PhantomData<(S, P)>);
+
+impl<'a, S, P> PlugLifetime<'a> for TypedH1FilterFuture<S, P>
+where
+    S: StreamingStream + 'a,
+    P: 'a,
+{
+    type T = FilterFuture<'a, S, P>;
+}
+
+/// Stream yielding a limited number of items of another. See
+/// [`StreamingStreamExt::take`](trait.StreamingStreamExt.html#method.take).
+pub struct Take<S> {
+    stream: S,
+    remaining: usize,
+}
+
+impl<S> StreamingStream for Take<S>
+where
+    S: StreamingStream + 'static,
+    for<'a> <S::H1NextFuture as PlugLifetime<'a>>::T:
+        NextFuture<'a, S::H1Item> + Unpin,
+{
+    type H1Item = S::H1Item;
+    type H1NextFuture = TypedH1TakeFuture<S>;
+
+    fn next<'a>(&'a mut self) -> <Self::H1NextFuture as PlugLifetime<'a>>::T
+    where
+        <Self::H1NextFuture as PlugLifetime<'a>>::T:
+            NextFuture<'a, Self::H1Item>,
+    {
+        let future = if self.remaining == 0 {
+            None
+        } else {
+            Some(self.stream.next())
+        };
+
+        TakeFuture {
+            future,
+            remaining: &mut self.remaining,
+        }
+    }
+}
+
+/// Future resolving to the next item of a [`Take`](struct.Take.html).
+///
+/// The item budget is only spent once an item is yielded, so dropping a
+/// pending future doesn't lose an item.
+pub struct TakeFuture<'a, S>
+where
+    S: StreamingStream,
+    S::H1NextFuture: PlugLifetime<'a>,
+{
+    future: Option<<S::H1NextFuture as PlugLifetime<'a>>::T>,
+    remaining: &'a mut usize,
+}
+
+impl<'a, S> Future for TakeFuture<'a, S>
+where
+    S: StreamingStream,
+    <S::H1NextFuture as PlugLifetime<'a>>::T: NextFuture<'a, S::H1Item> + Unpin,
+{
+    type Output = Option<<S::H1Item as PlugLifetime<'a>>::T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let future = match &mut this.future {
+            Some(future) => future,
+            None => return Poll::Ready(None),
+        };
+        match Pin::new(future).poll(cx) {
+            Poll::Ready(Some(x)) => {
+                this.future = None;
+                *this.remaining -= 1;
+
+                Poll::Ready(Some(x))
+            }
+            poll => poll,
+        }
+    }
+}
+
+/// HKT `TakeFuture<'a, S>` with a lifetime slot.
+pub struct TypedH1TakeFuture<S>(PhantomData<S>);
+
+impl<'a, S> PlugLifetime<'a> for TypedH1TakeFuture<S>
+where
+    S: StreamingStream + 'a,
+{
+    type T = TakeFuture<'a, S>;
+}
+
+/// Stream boxing the futures of another. See
+/// [`StreamingStreamExt::boxed`](trait.StreamingStreamExt.html#method.boxed).
+#[cfg(feature = "alloc")]
+pub struct Boxed<S>(S);
+
+#[cfg(feature = "alloc")]
+impl<S> StreamingStream for Boxed<S>
+where
+    S: StreamingStream + 'static,
+    for<'a> <S::H1NextFuture as PlugLifetime<'a>>::T: NextFuture<'a, S::H1Item>,
+{
+    type H1Item = S::H1Item;
+    type H1NextFuture = ItemH1BoxFuture<S::H1Item>;
+
+    fn next<'a>(&'a mut self) -> <Self::H1NextFuture as PlugLifetime<'a>>::T
+    where
+        <Self::H1NextFuture as PlugLifetime<'a>>::T:
+            NextFuture<'a, Self::H1Item>,
+    {
+        Box::pin(self.0.next())
+    }
+}
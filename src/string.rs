@@ -0,0 +1,79 @@
+//! A UTF-8 encoded, growable string.
+
+use crate::{
+    reference::TypedH1Reference, Capacity, PlugBorrow, StringBuilder, Stringy,
+    WithCapacity,
+};
+use alloc::{borrow::ToOwned, string::String};
+
+impl Stringy for String {
+    #[inline]
+    fn new(s: &str) -> Self {
+        String::from(s)
+    }
+
+    #[inline]
+    fn from_string(s: String) -> Self {
+        s
+    }
+
+    #[inline]
+    fn as_str(&self) -> &str {
+        String::as_str(self)
+    }
+
+    #[inline]
+    fn into_string(self) -> String {
+        self
+    }
+}
+
+impl StringBuilder for String {
+    #[inline]
+    fn push(&mut self, c: char) {
+        String::push(self, c)
+    }
+
+    #[inline]
+    fn push_str(&mut self, s: &str) {
+        String::push_str(self, s)
+    }
+
+    #[inline]
+    fn as_str(&self) -> &str {
+        String::as_str(self)
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        String::clear(self)
+    }
+}
+
+impl PlugBorrow for String {
+    type H1Borrowed = TypedH1Reference<str>;
+
+    #[inline]
+    fn borrow(&self) -> &str {
+        String::as_str(self)
+    }
+
+    #[inline]
+    fn to_owned(x: &str) -> Self {
+        <str as ToOwned>::to_owned(x)
+    }
+}
+
+impl WithCapacity for String {
+    #[inline]
+    fn with_capacity(capacity: usize) -> Self {
+        String::with_capacity(capacity)
+    }
+}
+
+impl Capacity for String {
+    #[inline]
+    fn capacity(&self) -> usize {
+        String::capacity(self)
+    }
+}
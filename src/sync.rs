@@ -1,42 +1,767 @@
 //! Useful synchronization primitives.
 
-use crate::{plug::PlugType, Rcb, WeakRcb};
-use std::sync::{Arc, Weak};
+#[cfg(feature = "std")]
+use crate::{
+    channel::{self, RecvError, TryRecvError},
+    lock::{
+        CellLike, CondvarLike, LazyLike, Lock, LockError, OnceLike,
+        PoisonPolicy, RwLockLike, ScopedLock, UpgradableRwLock,
+    },
+    plug::PlugLifetime,
+    reference::TypedH1MutReference,
+    ReborrowMut, SharedMut, StreamingIterator,
+};
+#[cfg(feature = "rc")]
+use crate::{fp::Transform, rc::H1Rc};
+#[cfg(feature = "alloc")]
+use crate::{
+    plug::PlugType, ConvertRcb, CowLike, DowncastRcb, NewPtr, PinnedRcb,
+    PointerFamily, Ptr, Rcb, RcbWithWeak, Stringy, WeakRcb,
+};
+#[cfg(feature = "rc")]
+use alloc::rc::Rc;
+#[cfg(feature = "alloc")]
+use alloc::{
+    string::String,
+    sync::{Arc, Weak},
+};
+#[cfg(feature = "allocator_api")]
+use core::alloc::{AllocError, Allocator};
+#[cfg(feature = "alloc")]
+use core::{any::Any, pin::Pin};
+#[cfg(feature = "std")]
+use std::{
+    marker::PhantomData,
+    mem,
+    ops::{Deref, DerefMut},
+    sync::{
+        mpsc, Condvar, LazyLock, Mutex, MutexGuard, OnceLock, RwLock,
+        RwLockReadGuard, RwLockWriteGuard,
+    },
+};
 
 /// HKT `std::sync::Arc<T>` with a type slot.
+#[cfg(feature = "alloc")]
 pub struct H1Arc;
 
-impl<T> PlugType<T> for H1Arc {
+#[cfg(feature = "alloc")]
+impl<T> PlugType<T> for H1Arc
+where
+    T: ?Sized,
+{
     type T = Arc<T>;
 }
 
 /// HKT `std::sync::Weak<T>` with a type slot.
+#[cfg(feature = "alloc")]
 pub struct H1Weak;
 
-impl<T> PlugType<T> for H1Weak {
+#[cfg(feature = "alloc")]
+impl<T> PlugType<T> for H1Weak
+where
+    T: ?Sized,
+{
     type T = Weak<T>;
 }
 
-impl<T> Rcb<T> for Arc<T> {
+/// HKT `std::sync::Mutex<T>` with a type slot.
+#[cfg(feature = "std")]
+pub struct H1Mutex;
+
+#[cfg(feature = "std")]
+impl<T> PlugType<T> for H1Mutex {
+    type T = Mutex<T>;
+}
+
+/// HKT `std::sync::RwLock<T>` with a type slot.
+#[cfg(feature = "std")]
+pub struct H1RwLock;
+
+#[cfg(feature = "std")]
+impl<T> PlugType<T> for H1RwLock {
+    type T = RwLock<T>;
+}
+
+/// HKT `std::sync::MutexGuard<'a, T>` with a lifetime and a type slot.
+#[cfg(feature = "std")]
+pub struct H2MutexGuard;
+
+#[cfg(feature = "std")]
+impl<'a> PlugLifetime<'a> for H2MutexGuard {
+    type T = H1MutexGuard<'a>;
+}
+
+/// HKT `std::sync::MutexGuard<'a, T>` with a type slot.
+#[cfg(feature = "std")]
+pub struct H1MutexGuard<'a>(PhantomData<&'a ()>);
+
+#[cfg(feature = "std")]
+impl<'a, T> PlugType<T> for H1MutexGuard<'a>
+where
+    T: 'a + ?Sized,
+{
+    type T = MutexGuard<'a, T>;
+}
+
+/// HKT `std::sync::MutexGuard<'a, T>` with a lifetime slot.
+#[cfg(feature = "std")]
+pub struct TypedH1MutexGuard<T>(PhantomData<T>)
+where
+    T: ?Sized;
+
+#[cfg(feature = "std")]
+impl<'a, T> PlugLifetime<'a> for TypedH1MutexGuard<T>
+where
+    T: 'a + ?Sized,
+{
+    type T = MutexGuard<'a, T>;
+}
+
+/// HKT `std::sync::RwLockReadGuard<'a, T>` with a lifetime and a type slot.
+#[cfg(feature = "std")]
+pub struct H2RwLockReadGuard;
+
+#[cfg(feature = "std")]
+impl<'a> PlugLifetime<'a> for H2RwLockReadGuard {
+    type T = H1RwLockReadGuard<'a>;
+}
+
+/// HKT `std::sync::RwLockReadGuard<'a, T>` with a type slot.
+#[cfg(feature = "std")]
+pub struct H1RwLockReadGuard<'a>(PhantomData<&'a ()>);
+
+#[cfg(feature = "std")]
+impl<'a, T> PlugType<T> for H1RwLockReadGuard<'a>
+where
+    T: 'a + ?Sized,
+{
+    type T = RwLockReadGuard<'a, T>;
+}
+
+/// HKT `std::sync::RwLockReadGuard<'a, T>` with a lifetime slot.
+#[cfg(feature = "std")]
+pub struct TypedH1RwLockReadGuard<T>(PhantomData<T>)
+where
+    T: ?Sized;
+
+#[cfg(feature = "std")]
+impl<'a, T> PlugLifetime<'a> for TypedH1RwLockReadGuard<T>
+where
+    T: 'a + ?Sized,
+{
+    type T = RwLockReadGuard<'a, T>;
+}
+
+/// HKT `std::sync::RwLockWriteGuard<'a, T>` with a lifetime and a type slot.
+#[cfg(feature = "std")]
+pub struct H2RwLockWriteGuard;
+
+#[cfg(feature = "std")]
+impl<'a> PlugLifetime<'a> for H2RwLockWriteGuard {
+    type T = H1RwLockWriteGuard<'a>;
+}
+
+/// HKT `std::sync::RwLockWriteGuard<'a, T>` with a type slot.
+#[cfg(feature = "std")]
+pub struct H1RwLockWriteGuard<'a>(PhantomData<&'a ()>);
+
+#[cfg(feature = "std")]
+impl<'a, T> PlugType<T> for H1RwLockWriteGuard<'a>
+where
+    T: 'a + ?Sized,
+{
+    type T = RwLockWriteGuard<'a, T>;
+}
+
+/// HKT `std::sync::RwLockWriteGuard<'a, T>` with a lifetime slot.
+#[cfg(feature = "std")]
+pub struct TypedH1RwLockWriteGuard<T>(PhantomData<T>)
+where
+    T: ?Sized;
+
+#[cfg(feature = "std")]
+impl<'a, T> PlugLifetime<'a> for TypedH1RwLockWriteGuard<T>
+where
+    T: 'a + ?Sized,
+{
+    type T = RwLockWriteGuard<'a, T>;
+}
+
+/// HKT `std::sync::mpsc::Iter<'a, T>` with a lifetime slot.
+#[cfg(feature = "std")]
+pub struct TypedH1ReceiverIter<T>(PhantomData<T>);
+
+#[cfg(feature = "std")]
+impl<'a, T> PlugLifetime<'a> for TypedH1ReceiverIter<T>
+where
+    T: 'a,
+{
+    type T = mpsc::Iter<'a, T>;
+}
+
+/// Pointer family of `std::sync::Arc<T>` and `std::sync::Weak<T>`.
+#[cfg(feature = "alloc")]
+pub struct ArcFamily;
+
+#[cfg(feature = "alloc")]
+impl<T> PointerFamily<T> for ArcFamily
+where
+    T: ?Sized,
+{
+    type Strong = Arc<T>;
     type Weak = Weak<T>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T, Q> ConvertRcb<Q> for Arc<T>
+where
+    T: Clone,
+    Q: PointerFamily<T>,
+{
+    type Output = Q::Strong;
 
+    fn convert_rcb(&self) -> Self::Output {
+        Q::Strong::new(T::clone(self))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Ptr<T> for Arc<T> where T: ?Sized {}
+
+#[cfg(feature = "alloc")]
+impl<T> NewPtr<T> for Arc<T> {
     fn new(x: T) -> Self {
         Arc::<T>::new(x)
     }
+}
 
-    fn try_unwrap(this: Self) -> Result<T, Self> {
+#[cfg(all(feature = "alloc", not(feature = "allocator_api")))]
+impl<T> Rcb<T> for Arc<T>
+where
+    T: ?Sized,
+{
+    fn new(x: T) -> Self
+    where
+        T: Sized,
+    {
+        Arc::<T>::new(x)
+    }
+
+    fn try_unwrap(this: Self) -> Result<T, Self>
+    where
+        T: Sized,
+    {
         Arc::<T>::try_unwrap(this)
     }
 
+    fn unwrap_or_clone(this: Self) -> T
+    where
+        T: Clone,
+    {
+        Arc::<T>::unwrap_or_clone(this)
+    }
+
+    fn into_raw(this: Self) -> *const T {
+        Arc::<T>::into_raw(this)
+    }
+
+    unsafe fn from_raw(ptr: *const T) -> Self {
+        Arc::<T>::from_raw(ptr)
+    }
+}
+
+/// Allocators must implement `Default` as `Rcb` constructors don't take an
+/// allocator argument.
+#[cfg(feature = "allocator_api")]
+impl<T, A> Rcb<T> for Arc<T, A>
+where
+    T: ?Sized,
+    A: Allocator + Clone + Default,
+{
+    fn new(x: T) -> Self
+    where
+        T: Sized,
+    {
+        Arc::<T, A>::new_in(x, A::default())
+    }
+
+    fn try_new(x: T) -> Result<Self, AllocError>
+    where
+        T: Sized,
+    {
+        Arc::<T, A>::try_new_in(x, A::default())
+    }
+
+    fn try_unwrap(this: Self) -> Result<T, Self>
+    where
+        T: Sized,
+    {
+        Arc::<T, A>::try_unwrap(this)
+    }
+
+    fn unwrap_or_clone(this: Self) -> T
+    where
+        T: Clone,
+    {
+        Arc::<T, A>::unwrap_or_clone(this)
+    }
+
+    fn into_raw(this: Self) -> *const T {
+        Arc::<T, A>::into_raw_with_allocator(this).0
+    }
+
+    unsafe fn from_raw(ptr: *const T) -> Self {
+        Arc::<T, A>::from_raw_in(ptr, A::default())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> RcbWithWeak<T> for Arc<T>
+where
+    T: ?Sized,
+{
+    type Weak = Weak<T>;
+
+    fn new_cyclic<F>(f: F) -> Self
+    where
+        T: Sized,
+        F: FnOnce(&Self::Weak) -> T,
+    {
+        Arc::<T>::new_cyclic(f)
+    }
+
     fn downgrade(this: &Self) -> Self::Weak {
         Arc::<T>::downgrade(this)
     }
 }
 
-impl<T> WeakRcb<T> for Weak<T> {
+#[cfg(feature = "alloc")]
+impl<T> PinnedRcb<T> for Arc<T> {
+    fn pin(x: T) -> Pin<Self> {
+        Arc::<T>::pin(x)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<U> DowncastRcb<U> for Arc<dyn Any + Send + Sync>
+where
+    U: Any + Send + Sync,
+{
+    type Downcast = Arc<U>;
+
+    fn downcast(this: Self) -> Result<Self::Downcast, Self> {
+        Arc::<dyn Any + Send + Sync>::downcast(this)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> SharedMut<T> for Arc<Mutex<T>> {
+    fn new(x: T) -> Self {
+        Arc::new(Mutex::new(x))
+    }
+
+    fn with<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        f(&mut self.lock().unwrap())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> SharedMut<T> for Arc<RwLock<T>> {
+    fn new(x: T) -> Self {
+        Arc::new(RwLock::new(x))
+    }
+
+    fn with<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        f(&mut self.write().unwrap())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> WeakRcb<T> for Weak<T>
+where
+    T: ?Sized,
+{
     type Strong = Arc<T>;
 
+    fn new() -> Self
+    where
+        T: Sized,
+    {
+        Weak::<T>::new()
+    }
+
     fn upgrade(&self) -> Option<Self::Strong> {
         Weak::<T>::upgrade(self)
     }
+
+    fn strong_count(&self) -> usize {
+        Weak::<T>::strong_count(self)
+    }
+
+    fn weak_count(&self) -> usize {
+        Weak::<T>::weak_count(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> CowLike<T> for Arc<T>
+where
+    T: Clone,
+{
+    fn from_owned(x: T) -> Self {
+        Arc::<T>::new(x)
+    }
+
+    fn to_mut(&mut self) -> &mut T {
+        Arc::<T>::make_mut(self)
+    }
+
+    fn into_owned(self) -> T {
+        Arc::<T>::unwrap_or_clone(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Stringy for Arc<str> {
+    #[inline]
+    fn new(s: &str) -> Self {
+        Arc::from(s)
+    }
+
+    #[inline]
+    fn from_string(s: String) -> Self {
+        Arc::from(s)
+    }
+
+    #[inline]
+    fn as_str(&self) -> &str {
+        self
+    }
+
+    #[inline]
+    fn into_string(self) -> String {
+        String::from(&*self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> Lock<T> for Mutex<T>
+where
+    T: 'static,
+{
+    type H1Guard = TypedH1MutexGuard<T>;
+
+    fn new(x: T) -> Self {
+        Mutex::<T>::new(x)
+    }
+
+    fn lock_with<'a>(
+        &'a self,
+        policy: PoisonPolicy,
+    ) -> Result<<Self::H1Guard as PlugLifetime<'a>>::T, LockError>
+    where
+        <Self::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = T>,
+    {
+        policy.recover(Mutex::<T>::lock(self))
+    }
+
+    fn try_lock<'a>(&'a self) -> Option<<Self::H1Guard as PlugLifetime<'a>>::T>
+    where
+        <Self::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = T>,
+    {
+        Mutex::<T>::try_lock(self).ok()
+    }
+}
+
+/// Locking grants write access.
+#[cfg(feature = "std")]
+impl<T> Lock<T> for RwLock<T>
+where
+    T: 'static,
+{
+    type H1Guard = TypedH1RwLockWriteGuard<T>;
+
+    fn new(x: T) -> Self {
+        RwLock::<T>::new(x)
+    }
+
+    fn lock_with<'a>(
+        &'a self,
+        policy: PoisonPolicy,
+    ) -> Result<<Self::H1Guard as PlugLifetime<'a>>::T, LockError>
+    where
+        <Self::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = T>,
+    {
+        policy.recover(RwLock::<T>::write(self))
+    }
+
+    fn try_lock<'a>(&'a self) -> Option<<Self::H1Guard as PlugLifetime<'a>>::T>
+    where
+        <Self::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = T>,
+    {
+        RwLock::<T>::try_write(self).ok()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> ScopedLock<T> for Mutex<T>
+where
+    T: ?Sized,
+{
+    fn with(&self, f: &mut dyn FnMut(&T)) {
+        f(&Mutex::<T>::lock(self).unwrap())
+    }
+
+    fn with_mut(&self, f: &mut dyn FnMut(&mut T)) {
+        f(&mut Mutex::<T>::lock(self).unwrap())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> ScopedLock<T> for RwLock<T>
+where
+    T: ?Sized,
+{
+    fn with(&self, f: &mut dyn FnMut(&T)) {
+        f(&RwLock::<T>::read(self).unwrap())
+    }
+
+    fn with_mut(&self, f: &mut dyn FnMut(&mut T)) {
+        f(&mut RwLock::<T>::write(self).unwrap())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> RwLockLike<T> for RwLock<T>
+where
+    T: 'static,
+{
+    type H1ReadGuard = TypedH1RwLockReadGuard<T>;
+
+    fn read_with<'a>(
+        &'a self,
+        policy: PoisonPolicy,
+    ) -> Result<<Self::H1ReadGuard as PlugLifetime<'a>>::T, LockError>
+    where
+        <Self::H1ReadGuard as PlugLifetime<'a>>::T: Deref<Target = T>,
+    {
+        policy.recover(RwLock::<T>::read(self))
+    }
+
+    fn try_read<'a>(
+        &'a self,
+    ) -> Option<<Self::H1ReadGuard as PlugLifetime<'a>>::T>
+    where
+        <Self::H1ReadGuard as PlugLifetime<'a>>::T: Deref<Target = T>,
+    {
+        RwLock::<T>::try_read(self).ok()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> CellLike<T> for Mutex<T> {
+    fn new(x: T) -> Self {
+        Mutex::<T>::new(x)
+    }
+
+    fn get(&self) -> T
+    where
+        T: Copy,
+    {
+        *Mutex::<T>::lock(self).unwrap()
+    }
+
+    fn set(&self, x: T) {
+        *Mutex::<T>::lock(self).unwrap() = x;
+    }
+
+    fn replace(&self, x: T) -> T {
+        mem::replace(&mut Mutex::<T>::lock(self).unwrap(), x)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> OnceLike<T> for OnceLock<T> {
+    fn new() -> Self {
+        OnceLock::<T>::new()
+    }
+
+    fn get(&self) -> Option<&T> {
+        OnceLock::<T>::get(self)
+    }
+
+    fn set(&self, x: T) -> Result<(), T> {
+        OnceLock::<T>::set(self, x)
+    }
+
+    fn get_or_init<F>(&self, f: F) -> &T
+    where
+        F: FnOnce() -> T,
+    {
+        OnceLock::<T>::get_or_init(self, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, F> LazyLike<T> for LazyLock<T, F>
+where
+    F: FnOnce() -> T,
+{
+    fn force(this: &Self) -> &T {
+        LazyLock::<T, F>::force(this)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> ReborrowMut<T> for MutexGuard<'_, T>
+where
+    T: 'static + ?Sized,
+{
+    type H1Reborrow = TypedH1MutReference<T>;
+
+    fn reborrow_mut<'a>(
+        &'a mut self,
+    ) -> <Self::H1Reborrow as PlugLifetime<'a>>::T
+    where
+        <Self::H1Reborrow as PlugLifetime<'a>>::T: DerefMut<Target = T>,
+    {
+        &mut **self
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> ReborrowMut<T> for RwLockWriteGuard<'_, T>
+where
+    T: 'static + ?Sized,
+{
+    type H1Reborrow = TypedH1MutReference<T>;
+
+    fn reborrow_mut<'a>(
+        &'a mut self,
+    ) -> <Self::H1Reborrow as PlugLifetime<'a>>::T
+    where
+        <Self::H1Reborrow as PlugLifetime<'a>>::T: DerefMut<Target = T>,
+    {
+        &mut **self
+    }
+}
+
+/// Upgradable reads are emulated with exclusive access.
+#[cfg(feature = "std")]
+impl<T> UpgradableRwLock<T> for RwLock<T>
+where
+    T: 'static,
+{
+    type H1UpgradableGuard = Self::H1Guard;
+
+    fn upgradable_read_with<'a>(
+        &'a self,
+        policy: PoisonPolicy,
+    ) -> Result<<Self::H1UpgradableGuard as PlugLifetime<'a>>::T, LockError>
+    where
+        <Self::H1UpgradableGuard as PlugLifetime<'a>>::T: Deref<Target = T>,
+    {
+        policy.recover(RwLock::<T>::write(self))
+    }
+
+    fn upgrade<'a>(
+        guard: <Self::H1UpgradableGuard as PlugLifetime<'a>>::T,
+    ) -> <Self::H1Guard as PlugLifetime<'a>>::T
+    where
+        <Self::H1UpgradableGuard as PlugLifetime<'a>>::T: Deref<Target = T>,
+        <Self::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = T>,
+    {
+        guard
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> CondvarLike<T, Mutex<T>> for Condvar
+where
+    T: 'static,
+{
+    fn new() -> Self {
+        Condvar::new()
+    }
+
+    fn wait_with<'a>(
+        &self,
+        guard: MutexGuard<'a, T>,
+        policy: PoisonPolicy,
+    ) -> Result<MutexGuard<'a, T>, LockError>
+    where
+        MutexGuard<'a, T>: DerefMut<Target = T>,
+    {
+        policy.recover(Condvar::wait(self, guard))
+    }
+
+    fn notify_one(&self) {
+        Condvar::notify_one(self)
+    }
+
+    fn notify_all(&self) {
+        Condvar::notify_all(self)
+    }
+}
+
+/// Moves the value out of the `Arc` if it is unique, otherwise clones it.
+#[cfg(feature = "rc")]
+impl<T> Transform<H1Rc, T> for H1Arc
+where
+    T: Clone,
+{
+    fn transform(fa: Arc<T>) -> Rc<T> {
+        Rc::new(Arc::unwrap_or_clone(fa))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> channel::Sender<T> for mpsc::Sender<T> {
+    fn send(&self, x: T) -> Result<(), T> {
+        mpsc::Sender::<T>::send(self, x).map_err(|err| err.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> channel::Sender<T> for mpsc::SyncSender<T> {
+    fn send(&self, x: T) -> Result<(), T> {
+        mpsc::SyncSender::<T>::send(self, x).map_err(|err| err.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> channel::Receiver<T> for mpsc::Receiver<T>
+where
+    T: 'static,
+{
+    type Sender = mpsc::Sender<T>;
+    type H1Iterator = TypedH1ReceiverIter<T>;
+
+    fn channel() -> (Self::Sender, Self) {
+        mpsc::channel()
+    }
+
+    fn recv(&self) -> Result<T, RecvError> {
+        mpsc::Receiver::<T>::recv(self).map_err(|_| RecvError)
+    }
+
+    fn try_recv(&self) -> Result<T, TryRecvError> {
+        mpsc::Receiver::<T>::try_recv(self).map_err(|err| match err {
+            mpsc::TryRecvError::Empty => TryRecvError::Empty,
+            mpsc::TryRecvError::Disconnected => TryRecvError::Disconnected,
+        })
+    }
+
+    fn iter<'a>(&'a self) -> <Self::H1Iterator as PlugLifetime<'a>>::T
+    where
+        <Self::H1Iterator as PlugLifetime<'a>>::T: StreamingIterator,
+    {
+        mpsc::Receiver::<T>::iter(self)
+    }
 }
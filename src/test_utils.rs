@@ -0,0 +1,268 @@
+//! Reusable conformance tests for implementations of the traits in this crate.
+//!
+//! Each function exercises the documented behaviour of a trait and panics if
+//! the implementation under test doesn't follow it, so they can be called
+//! directly from `#[test]` functions.
+
+use crate::{
+    fp::{Functor, Monad, Monoid},
+    lock::{CellLike, Lock, LockError, RwLockLike, ScopedLock},
+    plug::{PlugLifetime, PlugType},
+    stream::{NextFuture, StreamingStream},
+    SharedMut,
+};
+use std::{
+    fmt::Debug,
+    future::Future,
+    ops::{Deref, DerefMut},
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+/// Check a [`Lock`](../lock/trait.Lock.html) implementation.
+pub fn check_lock<L>()
+where
+    L: Lock<usize>,
+    for<'a> <L::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = usize>,
+{
+    let lock = L::new(0);
+    {
+        let mut guard = lock.lock().unwrap();
+        *guard += 1;
+        assert!(lock.try_lock().is_none(), "locked twice");
+    }
+    {
+        let mut guard = lock.try_lock().expect("lock not released");
+        *guard += 1;
+    }
+    assert_eq!(*lock.lock().unwrap(), 2);
+}
+
+/// Check a [`RwLockLike`](../lock/trait.RwLockLike.html) implementation.
+pub fn check_rw_lock<L>()
+where
+    L: RwLockLike<usize>,
+    for<'a> <L::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = usize>,
+    for<'a> <L::H1ReadGuard as PlugLifetime<'a>>::T: Deref<Target = usize>,
+{
+    check_lock::<L>();
+
+    let lock = L::new(0);
+    {
+        let a = lock.read().unwrap();
+        let b = lock.try_read().expect("shared access is exclusive");
+        assert_eq!(*a + *b, 0);
+        assert!(lock.try_write().is_none(), "written while read");
+    }
+    {
+        let mut guard = lock.write().unwrap();
+        *guard += 1;
+        assert!(lock.try_read().is_none(), "read while written");
+    }
+    assert_eq!(*lock.read().unwrap(), 1);
+}
+
+/// Check that a [`Lock`](../lock/trait.Lock.html) implementation reports
+/// re-entrant locking as [`LockError::WouldBlock`] instead of deadlocking,
+/// as `RefCell` does.
+///
+/// [`LockError::WouldBlock`]: ../lock/enum.LockError.html#variant.WouldBlock
+pub fn check_non_blocking_lock<L>()
+where
+    L: Lock<usize>,
+    for<'a> <L::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = usize>,
+{
+    let lock = L::new(0);
+    let _guard = lock.lock().unwrap();
+    assert_eq!(lock.lock().err(), Some(LockError::WouldBlock));
+}
+
+/// Check a [`ScopedLock`](../lock/trait.ScopedLock.html) implementation
+/// protecting a `0`.
+pub fn check_scoped_lock(lock: &dyn ScopedLock<usize>) {
+    lock.with_mut(&mut |x| *x += 1);
+    let mut value = 0;
+    lock.with(&mut |x| value = *x);
+    assert_eq!(value, 1);
+}
+
+/// Check a [`CellLike`](../lock/trait.CellLike.html) implementation.
+pub fn check_cell_like<C>()
+where
+    C: CellLike<usize>,
+{
+    let cell = C::new(1);
+    assert_eq!(cell.get(), 1);
+    cell.set(2);
+    assert_eq!(cell.replace(3), 2);
+    assert_eq!(cell.take(), 3);
+    assert_eq!(cell.get(), 0);
+}
+
+/// Check a [`SharedMut`](../trait.SharedMut.html) implementation.
+pub fn check_shared_mut<S>()
+where
+    S: SharedMut<usize>,
+{
+    let a = S::new(0);
+    let b = a.clone();
+    a.with(|x| *x += 1);
+    b.with(|x| *x += 1);
+    assert_eq!(a.with(|x| *x), 2);
+}
+
+/// Check the [`Functor`](../fp/trait.Functor.html) laws for `K` with the
+/// value returned by `fa` and the functions `f` and `g`:
+///
+/// - Identity: mapping with the identity function returns the value unchanged.
+/// - Composition: mapping with `f` then `g` is the same as mapping once with
+///   their composition.
+pub fn check_functor_laws<K, A>(
+    fa: impl Fn() -> <K as PlugType<A>>::T,
+    f: impl Fn(A) -> A,
+    g: impl Fn(A) -> A,
+) where
+    K: Functor<A, A>,
+    <K as PlugType<A>>::T: PartialEq + Debug,
+{
+    assert_eq!(K::fmap(fa(), |x| x), fa(), "identity");
+    assert_eq!(
+        K::fmap(K::fmap(fa(), &f), &g),
+        K::fmap(fa(), |x| g(f(x))),
+        "composition"
+    );
+}
+
+/// Check the [`Monad`](../fp/trait.Monad.html) laws for `K` with the value
+/// `a`, the monadic value returned by `fa` and the functions `f` and `g`:
+///
+/// - Left identity: binding `pure(a)` to `f` is the same as `f(a)`.
+/// - Right identity: binding `fa` to `pure` returns `fa` unchanged.
+/// - Associativity: binding to `f` then `g` is the same as binding to a
+///   function that binds `f(x)` to `g`.
+pub fn check_monad_laws<K, A>(
+    a: A,
+    fa: impl Fn() -> <K as PlugType<A>>::T,
+    f: impl Fn(A) -> <K as PlugType<A>>::T,
+    g: impl Fn(A) -> <K as PlugType<A>>::T,
+) where
+    K: Monad<A, A>,
+    A: Clone,
+    <K as PlugType<A>>::T: PartialEq + Debug,
+{
+    assert_eq!(K::bind(K::pure(a.clone()), &f), f(a), "left identity");
+    assert_eq!(K::bind(fa(), K::pure), fa(), "right identity");
+    assert_eq!(
+        K::bind(K::bind(fa(), &f), &g),
+        K::bind(fa(), |x| K::bind(f(x), &g)),
+        "associativity"
+    );
+}
+
+/// Check the [`Monoid`](../fp/trait.Monoid.html) laws with the values `a`,
+/// `b` and `c`:
+///
+/// - Identity: combining with `empty()` on either side returns the value
+///   unchanged.
+/// - Associativity: the grouping of `combine` doesn't matter.
+pub fn check_monoid_laws<M>(a: M, b: M, c: M)
+where
+    M: Monoid + Clone + PartialEq + Debug,
+{
+    assert_eq!(M::empty().combine(a.clone()), a, "left identity");
+    assert_eq!(a.clone().combine(M::empty()), a, "right identity");
+    assert_eq!(
+        a.clone().combine(b.clone()).combine(c.clone()),
+        a.combine(b.combine(c)),
+        "associativity"
+    );
+}
+
+/// Check that a [`StreamingStream`](../stream/trait.StreamingStream.html)
+/// yields `expected` in order, then `None`. Items are converted with
+/// `to_owned` before being compared.
+///
+/// This and the other stream checks are async and don't spawn tasks, so they
+/// can be awaited on any executor.
+pub async fn check_streaming_stream<S, T, F>(
+    mut stream: S,
+    expected: &[T],
+    mut to_owned: F,
+) where
+    S: StreamingStream,
+    for<'a> <S::H1NextFuture as PlugLifetime<'a>>::T: NextFuture<'a, S::H1Item>,
+    F: for<'a> FnMut(<S::H1Item as PlugLifetime<'a>>::T) -> T,
+    T: PartialEq + Debug,
+{
+    for x in expected {
+        assert_eq!(stream.next().await.map(&mut to_owned).as_ref(), Some(x));
+    }
+    assert!(stream.next().await.is_none());
+}
+
+/// Check that a [`StreamingStream`](../stream/trait.StreamingStream.html)
+/// keeps returning `None` once exhausted.
+pub async fn check_fused_streaming_stream<S>(mut stream: S)
+where
+    S: StreamingStream,
+    for<'a> <S::H1NextFuture as PlugLifetime<'a>>::T: NextFuture<'a, S::H1Item>,
+{
+    while stream.next().await.is_some() {}
+    for _ in 0..3 {
+        assert!(stream.next().await.is_none());
+    }
+}
+
+/// Check that dropping a pending `next()` future of a
+/// [`StreamingStream`](../stream/trait.StreamingStream.html) doesn't lose
+/// items.
+///
+/// Each `next()` future is polled once before being awaited. If it's still
+/// pending, it's dropped and a new one is awaited instead, which must resolve
+/// to the same item.
+pub async fn check_cancel_safe_streaming_stream<S, T, F>(
+    mut stream: S,
+    expected: &[T],
+    mut to_owned: F,
+) where
+    S: StreamingStream,
+    for<'a> <S::H1NextFuture as PlugLifetime<'a>>::T: NextFuture<'a, S::H1Item>,
+    F: for<'a> FnMut(<S::H1Item as PlugLifetime<'a>>::T) -> T,
+    T: PartialEq + Debug,
+{
+    let mut cx = Context::from_waker(Waker::noop());
+    for x in expected.iter().map(Some).chain([None]) {
+        let polled = match pin!(stream.next()).poll(&mut cx) {
+            Poll::Ready(item) => Some(item.map(&mut to_owned)),
+            Poll::Pending => None,
+        };
+        let item = match polled {
+            Some(item) => item,
+            None => stream.next().await.map(&mut to_owned),
+        };
+        assert_eq!(item.as_ref(), x);
+    }
+}
+
+/// Model-check a thread-safe [`SharedMut`](../trait.SharedMut.html)
+/// implementation with [loom](https://crates.io/crates/loom). The
+/// implementation must be built on `loom` synchronization primitives, such as
+/// the ones in the [loom](../loom/index.html) module, for the model to be
+/// meaningful.
+#[cfg(feature = "loom")]
+pub fn model_shared_mut<S>()
+where
+    S: SharedMut<usize> + Send + 'static,
+{
+    ::loom::model(|| {
+        let shared = S::new(0);
+        let handle = ::loom::thread::spawn({
+            let shared = shared.clone();
+            move || shared.with(|x| *x += 1)
+        });
+        shared.with(|x| *x += 1);
+        handle.join().unwrap();
+
+        assert_eq!(shared.with(|x| *x), 2);
+    });
+}
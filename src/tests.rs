@@ -1,17 +1,66 @@
 use crate::{
-    plug::{PlugLifetime, PlugType},
-    rc::H1Rc,
+    borrow::H2Cow,
+    boxed::{self, H1Box, OutputH1BoxFuture, OutputH1SendBoxFuture},
+    cell::TypedH1Ref,
+    collections::{
+        DoublyLinkedList, H1BinaryHeap, H1LinkedList, ListNode, Tree, TreeNode,
+    },
+    convert_rcb,
+    effect::{H1Reader, H1State, H1Writer, Reader, State, Writer},
+    fp::{
+        self, Alternative, Applicative, Bifunctor, Contravariant, Foldable,
+        Functor, Monad, MonadPlus, Monoid, Profunctor, Pure, Transform,
+        Traversable, Zip,
+    },
+    function::{H1Comparator, H1Predicate, H2Fn},
+    hlist::{HCons, HList, HNil, Injector, PlugAll, PlugAny, Selector},
+    iter,
+    lock::{
+        CellLike, CondvarLike, LazyLike, Lock, LockError, MappableGuard,
+        MappableGuardMut, MaybeAtomic, MaybeAtomicInt, OnceLike, PoisonPolicy,
+        RwLockLike, ScopedLock, UpgradableRwLock,
+    },
+    optics::{self, Lens, Prism},
+    option::{self, H1Option},
+    plug::{H1Identity, PlugLifetime, PlugType, PlugType2, H0},
+    rc::{H1Rc, RcFamily},
     reference::TypedH1Reference,
-    sync::H1Arc,
-    Rcb, StreamingIterator,
+    result::{ErrH1Result, H2Result},
+    stream::{
+        self, AsyncSequenceMut, ItemH1BoxFuture, ItemH1Ready, NextFuture,
+        PollStreamingIterator, StreamingStream, StreamingStreamExt,
+    },
+    sync::{ArcFamily, H1Arc},
+    test_utils,
+    tuple::H2Tuple,
+    validated::{H1Validated, Validated},
+    vec::{self, H1Vec},
+    ByteBuf, ConvertRcb, CowLike, DowncastRcb, NewPtr, OwningPtr, PinnedRcb,
+    PointerFamily, Ptr, Rcb, RcbWithWeak, ReborrowMut, SharedMut, SharedRcb,
+    StreamingIterator, StringBuilder, Stringy, WeakRcb,
 };
 use async_executor::LocalExecutor;
+use futures_lite::future;
+#[cfg(feature = "allocator_api")]
+use std::alloc::AllocError;
 use std::{
+    any::Any,
+    borrow::Cow,
+    cell::{Cell, LazyCell, OnceCell, RefCell},
+    collections::{BTreeMap, HashMap},
     future::Future,
+    marker::PhantomPinned,
+    num::ParseIntError,
+    ops::{Deref, DerefMut},
+    panic,
     pin::Pin,
     rc::Rc,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Condvar, LazyLock, Mutex, OnceLock, RwLock,
+    },
     task::{Context, Poll},
+    thread,
 };
 
 #[test]
@@ -23,7 +72,7 @@ fn self_borrowing_iterator() {
     impl StreamingIterator for SelfBorrowingIterator {
         type H1Item = TypedH1Reference<[usize]>;
 
-        fn next(&mut self) -> Option<<Self::H1Item as PlugLifetime>::T> {
+        fn next(&mut self) -> Option<<Self::H1Item as PlugLifetime<'_>>::T> {
             if self.buf[0] == 2 {
                 None
             } else {
@@ -41,6 +90,147 @@ fn self_borrowing_iterator() {
     assert_eq!(iter.next(), None);
 }
 
+#[test]
+fn streaming_iterator_adapters() {
+    use crate::iter::StreamingIteratorExt;
+
+    struct Counter {
+        buf: [usize; 1],
+        end: usize,
+    }
+
+    impl StreamingIterator for Counter {
+        type H1Item = TypedH1Reference<[usize]>;
+
+        fn next(&mut self) -> Option<<Self::H1Item as PlugLifetime<'_>>::T> {
+            if self.buf[0] == self.end {
+                None
+            } else {
+                self.buf[0] += 1;
+
+                Some(&self.buf)
+            }
+        }
+    }
+
+    fn counter(end: usize) -> Counter {
+        Counter { buf: [0], end }
+    }
+
+    fn collect<I>(mut iter: I) -> Vec<usize>
+    where
+        I: StreamingIterator,
+        for<'a> <I::H1Item as PlugLifetime<'a>>::T: AsRef<[usize]>,
+    {
+        let mut v = Vec::new();
+        while let Some(x) = iter.next() {
+            v.push(x.as_ref()[0]);
+        }
+
+        v
+    }
+
+    assert_eq!(collect(counter(10).filter(|x| x[0] % 3 == 0)), [3, 6, 9]);
+    assert_eq!(collect(counter(10).take(2)), [1, 2]);
+    assert_eq!(collect(counter(2).take(5)), [1, 2]);
+    assert_eq!(collect(counter(5).skip(3)), [4, 5]);
+    assert_eq!(collect(counter(2).skip(5)), []);
+    assert_eq!(collect(counter(5).skip_while(|x| x[0] < 3)), [3, 4, 5]);
+    assert_eq!(
+        collect(counter(10).skip_while(|x| x[0] < 3).filter(|x| x[0] < 5)),
+        [3, 4],
+    );
+
+    let mut squares = counter(10)
+        .skip(1)
+        .filter(|x| x[0] % 2 == 0)
+        .take(3)
+        .map(|x| x[0] * x[0]);
+    let mut v = Vec::new();
+    while let Some(x) = squares.next() {
+        v.push(x);
+    }
+    assert_eq!(v, [4, 16, 36]);
+}
+
+// The filtering adapters reborrow the underlying iterator through a raw
+// pointer after rejecting an item, so these cases are also run under Miri
+#[test]
+fn streaming_adapters_reborrow() {
+    use crate::iter::StreamingIteratorExt;
+
+    // Yields 1 to `end` from a buffer that is overwritten on each call, and
+    // `Pending` before each item when polled
+    struct Overwriting {
+        buf: Box<usize>,
+        end: usize,
+        ready: bool,
+    }
+
+    impl StreamingIterator for Overwriting {
+        type H1Item = TypedH1Reference<usize>;
+
+        fn next(&mut self) -> Option<&usize> {
+            if *self.buf == self.end {
+                return None;
+            }
+            *self.buf += 1;
+
+            Some(&self.buf)
+        }
+    }
+
+    impl PollStreamingIterator for Overwriting {
+        type H1Item = TypedH1Reference<usize>;
+
+        fn poll_next<'a>(
+            self: Pin<&'a mut Self>,
+            cx: &mut Context,
+        ) -> Poll<Option<&'a usize>> {
+            let this = self.get_mut();
+            if !this.ready && *this.buf != this.end {
+                this.ready = true;
+                cx.waker().wake_by_ref();
+
+                return Poll::Pending;
+            }
+            this.ready = false;
+
+            Poll::Ready(StreamingIterator::next(this))
+        }
+    }
+
+    let overwriting = |end| Overwriting {
+        buf: Box::new(0),
+        end,
+        ready: false,
+    };
+
+    // Rejected then accepted, then exhausted while rejecting
+    let mut picked = overwriting(5).filter(|x: &&usize| **x == 2 || **x == 4);
+    assert_eq!(picked.next(), Some(&2));
+    assert_eq!(picked.next(), Some(&4));
+    assert_eq!(picked.next(), None);
+    assert_eq!(picked.next(), None);
+
+    // Exhausted while still skipping, then skipping only a prefix
+    let mut skipped = overwriting(3).skip_while(|_: &&usize| true);
+    assert_eq!(skipped.next(), None);
+    assert_eq!(skipped.next(), None);
+    let mut late = overwriting(3).skip_while(|x: &&usize| **x < 3);
+    assert_eq!(late.next(), Some(&3));
+    assert_eq!(late.next(), None);
+
+    let mut picked = stream::from_poll(overwriting(5))
+        .filter(|x: &&usize| **x == 2 || **x == 4);
+    assert_eq!(future::block_on(picked.next()), Some(&2));
+    assert_eq!(future::block_on(picked.next()), Some(&4));
+    assert_eq!(future::block_on(picked.next()), None);
+
+    let mut none = stream::from_poll(overwriting(3)).filter(|_: &&usize| false);
+    assert_eq!(future::block_on(none.next()), None);
+}
+
 #[test]
 fn struct_using_either_rc_or_arc() {
     #[derive(Clone)]
@@ -77,67 +267,3179 @@ fn struct_using_either_rc_or_arc() {
     );
 }
 
+#[cfg(feature = "triomphe")]
 #[test]
-fn struct_using_either_rc_or_arc_with_hkt() {
-    struct StructWithReferenceCount<R>
+fn triomphe_arc_as_rcb() {
+    fn clone_secret<R>() -> String
     where
-        R: PlugType<String> + PlugType<usize>,
+        R: Rcb<String>,
     {
-        secret1: <R as PlugType<String>>::T,
-        secret2: <R as PlugType<usize>>::T,
+        let x = R::new("xpotato".to_string());
+
+        x.clone().as_str().to_string()
     }
 
-    impl<R> StructWithReferenceCount<R>
+    assert_eq!(clone_secret::<::triomphe::Arc<String>>(), "xpotato");
+}
+
+#[test]
+fn shared_rcb_across_threads() {
+    fn sum_in_threads<R>(x: R) -> usize
     where
-        R: PlugType<String> + PlugType<usize>,
-        <R as PlugType<String>>::T: Rcb<String>,
-        <R as PlugType<usize>>::T: Rcb<usize>,
+        R: SharedRcb<usize> + 'static,
+    {
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let x = x.clone();
+
+                thread::spawn(move || *x)
+            })
+            .collect();
+
+        handles.into_iter().map(|x| x.join().unwrap()).sum()
+    }
+
+    assert_eq!(sum_in_threads(Arc::new(10)), 40);
+    #[cfg(feature = "triomphe")]
+    assert_eq!(sum_in_threads(::triomphe::Arc::new(10)), 40);
+}
+
+#[test]
+fn struct_using_any_shared_mut() {
+    struct Counter<S> {
+        count: S,
+    }
+
+    impl<S> Counter<S>
+    where
+        S: SharedMut<usize>,
     {
         fn new() -> Self {
+            Self { count: S::new(0) }
+        }
+
+        fn handle(&self) -> Self {
             Self {
-                secret1: <R as PlugType<String>>::T::new("xpotato".to_string()),
-                secret2: <R as PlugType<usize>>::T::new(42),
+                count: self.count.clone(),
             }
         }
 
-        fn secrets(&self) -> (&str, usize) {
-            (self.secret1.as_str(), *self.secret2)
+        fn increment(&self) -> usize {
+            self.count.with(|x| {
+                *x += 1;
+
+                *x
+            })
         }
     }
 
-    // Autoderive doesn't work in this case
-    impl<R> Clone for StructWithReferenceCount<R>
+    fn check<S>()
     where
-        R: PlugType<String> + PlugType<usize>,
-        <R as PlugType<String>>::T: Rcb<String>,
-        <R as PlugType<usize>>::T: Rcb<usize>,
+        S: SharedMut<usize>,
     {
-        fn clone(&self) -> Self {
-            Self {
-                secret1: self.secret1.clone(),
-                secret2: self.secret2.clone(),
+        let counter = Counter::<S>::new();
+        let handle = counter.handle();
+
+        assert_eq!(counter.increment(), 1);
+        assert_eq!(handle.increment(), 2);
+    }
+
+    check::<Rc<RefCell<usize>>>();
+    check::<Arc<Mutex<usize>>>();
+    check::<Arc<RwLock<usize>>>();
+}
+
+#[test]
+fn struct_using_any_lock() {
+    struct Counter<L> {
+        count: L,
+    }
+
+    impl<L> Counter<L>
+    where
+        L: Lock<usize>,
+        for<'a> <L::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = usize>,
+    {
+        fn new() -> Self {
+            Self { count: L::new(0) }
+        }
+
+        fn increment(&self) -> usize {
+            let mut count = self.count.lock().unwrap();
+            *count += 1;
+
+            *count
+        }
+    }
+
+    fn check<L>()
+    where
+        L: Lock<usize>,
+        for<'a> <L::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = usize>,
+    {
+        let counter = Counter::<L>::new();
+
+        assert_eq!(counter.increment(), 1);
+        assert_eq!(counter.increment(), 2);
+    }
+
+    check::<RefCell<usize>>();
+    check::<Mutex<usize>>();
+    check::<RwLock<usize>>();
+    #[cfg(feature = "parking_lot")]
+    {
+        check::<parking_lot::Mutex<usize>>();
+        check::<parking_lot::RwLock<usize>>();
+    }
+}
+
+#[test]
+fn lock_errors() {
+    fn poisoned<L>() -> L
+    where
+        L: Lock<usize> + Send + Sync,
+        for<'a> <L::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = usize>,
+    {
+        let lock = L::new(42);
+        thread::scope(|scope| {
+            let handle = scope.spawn(|| {
+                let _guard = lock.lock();
+                panic!("poison the lock");
+            });
+            assert!(handle.join().is_err());
+        });
+
+        lock
+    }
+
+    fn check_poisoned<L>()
+    where
+        L: Lock<usize> + Send + Sync,
+        for<'a> <L::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = usize>,
+    {
+        let lock = poisoned::<L>();
+        assert_eq!(lock.lock().err(), Some(LockError::Poisoned));
+        assert_eq!(*lock.lock_with(PoisonPolicy::Ignore).unwrap(), 42);
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            let _ = lock.lock_with(PoisonPolicy::Panic);
+        }));
+        assert!(result.is_err());
+    }
+
+    check_poisoned::<Mutex<usize>>();
+    check_poisoned::<RwLock<usize>>();
+
+    let cell = RefCell::new(42);
+    let _guard = Lock::lock(&cell).unwrap();
+    assert_eq!(Lock::lock(&cell).err(), Some(LockError::WouldBlock));
+    assert_eq!(cell.read().err(), Some(LockError::WouldBlock));
+}
+
+#[test]
+fn try_lock() {
+    fn check<L>()
+    where
+        L: RwLockLike<usize>,
+        for<'a> <L::H1Guard as PlugLifetime<'a>>::T: DerefMut<Target = usize>,
+        for<'a> <L::H1ReadGuard as PlugLifetime<'a>>::T: Deref<Target = usize>,
+    {
+        let lock = L::new(42);
+        {
+            let _guard = lock.try_write().unwrap();
+            assert!(lock.try_lock().is_none());
+            assert!(lock.try_read().is_none());
+        }
+        {
+            let _guard = lock.try_read().unwrap();
+            assert!(lock.try_read().is_some());
+            assert!(lock.try_write().is_none());
+        }
+        assert_eq!(*lock.try_lock().unwrap(), 42);
+    }
+
+    check::<RefCell<usize>>();
+    check::<RwLock<usize>>();
+    #[cfg(feature = "parking_lot")]
+    check::<parking_lot::RwLock<usize>>();
+
+    let mutex = Mutex::new(42);
+    let guard = Lock::try_lock(&mutex).unwrap();
+    assert!(Lock::try_lock(&mutex).is_none());
+    drop(guard);
+    assert!(Lock::try_lock(&mutex).is_some());
+}
+
+#[test]
+fn upgradable_read_cache() {
+    fn get_or_insert<L>(cache: &L, key: usize) -> String
+    where
+        L: UpgradableRwLock<Vec<(usize, String)>>,
+        for<'a> <L::H1Guard as PlugLifetime<'a>>::T:
+            DerefMut<Target = Vec<(usize, String)>>,
+        for<'a> <L::H1UpgradableGuard as PlugLifetime<'a>>::T:
+            Deref<Target = Vec<(usize, String)>>,
+    {
+        let entries = cache.upgradable_read().unwrap();
+        if let Some((_, value)) = entries.iter().find(|(k, _)| *k == key) {
+            return value.clone();
+        }
+
+        let mut entries = L::upgrade(entries);
+        let value = key.to_string();
+        entries.push((key, value.clone()));
+
+        value
+    }
+
+    fn check<L>()
+    where
+        L: UpgradableRwLock<Vec<(usize, String)>>,
+        for<'a> <L::H1Guard as PlugLifetime<'a>>::T:
+            DerefMut<Target = Vec<(usize, String)>>,
+        for<'a> <L::H1UpgradableGuard as PlugLifetime<'a>>::T:
+            Deref<Target = Vec<(usize, String)>>,
+    {
+        let cache = L::new(Vec::new());
+
+        assert_eq!(get_or_insert(&cache, 1), "1");
+        assert_eq!(get_or_insert(&cache, 1), "1");
+        assert_eq!(get_or_insert(&cache, 2), "2");
+        assert_eq!(cache.lock().unwrap().len(), 2);
+    }
+
+    check::<RefCell<_>>();
+    check::<RwLock<_>>();
+    #[cfg(feature = "parking_lot")]
+    check::<parking_lot::RwLock<_>>();
+}
+
+#[test]
+fn map_guard() {
+    struct Person<L> {
+        inner: L,
+    }
+
+    struct PersonInner {
+        name: String,
+        age: usize,
+    }
+
+    type H1Name<L> = <<L as Lock<PersonInner>>::H1Guard as MappableGuardMut<
+        PersonInner,
+        String,
+    >>::H1Mapped;
+
+    impl<L> Person<L>
+    where
+        L: Lock<PersonInner>,
+        L::H1Guard: MappableGuardMut<PersonInner, String>,
+        for<'a> <L::H1Guard as PlugLifetime<'a>>::T:
+            DerefMut<Target = PersonInner>,
+    {
+        fn name<'a>(&'a self) -> <H1Name<L> as PlugLifetime<'a>>::T {
+            L::H1Guard::map_guard_mut(self.inner.lock().unwrap(), |x| {
+                &mut x.name
+            })
+        }
+    }
+
+    fn rename<L>(person: &Person<L>)
+    where
+        L: Lock<PersonInner>,
+        L::H1Guard: MappableGuardMut<PersonInner, String>,
+        for<'a> <L::H1Guard as PlugLifetime<'a>>::T:
+            DerefMut<Target = PersonInner>,
+        for<'a> <H1Name<L> as PlugLifetime<'a>>::T: DerefMut<Target = String>,
+    {
+        let mut name = person.name();
+        assert_eq!(*name, "Alice");
+        name.push_str(" Smith");
+    }
+
+    let person = Person {
+        inner: RefCell::new(PersonInner {
+            name: "Alice".to_string(),
+            age: 30,
+        }),
+    };
+    rename(&person);
+    assert_eq!(person.inner.borrow().name, "Alice Smith");
+    let age = TypedH1Ref::map_guard(person.inner.borrow(), |x| &x.age);
+    assert_eq!(*age, 30);
+
+    #[cfg(feature = "parking_lot")]
+    {
+        let person = Person {
+            inner: parking_lot::Mutex::new(PersonInner {
+                name: "Alice".to_string(),
+                age: 30,
+            }),
+        };
+        rename(&person);
+        assert_eq!(person.inner.lock().name, "Alice Smith");
+    }
+}
+
+#[test]
+fn generic_condvar() {
+    fn check<L, C>()
+    where
+        L: Lock<Option<usize>> + Sync,
+        C: CondvarLike<Option<usize>, L> + Sync,
+        for<'a> <L::H1Guard as PlugLifetime<'a>>::T:
+            DerefMut<Target = Option<usize>>,
+    {
+        let slot = L::new(None);
+        let ready = C::new();
+
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                *slot.lock().unwrap() = Some(42);
+                ready.notify_all();
+            });
+
+            let mut value = slot.lock().unwrap();
+            while value.is_none() {
+                value = ready.wait(value).unwrap();
             }
+            assert_eq!(*value, Some(42));
+        });
+    }
+
+    check::<Mutex<_>, Condvar>();
+    #[cfg(feature = "parking_lot")]
+    check::<parking_lot::Mutex<_>, parking_lot::Condvar>();
+}
+
+#[test]
+fn lock_conformance() {
+    test_utils::check_lock::<Mutex<usize>>();
+    test_utils::check_rw_lock::<RwLock<usize>>();
+    test_utils::check_rw_lock::<RefCell<usize>>();
+    test_utils::check_non_blocking_lock::<RefCell<usize>>();
+    test_utils::check_scoped_lock(&Mutex::new(0));
+    test_utils::check_scoped_lock(&RwLock::new(0));
+    test_utils::check_scoped_lock(&RefCell::new(0));
+    test_utils::check_cell_like::<Cell<usize>>();
+    test_utils::check_cell_like::<Mutex<usize>>();
+    test_utils::check_shared_mut::<Rc<RefCell<usize>>>();
+    test_utils::check_shared_mut::<Arc<Mutex<usize>>>();
+    test_utils::check_shared_mut::<Arc<RwLock<usize>>>();
+    #[cfg(feature = "parking_lot")]
+    {
+        test_utils::check_lock::<parking_lot::Mutex<usize>>();
+        test_utils::check_rw_lock::<parking_lot::RwLock<usize>>();
+        test_utils::check_scoped_lock(&parking_lot::Mutex::new(0));
+        test_utils::check_scoped_lock(&parking_lot::RwLock::new(0));
+    }
+    #[cfg(feature = "crossbeam-utils")]
+    test_utils::check_cell_like::<crossbeam_utils::atomic::AtomicCell<usize>>();
+}
+
+#[cfg(feature = "loom")]
+#[test]
+fn loom_shared_mut() {
+    use ::loom::sync::{Arc, Mutex, RwLock};
+
+    test_utils::model_shared_mut::<Arc<Mutex<usize>>>();
+    test_utils::model_shared_mut::<Arc<RwLock<usize>>>();
+}
+
+#[test]
+fn dyn_scoped_lock() {
+    let locks: Vec<Box<dyn ScopedLock<Vec<usize>>>> = vec![
+        Box::new(RefCell::new(vec![1])),
+        Box::new(Mutex::new(vec![1])),
+        Box::new(RwLock::new(vec![1])),
+    ];
+
+    for lock in &locks {
+        lock.with_mut(&mut |x| x.push(2));
+        let mut sum = 0;
+        lock.with(&mut |x| sum = x.iter().sum());
+        assert_eq!(sum, 3);
+    }
+}
+
+#[test]
+fn read_mostly_cache() {
+    struct Cache<L> {
+        entries: L,
+    }
+
+    impl<L> Cache<L>
+    where
+        L: RwLockLike<Vec<(usize, String)>>,
+        for<'a> <L::H1Guard as PlugLifetime<'a>>::T:
+            DerefMut<Target = Vec<(usize, String)>>,
+        for<'a> <L::H1ReadGuard as PlugLifetime<'a>>::T:
+            Deref<Target = Vec<(usize, String)>>,
+    {
+        fn get_or_insert(&self, key: usize) -> String {
+            let found = self
+                .entries
+                .read()
+                .unwrap()
+                .iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, v)| v.clone());
+            found.unwrap_or_else(|| {
+                let value = key.to_string();
+                self.entries.write().unwrap().push((key, value.clone()));
+
+                value
+            })
+        }
+
+        fn len(&self) -> usize {
+            self.entries.read().unwrap().len()
         }
     }
 
-    assert_eq!(
-        StructWithReferenceCount::<H1Rc>::new().clone().secrets(),
-        ("xpotato", 42)
-    );
-    assert_eq!(
-        StructWithReferenceCount::<H1Arc>::new().clone().secrets(),
-        ("xpotato", 42)
-    );
+    fn check<L>()
+    where
+        L: RwLockLike<Vec<(usize, String)>>,
+        for<'a> <L::H1Guard as PlugLifetime<'a>>::T:
+            DerefMut<Target = Vec<(usize, String)>>,
+        for<'a> <L::H1ReadGuard as PlugLifetime<'a>>::T:
+            Deref<Target = Vec<(usize, String)>>,
+    {
+        let cache = Cache {
+            entries: L::new(Vec::new()),
+        };
+
+        assert_eq!(cache.get_or_insert(1), "1");
+        assert_eq!(cache.get_or_insert(2), "2");
+        assert_eq!(cache.get_or_insert(1), "1");
+        assert_eq!(cache.len(), 2);
+    }
+
+    check::<RefCell<_>>();
+    check::<RwLock<_>>();
+    #[cfg(feature = "parking_lot")]
+    check::<parking_lot::RwLock<_>>();
+}
+
+#[test]
+fn struct_using_any_cell() {
+    struct Stats<C> {
+        hits: C,
+        last: C,
+    }
+
+    fn check<C>()
+    where
+        C: CellLike<usize>,
+    {
+        let stats = Stats {
+            hits: C::new(0),
+            last: C::new(0),
+        };
+
+        for x in 1..=3 {
+            stats.hits.set(stats.hits.get() + 1);
+            assert_eq!(stats.last.replace(x), x - 1);
+        }
+        assert_eq!(stats.hits.get(), 3);
+        assert_eq!(stats.last.take(), 3);
+        assert_eq!(stats.last.get(), 0);
+    }
+
+    check::<Cell<usize>>();
+    check::<Mutex<usize>>();
+    #[cfg(feature = "crossbeam-utils")]
+    check::<crossbeam_utils::atomic::AtomicCell<usize>>();
+}
+
+#[cfg(any(feature = "async-lock", feature = "tokio"))]
+#[test]
+fn struct_using_any_async_lock() {
+    use crate::lock::{AsyncLock, GuardFuture};
+
+    async fn increment<L>(count: &L) -> usize
+    where
+        L: AsyncLock<usize>,
+        for<'a> <L::H1LockFuture as PlugLifetime<'a>>::T: GuardFuture<usize>,
+    {
+        let mut count = count.lock().await;
+        *count += 1;
+
+        *count
+    }
+
+    fn check<L>()
+    where
+        L: AsyncLock<usize>,
+        for<'a> <L::H1LockFuture as PlugLifetime<'a>>::T: GuardFuture<usize>,
+    {
+        let count = L::new(0);
+
+        assert_eq!(future::block_on(increment(&count)), 1);
+        assert_eq!(future::block_on(increment(&count)), 2);
+    }
+
+    #[cfg(feature = "async-lock")]
+    check::<async_lock::Mutex<usize>>();
+    #[cfg(feature = "tokio")]
+    check::<tokio::sync::Mutex<usize>>();
+}
+
+#[cfg(feature = "tokio")]
+#[test]
+fn struct_using_any_async_rw_lock() {
+    use crate::lock::{AsyncRwLockLike, GuardFuture, ReadGuardFuture};
+
+    struct Cache<L> {
+        entries: L,
+    }
+
+    impl<L> Cache<L>
+    where
+        L: AsyncRwLockLike<Vec<usize>>,
+        for<'a> <L::H1LockFuture as PlugLifetime<'a>>::T:
+            GuardFuture<Vec<usize>>,
+        for<'a> <L::H1ReadFuture as PlugLifetime<'a>>::T:
+            ReadGuardFuture<Vec<usize>>,
+    {
+        async fn insert(&self, x: usize) {
+            self.entries.lock().await.push(x);
+        }
+
+        async fn len(&self) -> usize {
+            self.entries.read().await.len()
+        }
+    }
+
+    let cache = Cache {
+        entries: tokio::sync::RwLock::new(Vec::new()),
+    };
+    future::block_on(cache.insert(1));
+    future::block_on(cache.insert(2));
+    assert_eq!(future::block_on(cache.len()), 2);
+}
+
+#[cfg(any(feature = "async-once-cell", feature = "tokio"))]
+#[test]
+fn struct_using_any_async_once() {
+    use crate::lock::{AsyncOnceLike, InitFuture};
+
+    struct Connection<O> {
+        address: O,
+    }
+
+    impl<O> Connection<O>
+    where
+        O: AsyncOnceLike<String>,
+        for<'a> <O::H1InitFuture as PlugLifetime<'a>>::T:
+            InitFuture<'a, String>,
+    {
+        async fn address(&self) -> &str {
+            self.address
+                .get_or_init(async { "localhost".to_string() })
+                .await
+        }
+    }
+
+    fn check<O>()
+    where
+        O: AsyncOnceLike<String>,
+        for<'a> <O::H1InitFuture as PlugLifetime<'a>>::T:
+            InitFuture<'a, String>,
+    {
+        let connection = Connection { address: O::new() };
+        assert_eq!(connection.address.get(), None);
+        assert_eq!(future::block_on(connection.address()), "localhost");
+        assert_eq!(
+            future::block_on(
+                connection
+                    .address
+                    .get_or_init(async { "remote".to_string() })
+            ),
+            "localhost"
+        );
+        assert_eq!(connection.address.get().unwrap(), "localhost");
+    }
+
+    #[cfg(feature = "async-once-cell")]
+    check::<async_once_cell::OnceCell<String>>();
+    #[cfg(feature = "tokio")]
+    check::<tokio::sync::OnceCell<String>>();
+}
+
+#[test]
+fn struct_using_any_once() {
+    struct Config<O> {
+        name: O,
+    }
+
+    impl<O> Config<O>
+    where
+        O: OnceLike<String>,
+    {
+        fn name(&self) -> &str {
+            self.name.get_or_init(|| "default".to_string())
+        }
+    }
+
+    fn check<O>()
+    where
+        O: OnceLike<String>,
+    {
+        let config = Config { name: O::new() };
+        assert_eq!(config.name.get(), None);
+        assert_eq!(config.name(), "default");
+        assert_eq!(
+            config.name.set("other".to_string()),
+            Err("other".to_string())
+        );
+
+        let config = Config { name: O::new() };
+        assert_eq!(config.name.set("custom".to_string()), Ok(()));
+        assert_eq!(config.name(), "custom");
+    }
+
+    fn force<L>(lazy: &L) -> usize
+    where
+        L: LazyLike<usize>,
+    {
+        *L::force(lazy)
+    }
+
+    check::<OnceCell<String>>();
+    check::<OnceLock<String>>();
+    assert_eq!(force(&LazyCell::new(|| 42)), 42);
+    assert_eq!(force(&LazyLock::new(|| 42)), 42);
+    #[cfg(feature = "once_cell")]
+    {
+        check::<once_cell::unsync::OnceCell<String>>();
+        check::<once_cell::sync::OnceCell<String>>();
+        assert_eq!(force(&once_cell::unsync::Lazy::new(|| 42)), 42);
+        assert_eq!(force(&once_cell::sync::Lazy::new(|| 42)), 42);
+    }
+}
+
+#[test]
+fn struct_using_any_counter() {
+    struct Stats<C, F> {
+        hits: C,
+        done: F,
+    }
+
+    fn check<C, F>()
+    where
+        C: MaybeAtomicInt<usize>,
+        F: MaybeAtomic<bool>,
+    {
+        let stats = Stats {
+            hits: C::new(0),
+            done: F::new(false),
+        };
+
+        for _ in 0..3 {
+            stats.hits.fetch_add(1, Ordering::Relaxed);
+        }
+        assert_eq!(stats.hits.fetch_sub(1, Ordering::Relaxed), 3);
+        assert_eq!(stats.hits.load(Ordering::Relaxed), 2);
+        assert_eq!(
+            stats.done.compare_exchange(
+                false,
+                true,
+                Ordering::AcqRel,
+                Ordering::Acquire
+            ),
+            Ok(false)
+        );
+        assert_eq!(
+            stats.done.compare_exchange(
+                false,
+                true,
+                Ordering::AcqRel,
+                Ordering::Acquire
+            ),
+            Err(true)
+        );
+        assert!(stats.done.swap(false, Ordering::AcqRel));
+        stats.hits.store(0, Ordering::Relaxed);
+        assert_eq!(stats.hits.load(Ordering::Relaxed), 0);
+    }
+
+    check::<Cell<usize>, Cell<bool>>();
+    check::<AtomicUsize, AtomicBool>();
+}
+
+#[test]
+fn generic_reborrow() {
+    fn push_twice<R>(mut handle: R) -> usize
+    where
+        R: ReborrowMut<Vec<usize>>,
+        for<'a> <R::H1Reborrow as PlugLifetime<'a>>::T:
+            DerefMut<Target = Vec<usize>>,
+    {
+        fn push(x: &mut Vec<usize>) {
+            x.push(x.len());
+        }
+
+        push(&mut handle.reborrow_mut());
+        push(&mut handle.reborrow_mut());
+
+        handle.reborrow_mut().len()
+    }
+
+    let mut vec = Vec::new();
+    let cell = RefCell::new(Vec::new());
+    let mutex = Mutex::new(Vec::new());
+
+    assert_eq!(push_twice(&mut vec), 2);
+    assert_eq!(push_twice(Box::new(vec)), 4);
+    assert_eq!(push_twice(cell.borrow_mut()), 2);
+    assert_eq!(push_twice(mutex.lock().unwrap()), 2);
+    assert_eq!(*mutex.lock().unwrap(), [0, 1]);
+}
+
+#[test]
+fn generic_functor() {
+    fn describe<K>(
+        numbers: <K as PlugType<usize>>::T,
+    ) -> <K as PlugType<String>>::T
+    where
+        K: Functor<usize, String>,
+    {
+        K::fmap(numbers, |x| format!("#{}", x))
+    }
+
+    assert_eq!(describe::<H1Vec>(vec![1, 2]), ["#1", "#2"]);
+    assert_eq!(describe::<H1Option>(Some(1)), Some("#1".to_string()));
+    assert_eq!(describe::<H1Option>(None), None);
+    assert_eq!(describe::<H1Box>(Box::new(1)), Box::new("#1".to_string()));
+    assert_eq!(
+        describe::<ErrH1Result<()>>(Ok(1)),
+        Ok::<_, ()>("#1".to_string())
+    );
+    assert_eq!(describe::<H1Identity>(1), "#1");
+}
+
+#[test]
+fn generic_applicative() {
+    fn add<K>(
+        a: <K as PlugType<usize>>::T,
+        b: <K as PlugType<usize>>::T,
+    ) -> <K as PlugType<usize>>::T
+    where
+        K: Applicative<usize, usize, usize>,
+    {
+        K::map2(a, b, |a, b| a + b)
+    }
+
+    fn increment<K>(x: usize) -> <K as PlugType<usize>>::T
+    where
+        K: Pure<usize> + Applicative<usize, usize, usize>,
+    {
+        add::<K>(K::pure(x), K::pure(1))
+    }
+
+    assert_eq!(add::<H1Option>(Some(1), Some(2)), Some(3));
+    assert_eq!(add::<H1Option>(Some(1), None), None);
+    assert_eq!(add::<H1Vec>(vec![1, 2], vec![10, 20]), [11, 21, 12, 22]);
+    assert_eq!(add::<ErrH1Result<&str>>(Ok(1), Err("no")), Err("no"));
+    assert_eq!(increment::<H1Box>(1), Box::new(2));
+    assert_eq!(increment::<H1Identity>(1), 2);
+
+    let ff: Vec<fn(usize) -> usize> = vec![|x| x + 1, |x| x * 10];
+    assert_eq!(fp::apply::<H1Vec, _, _, _>(ff, vec![1, 2]), [2, 3, 10, 20]);
+}
+
+#[test]
+fn generic_monad() {
+    fn half_twice<K>(
+        x: <K as PlugType<usize>>::T,
+        half: impl Fn(usize) -> <K as PlugType<usize>>::T + Copy,
+    ) -> <K as PlugType<usize>>::T
+    where
+        K: Monad<usize, usize>,
+    {
+        K::bind(K::bind(x, half), half)
+    }
+
+    fn even_half(x: usize) -> Option<usize> {
+        if x.is_multiple_of(2) {
+            Some(x / 2)
+        } else {
+            None
+        }
+    }
+
+    assert_eq!(half_twice::<H1Option>(Some(8), even_half), Some(2));
+    assert_eq!(half_twice::<H1Option>(Some(6), even_half), None);
+    assert_eq!(
+        half_twice::<ErrH1Result<usize>>(Ok(6), |x| even_half(x).ok_or(x)),
+        Err(3)
+    );
+    assert_eq!(
+        half_twice::<H1Vec>(vec![8], |x| vec![x / 2, x / 2 + 1]),
+        [2, 3, 2, 3]
+    );
+    assert_eq!(
+        half_twice::<H1Box>(Box::new(8), |x| Box::new(x / 2)),
+        Box::new(2)
+    );
+    assert_eq!(half_twice::<H1Identity>(8, |x| x / 2), 2);
+}
+
+#[test]
+fn generic_foldable() {
+    fn summary<K>(xs: impl Fn() -> <K as PlugType<usize>>::T) -> String
+    where
+        K: Foldable<usize>,
+    {
+        format!(
+            "{} items, total {}, sorted {:?}",
+            K::length(xs()),
+            K::fold_map(xs(), |x| fp::Sum(x * 10)).0,
+            {
+                let mut vec = K::to_vec(xs());
+                vec.sort();
+
+                vec
+            }
+        )
+    }
+
+    assert_eq!(
+        summary::<H1Vec>(|| vec![2, 1]),
+        "2 items, total 30, sorted [1, 2]"
+    );
+    assert_eq!(summary::<H1Option>(|| None), "0 items, total 0, sorted []");
+    assert_eq!(
+        summary::<ErrH1Result<()>>(|| Ok(1)),
+        "1 items, total 10, sorted [1]"
+    );
+    assert_eq!(
+        summary::<H1BinaryHeap>(|| vec![3, 1, 2].into()),
+        "3 items, total 60, sorted [1, 2, 3]"
+    );
+    assert_eq!(
+        H1LinkedList::fold((1..4).collect(), 0, |a, x| a * 10 + x),
+        123
+    );
+}
+
+#[test]
+fn generic_traverse() {
+    fn parse_all<K, M>(
+        xs: <K as PlugType<&'static str>>::T,
+        parse: impl Fn(&str) -> <M as PlugType<usize>>::T,
+    ) -> <M as PlugType<<K as PlugType<usize>>::T>>::T
+    where
+        K: Traversable<M, &'static str, usize>,
+        M: PlugType<usize> + PlugType<<K as PlugType<usize>>::T>,
+    {
+        K::traverse(xs, parse)
+    }
+
+    let some = |x: &str| x.parse().ok();
+    let ok = |x: &str| x.parse::<usize>();
+
+    assert_eq!(
+        parse_all::<H1Vec, H1Option>(vec!["1", "2"], some),
+        Some(vec![1, 2])
+    );
+    assert_eq!(parse_all::<H1Vec, H1Option>(vec!["1", "x"], some), None);
+    assert_eq!(
+        parse_all::<H1Vec, ErrH1Result<_>>(vec!["1", "2"], ok),
+        Ok(vec![1, 2])
+    );
+    assert!(parse_all::<H1Vec, ErrH1Result<_>>(vec!["x"], ok).is_err());
+    assert_eq!(
+        parse_all::<H1Option, ErrH1Result<_>>(Some("1"), ok),
+        Ok(Some(1))
+    );
+    assert_eq!(
+        parse_all::<H1Vec, H1Vec>(vec!["1", "2"], |x| vec![x.len(), 0]),
+        [[1, 1], [1, 0], [0, 1], [0, 0]]
+    );
+    assert_eq!(
+        fp::sequence::<H1Vec, H1Option, _>(vec![Some(1), Some(2)]),
+        Some(vec![1, 2])
+    );
+    assert_eq!(
+        fp::sequence::<ErrH1Result<()>, H1Option, _>(Ok(None::<usize>)),
+        None
+    );
+}
+
+#[test]
+fn traverse_vec_visits_every_element() {
+    let calls = Cell::new(0);
+    let parse = |x: &str| {
+        calls.set(calls.get() + 1);
+
+        x.parse::<usize>()
+    };
+
+    let option: Option<Vec<usize>> =
+        <H1Vec as Traversable<H1Option, _, _>>::traverse(
+            vec!["1", "x", "3"],
+            |x| parse(x).ok(),
+        );
+    assert_eq!(option, None);
+    assert_eq!(calls.replace(0), 3);
+
+    let result: Result<Vec<usize>, _> =
+        <H1Vec as Traversable<ErrH1Result<_>, _, _>>::traverse(
+            vec!["x", "2", "y"],
+            parse,
+        );
+    assert!(result.is_err());
+    assert_eq!(calls.get(), 3);
+}
+
+#[test]
+fn generic_bifunctor() {
+    fn describe<K>(
+        x: <K as PlugType2<usize, ParseIntError>>::T,
+    ) -> <K as PlugType2<String, String>>::T
+    where
+        K: Bifunctor<usize, ParseIntError, String, String>,
+    {
+        K::bimap(x, |x| format!("#{}", x), |err| err.to_string())
+    }
+
+    let err = "x".parse::<usize>().unwrap_err();
+
+    assert_eq!(describe::<H2Result>(Ok(1)), Ok("#1".to_string()));
+    assert_eq!(describe::<H2Result>(Err(err.clone())), Err(err.to_string()));
+    assert_eq!(
+        describe::<H2Tuple>((1, err.clone())),
+        ("#1".to_string(), err.to_string())
+    );
+    assert_eq!(
+        fp::map_right::<H2Result, usize, _, _, _>(Err::<usize, _>(err), |_| ()),
+        Err(())
+    );
+    assert_eq!(
+        fp::map_left::<H2Tuple, _, _, _, _>((1, 2), |x| x + 1),
+        (2, 2)
+    );
+    #[cfg(feature = "either")]
+    {
+        use crate::either::H2Either;
+        use ::either::Either;
+
+        assert_eq!(
+            describe::<H2Either>(Either::Left(1)),
+            Either::Left("#1".to_string())
+        );
+    }
+}
+
+#[test]
+fn generic_contravariant() {
+    fn by_len<K>(fa: <K as PlugType<usize>>::T) -> <K as PlugType<String>>::T
+    where
+        K: Contravariant<usize, String>,
+    {
+        K::contramap(fa, |s: &String| s.len())
+    }
+
+    let is_short = by_len::<H1Predicate>(Box::new(|len| *len < 4));
+    assert!(is_short(&"abc".to_string()));
+    assert!(!is_short(&"abcd".to_string()));
+
+    let cmp = by_len::<H1Comparator>(Box::new(usize::cmp));
+    let mut words = vec!["ccc".to_string(), "a".to_string(), "bb".to_string()];
+    words.sort_by(|x, y| cmp(x, y));
+    assert_eq!(words, vec!["a", "bb", "ccc"]);
+}
+
+#[test]
+fn generic_profunctor() {
+    fn parse_then_show<K>(
+        fab: <K as PlugType2<usize, usize>>::T,
+    ) -> <K as PlugType2<&'static str, String>>::T
+    where
+        K: Profunctor<usize, usize, &'static str, String>,
+    {
+        K::dimap(fab, |s: &str| s.parse().unwrap(), |x: usize| x.to_string())
+    }
+
+    let double = parse_then_show::<H2Fn>(Box::new(|x| x * 2));
+    assert_eq!(double("21"), "42");
+
+    let len = fp::lmap::<H2Fn, _, _, _, _>(
+        Box::new(|x: usize| x + 1) as Box<dyn Fn(usize) -> usize>,
+        |s: String| s.len(),
+    );
+    assert_eq!(len("ab".to_string()), 3);
+    let even = fp::rmap::<H2Fn, _, _, _, _>(
+        Box::new(|x: usize| x + 1) as Box<dyn Fn(usize) -> usize>,
+        |x| x % 2 == 0,
+    );
+    assert!(even(1));
+}
+
+#[test]
+fn generic_monoid() {
+    fn concat<M>(xs: Vec<M>) -> M
+    where
+        M: Monoid,
+    {
+        xs.into_iter().fold(M::empty(), M::combine)
+    }
+
+    assert_eq!(concat(vec!["a".to_string(), "b".to_string()]), "ab");
+    assert_eq!(concat(vec![vec![1], vec![], vec![2, 3]]), vec![1, 2, 3]);
+    assert_eq!(concat(vec![fp::Sum(2), fp::Sum(3)]), fp::Sum(5));
+    assert_eq!(concat::<fp::Product<u8>>(vec![]), fp::Product(1));
+    assert_eq!(
+        concat(vec![Some(fp::Product(2)), None, Some(fp::Product(4))]),
+        Some(fp::Product(8))
+    );
+    assert_eq!(concat::<Option<String>>(vec![None]), None);
+
+    let counts: BTreeMap<_, _> = concat(
+        "abca"
+            .chars()
+            .map(|c| vec![(c, fp::Sum(1))].into_iter().collect())
+            .collect(),
+    );
+    assert_eq!(
+        counts.into_iter().collect::<Vec<_>>(),
+        vec![('a', fp::Sum(2)), ('b', fp::Sum(1)), ('c', fp::Sum(1))]
+    );
+    let words: HashMap<_, _> = concat(vec![
+        vec![(1, "a".to_string())].into_iter().collect(),
+        vec![(1, "b".to_string()), (2, "c".to_string())]
+            .into_iter()
+            .collect(),
+    ]);
+    assert_eq!(words[&1], "ab");
+    assert_eq!(words[&2], "c");
+}
+
+#[test]
+fn generic_alternative() {
+    fn digit<K>(c: char) -> <K as PlugType<u32>>::T
+    where
+        K: Alternative<u32> + Pure<u32>,
+    {
+        match c.to_digit(10) {
+            Some(x) => K::pure(x),
+            None => K::empty(),
+        }
+    }
+
+    fn literal<K>(s: &str) -> <K as PlugType<u32>>::T
+    where
+        K: MonadPlus<u32, u32> + Pure<u32>,
+    {
+        let first = Iterator::next(&mut s.chars()).unwrap_or_default();
+        fp::choice::<K, _, _>(vec![
+            digit::<K>(first),
+            K::bind(digit::<K>(s.chars().last().unwrap_or_default()), |x| {
+                K::pure(x * 10)
+            }),
+            match first {
+                'x' => K::pure(0),
+                _ => K::empty(),
+            },
+        ])
+    }
+
+    assert_eq!(literal::<H1Option>("1a2"), Some(1));
+    assert_eq!(literal::<H1Option>("a2"), Some(20));
+    assert_eq!(literal::<H1Option>("x"), Some(0));
+    assert_eq!(literal::<H1Option>("a"), None);
+    assert_eq!(literal::<H1Vec>("1a2"), vec![1, 20]);
+    assert_eq!(literal::<H1Vec>("x"), vec![0]);
+    assert_eq!(literal::<H1Vec>(""), vec![]);
+}
+
+#[test]
+fn generic_transform() {
+    fn doubled<F, G>(fa: <F as PlugType<usize>>::T) -> <G as PlugType<usize>>::T
+    where
+        F: Functor<usize, usize> + Transform<G, usize>,
+        G: PlugType<usize>,
+    {
+        F::transform(F::fmap(fa, |x| x * 2))
+    }
+
+    assert_eq!(doubled::<H1Vec, H1Option>(vec![1, 2]), Some(2));
+    assert_eq!(doubled::<H1Vec, H1Option>(vec![]), None);
+    assert_eq!(doubled::<H1Option, H1Vec>(Some(3)), vec![6]);
+    assert_eq!(doubled::<ErrH1Result<()>, H1Option>(Err(())), None);
+
+    let rc = Rc::new(vec![1]);
+    let arc: Arc<_> = H1Rc::transform(rc.clone());
+    assert_eq!(*arc, [1]);
+    assert_eq!(*<H1Arc as Transform<H1Rc, _>>::transform(arc), [1]);
+}
+
+#[test]
+fn optics() {
+    struct Settings {
+        retries: Option<Result<u8, String>>,
+    }
+
+    let retries = optics::compose(
+        optics::prism::<Settings, _>(
+            |s| Some(&s.retries),
+            |s| Some(&mut s.retries),
+            |retries| Settings { retries },
+        ),
+        optics::compose(optics::some(), optics::ok()),
+    );
+
+    let mut settings = retries.review(3);
+    assert_eq!(retries.preview(&settings), Some(&3));
+    assert!(Prism::modify(&retries, &mut settings, |x| *x += 1));
+    assert_eq!(settings.retries, Some(Ok(4)));
+    settings.retries = Some(Err("bad".to_string()));
+    assert_eq!(retries.preview(&settings), None);
+    assert!(!Prism::modify(&retries, &mut settings, |x| *x += 1));
+
+    let pair = optics::compose(
+        optics::boxed(),
+        optics::lens::<(u8, String), _>(|x| &x.1, |x| &mut x.1),
+    );
+    let mut boxed = Box::new((1, "a".to_string()));
+    assert_eq!(pair.set(&mut boxed, "b".to_string()), "a");
+    Lens::modify(&pair, &mut boxed, |s| s.push('c'));
+    assert_eq!(pair.get(&boxed), "bc");
+}
+
+#[test]
+fn validated() {
+    fn signup<K>(
+        name: <K as PlugType<String>>::T,
+        age: <K as PlugType<u8>>::T,
+    ) -> <K as PlugType<(String, u8)>>::T
+    where
+        K: Applicative<String, u8, (String, u8)>,
+    {
+        K::map2(name, age, |name, age| (name, age))
+    }
+
+    fn check<T>(x: T, ok: bool, err: &str) -> Validated<Vec<String>, T> {
+        if ok {
+            Validated::Valid(x)
+        } else {
+            Validated::Invalid(vec![err.to_string()])
+        }
+    }
+
+    assert_eq!(
+        signup::<H1Validated<_>>(
+            check("a".to_string(), true, "name"),
+            check(20, true, "age")
+        ),
+        Validated::Valid(("a".to_string(), 20))
+    );
+    assert_eq!(
+        signup::<H1Validated<_>>(
+            check(String::new(), false, "name"),
+            check(200, false, "age")
+        )
+        .into_result(),
+        Err(vec!["name".to_string(), "age".to_string()])
+    );
+    assert_eq!(
+        signup::<ErrH1Result<_>>(Err("name"), Err("age")),
+        Err("name")
+    );
+}
+
+#[test]
+fn kleisli() {
+    fn parse_then_invert<K>(
+        parse: impl FnMut(&'static str) -> <K as PlugType<u32>>::T,
+        invert: impl FnMut(u32) -> <K as PlugType<f64>>::T,
+    ) -> impl FnMut(&'static str) -> <K as PlugType<f64>>::T
+    where
+        K: Monad<u32, f64>,
+    {
+        fp::compose_k::<K, _, _, _, _, _>(parse, invert)
+    }
+
+    let mut checked = parse_then_invert::<H1Option>(
+        |s| s.parse().ok(),
+        |x| if x == 0 { None } else { Some(1.0 / x as f64) },
+    );
+    assert_eq!(checked("4"), Some(0.25));
+    assert_eq!(checked("0"), None);
+    assert_eq!(checked("x"), None);
+
+    let mut all = parse_then_invert::<H1Vec>(
+        |s| s.split(',').map(|x| x.parse().unwrap()).collect(),
+        |x| vec![x as f64, 1.0 / x as f64],
+    );
+    assert_eq!(all("1,2"), vec![1.0, 1.0, 2.0, 0.5]);
+}
+
+#[test]
+fn effects() {
+    fn countdown<K>(
+        n: u32,
+        step: impl Fn(u32) -> <K as PlugType<u32>>::T + Copy,
+    ) -> <K as PlugType<u32>>::T
+    where
+        K: Monad<u32, u32> + Pure<u32>,
+    {
+        if n == 0 {
+            K::pure(0)
+        } else {
+            K::bind(step(n), move |n| countdown::<K>(n - 1, step))
+        }
+    }
+
+    let logged = countdown::<H1Writer<Vec<u32>>>(3, |n| Writer {
+        value: n,
+        log: vec![n],
+    });
+    assert_eq!(logged.run(), (0, vec![3, 2, 1]));
+
+    let counter = State::<u32, ()>::modify(|s| s + 1)
+        .and_then(|()| State::get())
+        .map(|s| s * 10)
+        .and_then(|x| H1State::pure(x + 1));
+    assert_eq!(counter.run(1), (21, 2));
+    assert_eq!(State::put(5).and_then(|()| State::get()).run(0), (5, 5));
+
+    let greeting = Reader::<String, _>::ask()
+        .map(|name| format!("hi {}", name))
+        .and_then(|s| {
+            Reader::new(move |name: &String| format!("{}{}", s, name.len()))
+        });
+    assert_eq!(greeting.run(&"bob".to_string()), "hi bob3");
+    assert_eq!(Writer::tell("x").run(), ((), "x"));
+}
+
+#[test]
+fn state_and_reader_monad_laws() {
+    // The carriers are functions, so the laws are checked by running both
+    // sides instead of with `test_utils::check_monad_laws`
+    fn step(x: u32) -> State<u32, u32> {
+        State::new(move |s| (x + s, s * 2))
+    }
+
+    fn shift(x: u32) -> State<u32, u32> {
+        State::modify(move |s| s + x).map(move |()| x * 3)
+    }
+
+    for s in 0..3 {
+        assert_eq!(H1State::pure(1).and_then(step).run(s), step(1).run(s));
+        assert_eq!(step(1).and_then(H1State::pure).run(s), step(1).run(s));
+        assert_eq!(
+            step(1).and_then(step).and_then(shift).run(s),
+            step(1).and_then(|x| step(x).and_then(shift)).run(s)
+        );
+        assert_eq!(step(1).map(|x| x).run(s), step(1).run(s));
+        assert_eq!(
+            step(1).map(|x| x + 1).map(|x| x * 2).run(s),
+            step(1).map(|x| (x + 1) * 2).run(s)
+        );
+    }
+
+    fn scale(x: u32) -> Reader<u32, u32> {
+        Reader::new(move |r| x * r)
+    }
+
+    fn offset(x: u32) -> Reader<u32, u32> {
+        Reader::new(move |r| x + r)
+    }
+
+    for r in 0..3 {
+        assert_eq!(H1Reader::pure(2).and_then(scale).run(&r), scale(2).run(&r));
+        assert_eq!(scale(2).and_then(H1Reader::pure).run(&r), scale(2).run(&r));
+        assert_eq!(
+            scale(2).and_then(scale).and_then(offset).run(&r),
+            scale(2).and_then(|x| scale(x).and_then(offset)).run(&r)
+        );
+        assert_eq!(
+            scale(2).map(|x| x + 1).map(|x| x * 2).run(&r),
+            scale(2).map(|x| (x + 1) * 2).run(&r)
+        );
+    }
+}
+
+#[test]
+fn type_class_laws() {
+    let inc = |x: usize| x + 1;
+    let double = |x: usize| x * 2;
+
+    test_utils::check_functor_laws::<H1Vec, _>(|| vec![1, 2], inc, double);
+    test_utils::check_functor_laws::<H1Option, _>(|| Some(1), inc, double);
+    test_utils::check_functor_laws::<ErrH1Result<()>, _>(|| Ok(1), inc, double);
+    test_utils::check_functor_laws::<H1Box, _>(|| Box::new(1), inc, double);
+    test_utils::check_functor_laws::<H1Identity, _>(|| 1, inc, double);
+    test_utils::check_functor_laws::<H1Writer<String>, _>(
+        || Writer {
+            value: 1,
+            log: "a".to_string(),
+        },
+        inc,
+        double,
+    );
+
+    test_utils::check_monad_laws::<H1Vec, _>(
+        1,
+        || vec![1, 2],
+        |x| vec![x, x + 1],
+        |x| vec![x * 2],
+    );
+    test_utils::check_monad_laws::<H1Option, _>(
+        1,
+        || Some(2),
+        |x| Some(x + 1),
+        |x| if x > 2 { None } else { Some(x) },
+    );
+    test_utils::check_monad_laws::<ErrH1Result<usize>, _>(
+        1,
+        || Ok(2),
+        |x| Ok(x + 1),
+        Err,
+    );
+    test_utils::check_monad_laws::<H1Box, _>(
+        1,
+        || Box::new(2),
+        |x| Box::new(x + 1),
+        |x| Box::new(x * 2),
+    );
+    test_utils::check_monad_laws::<H1Identity, _>(1, || 2, inc, double);
+    test_utils::check_monad_laws::<H1Writer<Vec<usize>>, _>(
+        1,
+        || Writer {
+            value: 2,
+            log: vec![0],
+        },
+        |x| Writer {
+            value: x + 1,
+            log: vec![x],
+        },
+        |x| Writer {
+            value: x,
+            log: vec![x, x],
+        },
+    );
+
+    test_utils::check_monoid_laws(
+        "a".to_string(),
+        "b".to_string(),
+        "c".to_string(),
+    );
+    test_utils::check_monoid_laws(vec![1], vec![], vec![2, 3]);
+    test_utils::check_monoid_laws(Some(fp::Sum(1)), None, Some(fp::Sum(2)));
+    test_utils::check_monoid_laws(
+        fp::Product(2),
+        fp::Product(3),
+        fp::Product(4),
+    );
+    test_utils::check_monoid_laws(
+        vec![(1, "a".to_string())]
+            .into_iter()
+            .collect::<BTreeMap<_, _>>(),
+        vec![(1, "b".to_string()), (2, "c".to_string())]
+            .into_iter()
+            .collect(),
+        vec![(2, "d".to_string())].into_iter().collect(),
+    );
+}
+
+#[test]
+fn iterator_kinds() {
+    fn lengths<K>(words: <K as PlugType<&'static str>>::T) -> Vec<usize>
+    where
+        K: Functor<&'static str, usize> + Foldable<usize>,
+    {
+        K::to_vec(K::fmap(words, str::len))
+    }
+
+    assert_eq!(
+        lengths::<option::H1OptionIntoIter>(Some("abc").into_iter()),
+        [3]
+    );
+    assert_eq!(lengths::<option::H1OptionIntoIter>(None.into_iter()), []);
+    assert_eq!(lengths::<iter::H1Empty>(std::iter::empty()), []);
+
+    let lens = iter::H1Repeat::fmap(iter::H1Repeat::pure("ab"), str::len);
+    assert_eq!(lens.take(2).collect::<Vec<_>>(), [2, 2]);
+
+    assert_eq!(vec::H1VecIntoIter::to_vec(vec::H1VecIntoIter::pure(1)), [1]);
+    assert_eq!(iter::H1Once::to_vec(iter::H1Once::pure(1)), [1]);
+
+    assert_eq!(iter::H1Once::length(iter::H1Once::pure("a")), 1);
+    assert_eq!(iter::H1Empty::length(std::iter::empty::<u8>()), 0);
+    assert_eq!(
+        iter::H1Once::fold_map(iter::H1Once::pure(2), fp::Sum),
+        fp::Sum(2)
+    );
+}
+
+#[test]
+fn generic_map2_and_zip() {
+    fn sums<K>(
+        xs: impl Fn() -> <K as PlugType<u8>>::T,
+        ys: impl Fn() -> <K as PlugType<u8>>::T,
+    ) -> (<K as PlugType<u8>>::T, <K as PlugType<u8>>::T)
+    where
+        K: Applicative<u8, u8, u8> + Zip<u8, u8, u8>,
+    {
+        (
+            fp::map2::<K, _, _, _, _>(xs(), ys(), |x, y| x + y),
+            fp::zip_with::<K, _, _, _, _>(xs(), ys(), |x, y| x + y),
+        )
+    }
+
+    assert_eq!(
+        sums::<H1Vec>(|| vec![1, 2], || vec![10, 20, 30]),
+        (vec![11, 21, 31, 12, 22, 32], vec![11, 22])
+    );
+    assert_eq!(sums::<H1Option>(|| Some(1), || Some(2)), (Some(3), Some(3)));
+    assert_eq!(sums::<H1Option>(|| Some(1), || None), (None, None));
+    assert_eq!(sums::<ErrH1Result<()>>(|| Ok(1), || Ok(2)), (Ok(3), Ok(3)));
+    assert_eq!(sums::<H1Identity>(|| 1, || 2), (3, 3));
+    assert_eq!(
+        sums::<H1Validated<String>>(
+            || Validated::Invalid("a".to_string()),
+            || Validated::Invalid("b".to_string())
+        )
+        .1,
+        Validated::Invalid("ab".to_string())
+    );
+}
+
+#[test]
+fn collect_option_and_result() {
+    fn parse_all<K>(
+        xs: <K as PlugType<&'static str>>::T,
+    ) -> Result<<K as PlugType<u8>>::T, ParseIntError>
+    where
+        K: Functor<&'static str, Result<u8, ParseIntError>>
+            + Traversable<
+                ErrH1Result<ParseIntError>,
+                Result<u8, ParseIntError>,
+                u8,
+            >,
+    {
+        fp::collect_result::<K, _, _>(K::fmap(xs, str::parse))
+    }
+
+    assert_eq!(parse_all::<H1Vec>(vec!["1", "2"]), Ok(vec![1, 2]));
+    assert!(parse_all::<H1Vec>(vec!["1", "x"]).is_err());
+    assert_eq!(parse_all::<H1Option>(Some("3")), Ok(Some(3)));
+    assert_eq!(parse_all::<H1Option>(None), Ok(None));
+
+    assert_eq!(
+        fp::collect_option::<H1Vec, _>(vec![Some(1), Some(2)]),
+        Some(vec![1, 2])
+    );
+    assert_eq!(fp::collect_option::<H1Vec, u8>(vec![Some(1), None]), None);
+}
+
+#[test]
+fn hlist_of_kinds() {
+    type Kinds = HCons<H1Vec, HCons<H1Option, HCons<H1Box, HNil>>>;
+
+    fn total<Ks, I, J, L>(xs: &<Ks as PlugAll<u8>>::T) -> u8
+    where
+        Ks: PlugAll<u8>,
+        <Ks as PlugAll<u8>>::T: Selector<Vec<u8>, I>
+            + Selector<Option<u8>, J>
+            + Selector<Box<u8>, L>,
+    {
+        Selector::<Vec<u8>, _>::get(xs).iter().sum::<u8>()
+            + Selector::<Option<u8>, _>::get(xs).unwrap_or(0)
+            + **Selector::<Box<u8>, _>::get(xs)
+    }
+
+    let mut xs: <Kinds as PlugAll<u8>>::T =
+        HNil.prepend(Box::new(1)).prepend(None).prepend(vec![2, 3]);
+    assert_eq!(<<Kinds as PlugAll<u8>>::T as HList>::LEN, 3);
+    assert_eq!(total::<Kinds, _, _, _>(&xs), 6);
+    *xs.get_mut() = Some(4);
+    assert_eq!(total::<Kinds, _, _, _>(&xs), 10);
+
+    let any =
+        <<Kinds as PlugAny<u8>>::T as Injector<Option<u8>, _>>::inject(Some(1));
+    let any = Injector::<Vec<u8>, _>::uninject(any).unwrap_err();
+    assert_eq!(Injector::<Option<u8>, _>::uninject(any), Ok(Some(1)));
+}
+
+#[test]
+fn custom_rcb_without_weak() {
+    // A handle that deliberately doesn't expose weak pointers, like the ones
+    // handed out by arenas
+    struct Handle<T>(Rc<T>);
+
+    impl<T> Clone for Handle<T> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+
+    impl<T> Deref for Handle<T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            &self.0
+        }
+    }
+
+    impl<T> Rcb<T> for Handle<T> {
+        fn new(x: T) -> Self {
+            Self(Rc::new(x))
+        }
+
+        fn try_unwrap(this: Self) -> Result<T, Self> {
+            Rc::try_unwrap(this.0).map_err(Self)
+        }
+
+        fn into_raw(this: Self) -> *const T {
+            Rc::into_raw(this.0)
+        }
+
+        unsafe fn from_raw(ptr: *const T) -> Self {
+            Self(Rc::from_raw(ptr))
+        }
+    }
+
+    fn share_and_unwrap<R>(x: usize) -> (usize, Option<usize>)
+    where
+        R: Rcb<usize>,
+    {
+        let x = R::new(x);
+        let y = x.clone();
+        let shared = R::try_unwrap(y).ok();
+
+        (*x, shared.or_else(|| R::try_unwrap(x).ok()))
+    }
+
+    assert_eq!(share_and_unwrap::<Handle<usize>>(42), (42, Some(42)));
+    assert_eq!(share_and_unwrap::<Rc<usize>>(42), (42, Some(42)));
+    #[cfg(feature = "allocator_api")]
+    assert_eq!(*<Handle<usize> as Rcb<_>>::try_new(42).unwrap(), 42);
+}
+
+#[test]
+fn rcb_raw_round_trip() {
+    fn round_trip<R>(x: R) -> R
+    where
+        R: Rcb<String>,
+    {
+        unsafe { R::from_raw(R::into_raw(x)) }
+    }
+
+    let rc = Rc::new("rc".to_string());
+    let copy = round_trip(rc.clone());
+    assert_eq!(*copy, "rc");
+    assert_eq!(Rc::strong_count(&rc), 2);
+    drop(copy);
+    assert_eq!(Rc::strong_count(&rc), 1);
+
+    let arc = Arc::new("arc".to_string());
+    let copy = round_trip(arc.clone());
+    assert_eq!(*copy, "arc");
+    assert_eq!(Arc::strong_count(&arc), 2);
+    drop(copy);
+    assert_eq!(Arc::strong_count(&arc), 1);
+}
+
+#[cfg(feature = "allocator_api")]
+#[test]
+fn rcb_with_custom_allocator() {
+    use std::{
+        alloc::{Allocator, Global, Layout},
+        ptr::NonNull,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Clone, Default)]
+    struct CountingAllocator;
+
+    unsafe impl Allocator for CountingAllocator {
+        fn allocate(
+            &self,
+            layout: Layout,
+        ) -> Result<NonNull<[u8]>, AllocError> {
+            ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+
+            Global.allocate(layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            Global.deallocate(ptr, layout)
+        }
+    }
+
+    fn roundtrip<R>(x: usize) -> usize
+    where
+        R: Rcb<usize>,
+    {
+        let x = R::try_new(x).unwrap();
+        let x = unsafe { R::from_raw(R::into_raw(x)) };
+
+        R::unwrap_or_clone(x)
+    }
+
+    assert_eq!(roundtrip::<Rc<usize, CountingAllocator>>(42), 42);
+    assert_eq!(roundtrip::<Arc<usize, CountingAllocator>>(42), 42);
+    assert_eq!(ALLOCATIONS.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn struct_using_either_rc_or_arc_with_hkt() {
+    struct StructWithReferenceCount<R>
+    where
+        R: PlugType<String> + PlugType<usize>,
+    {
+        secret1: <R as PlugType<String>>::T,
+        secret2: <R as PlugType<usize>>::T,
+    }
+
+    impl<R> StructWithReferenceCount<R>
+    where
+        R: PlugType<String> + PlugType<usize>,
+        <R as PlugType<String>>::T: Rcb<String>,
+        <R as PlugType<usize>>::T: Rcb<usize>,
+    {
+        fn new() -> Self {
+            Self {
+                secret1: <R as PlugType<String>>::T::new("xpotato".to_string()),
+                secret2: <R as PlugType<usize>>::T::new(42),
+            }
+        }
+
+        fn secrets(&self) -> (&str, usize) {
+            (self.secret1.as_str(), *self.secret2)
+        }
+    }
+
+    // Autoderive doesn't work in this case
+    impl<R> Clone for StructWithReferenceCount<R>
+    where
+        R: PlugType<String> + PlugType<usize>,
+        <R as PlugType<String>>::T: Rcb<String>,
+        <R as PlugType<usize>>::T: Rcb<usize>,
+    {
+        fn clone(&self) -> Self {
+            Self {
+                secret1: self.secret1.clone(),
+                secret2: self.secret2.clone(),
+            }
+        }
+    }
+
+    assert_eq!(
+        StructWithReferenceCount::<H1Rc>::new().clone().secrets(),
+        ("xpotato", 42)
+    );
+    assert_eq!(
+        StructWithReferenceCount::<H1Arc>::new().clone().secrets(),
+        ("xpotato", 42)
+    );
+}
+
+#[test]
+fn struct_using_pointer_family() {
+    struct StructWithObserver<P>
+    where
+        P: PointerFamily<String>,
+    {
+        owner: Option<P::Strong>,
+        observer: P::Weak,
+    }
+
+    impl<P> StructWithObserver<P>
+    where
+        P: PointerFamily<String>,
+    {
+        fn new() -> Self {
+            let owner = P::Strong::new("xpotato".to_string());
+            let observer = P::Strong::downgrade(&owner);
+
+            Self {
+                owner: Some(owner),
+                observer,
+            }
+        }
+
+        fn observe(&self) -> Option<String> {
+            self.observer.upgrade().map(|x| x.to_string())
+        }
+
+        fn drop_owner(&mut self) {
+            self.owner = None;
+        }
+    }
+
+    let mut rc = StructWithObserver::<RcFamily>::new();
+    let mut arc = StructWithObserver::<ArcFamily>::new();
+
+    assert_eq!(rc.observe().as_deref(), Some("xpotato"));
+    assert_eq!(arc.observe().as_deref(), Some("xpotato"));
+
+    rc.drop_owner();
+    arc.drop_owner();
+
+    assert_eq!(rc.observe(), None);
+    assert_eq!(arc.observe(), None);
+}
+
+#[test]
+fn weak_rcb_counts() {
+    fn check<P>()
+    where
+        P: PointerFamily<usize>,
+    {
+        let strong = P::Strong::new(42);
+        let other_strong = strong.clone();
+        let weak = P::Strong::downgrade(&strong);
+        let other_weak = weak.clone();
+
+        assert_eq!(weak.strong_count(), 2);
+        assert_eq!(weak.weak_count(), 2);
+
+        drop(strong);
+        drop(other_weak);
+        assert_eq!(weak.strong_count(), 1);
+        assert_eq!(weak.weak_count(), 1);
+
+        drop(other_strong);
+        assert_eq!(weak.strong_count(), 0);
+        assert_eq!(weak.weak_count(), 0);
+    }
+
+    check::<RcFamily>();
+    check::<ArcFamily>();
+}
+
+#[test]
+fn doubly_linked_list() {
+    fn check<P>()
+    where
+        P: PointerFamily<ListNode<usize, P>>,
+    {
+        let mut list = DoublyLinkedList::<usize, P>::new();
+        list.push_back(2);
+        list.push_front(1);
+        list.push_back(3);
+
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.front(), Some(&1));
+        assert_eq!(list.back(), Some(&3));
+
+        let mut iter = list.iter();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), None);
+
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.pop_back(), Some(2));
+        assert_eq!(list.pop_front(), None);
+        assert!(list.is_empty());
+
+        for x in 0..100_000 {
+            list.push_back(x);
+        }
+    }
+
+    fn is_send_and_sync<T: Send + Sync>() {}
+
+    check::<RcFamily>();
+    check::<ArcFamily>();
+    is_send_and_sync::<DoublyLinkedList<usize, ArcFamily>>();
+}
+
+#[test]
+fn tree() {
+    fn values<P>(nodes: Vec<Tree<&'static str, P>>) -> Vec<&'static str>
+    where
+        P: PointerFamily<TreeNode<&'static str, P>>,
+    {
+        nodes.iter().map(|x| *x.value()).collect()
+    }
+
+    fn check<P>()
+    where
+        P: PointerFamily<TreeNode<&'static str, P>>,
+    {
+        let leaf = Tree::<_, P>::new("leaf");
+        let branch = Tree::with_children("branch", vec![leaf.clone()]);
+        let root = Tree::with_children("root", vec![branch.clone()]);
+
+        assert!(root.parent().is_none());
+        assert!(branch.parent().unwrap().ptr_eq(&root));
+        assert!(leaf.parent().unwrap().ptr_eq(&branch));
+        assert_eq!(values(root.children()), vec!["branch"]);
+
+        root.append(leaf.clone());
+        assert!(leaf.parent().unwrap().ptr_eq(&root));
+        assert_eq!(values(root.children()), vec!["branch", "leaf"]);
+        assert_eq!(values(branch.children()), Vec::<&str>::new());
+
+        leaf.detach();
+        assert!(leaf.parent().is_none());
+        assert_eq!(values(root.children()), vec!["branch"]);
+
+        drop(root);
+        assert!(branch.parent().is_none());
+    }
+
+    check::<RcFamily>();
+    check::<ArcFamily>();
+}
+
+#[test]
+fn convert_rc_structures_to_arc() {
+    let mut list = DoublyLinkedList::<_, RcFamily>::new();
+    list.push_back(1);
+    list.push_back(2);
+    let tree = Tree::<_, RcFamily>::with_children(
+        "root",
+        vec![Tree::new("left"), Tree::new("right")],
+    );
+    let rc = Rc::new(42);
+
+    let list: DoublyLinkedList<_, ArcFamily> = list.convert_rcb();
+    let tree: Tree<_, ArcFamily> = tree.convert_rcb();
+    let arc: Arc<_> = ConvertRcb::<ArcFamily>::convert_rcb(&rc);
+    let tree = thread::spawn(move || {
+        assert_eq!(list.front(), Some(&1));
+        assert_eq!(list.back(), Some(&2));
+        assert_eq!(*arc, 42);
+
+        tree
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(*tree.value(), "root");
+    assert_eq!(tree.children().len(), 2);
+    assert!(tree.children()[1].parent().unwrap().ptr_eq(&tree));
+    assert_eq!(*convert_rcb::<_, _, Arc<_>>(&rc), 42);
+}
+
+#[test]
+fn cyclic_struct_using_pointer_family() {
+    struct SelfAware<P>
+    where
+        P: PointerFamily<SelfAware<P>>,
+    {
+        this: P::Weak,
+        value: usize,
+    }
+
+    fn check<P>()
+    where
+        P: PointerFamily<SelfAware<P>>,
+    {
+        let x = P::Strong::new_cyclic(|this| SelfAware {
+            this: this.clone(),
+            value: 42,
+        });
+
+        assert_eq!(x.this.upgrade().map(|x| x.value), Some(42));
+        assert_eq!(P::Weak::new().upgrade().map(|x| x.value), None);
+    }
+
+    check::<RcFamily>();
+    check::<ArcFamily>();
+}
+
+#[test]
+fn unwrap_or_clone_using_either_rc_or_arc() {
+    fn append_potato<R>(x: R) -> String
+    where
+        R: Rcb<String>,
+    {
+        let mut x = R::unwrap_or_clone(x);
+        x.push_str("potato");
+
+        x
+    }
+
+    let unique = Rc::new("x".to_string());
+    let shared = Arc::new("x".to_string());
+    let _other = shared.clone();
+
+    assert_eq!(append_potato(unique), "xpotato");
+    assert_eq!(append_potato(shared.clone()), "xpotato");
+    assert_eq!(*shared, "x");
+}
+
+#[test]
+fn ptr_over_owning_and_borrowing_handles() {
+    fn shout<P>(x: P) -> String
+    where
+        P: Ptr<str>,
+    {
+        x.to_uppercase()
+    }
+
+    fn new_and_shout<P>() -> String
+    where
+        P: NewPtr<String>,
+    {
+        shout(P::new("xpotato".to_string()).as_str())
+    }
+
+    assert_eq!(shout("xpotato"), "XPOTATO");
+    assert_eq!(shout(Box::<str>::from("xpotato")), "XPOTATO");
+    assert_eq!(shout(Rc::<str>::from("xpotato")), "XPOTATO");
+    assert_eq!(shout(Arc::<str>::from("xpotato")), "XPOTATO");
+    assert_eq!(new_and_shout::<Box<String>>(), "XPOTATO");
+    assert_eq!(new_and_shout::<Rc<String>>(), "XPOTATO");
+    assert_eq!(new_and_shout::<Arc<String>>(), "XPOTATO");
+}
+
+#[test]
+fn struct_using_either_box_or_rc_with_hkt() {
+    struct StructWithPointers<P>
+    where
+        P: PlugType<str> + PlugType<[usize]>,
+    {
+        name: <P as PlugType<str>>::T,
+        values: <P as PlugType<[usize]>>::T,
+    }
+
+    impl<P> StructWithPointers<P>
+    where
+        P: PlugType<str> + PlugType<[usize]>,
+        <P as PlugType<str>>::T: Ptr<str> + for<'a> From<&'a str>,
+        <P as PlugType<[usize]>>::T: Ptr<[usize]> + for<'a> From<&'a [usize]>,
+    {
+        fn new() -> Self {
+            Self {
+                name: "xpotato".into(),
+                values: [1, 2, 3].as_ref().into(),
+            }
+        }
+
+        fn summary(&self) -> (&str, usize) {
+            (&self.name, self.values.iter().sum())
+        }
+    }
+
+    assert_eq!(StructWithPointers::<H1Box>::new().summary(), ("xpotato", 6));
+    assert_eq!(StructWithPointers::<H1Rc>::new().summary(), ("xpotato", 6));
+    assert_eq!(StructWithPointers::<H1Arc>::new().summary(), ("xpotato", 6));
+}
+
+#[test]
+fn struct_using_cow_with_hkt() {
+    type H1StaticCow = <H2Cow as PlugLifetime<'static>>::T;
+
+    let owned: <H1StaticCow as PlugType<str>>::T =
+        Cow::Owned("xpotato".to_string());
+    let borrowed: <H1StaticCow as PlugType<str>>::T = Cow::Borrowed("xpotato");
+
+    assert_eq!(owned, borrowed);
+}
+
+#[test]
+fn struct_with_maybe_present_field() {
+    struct Request<M>
+    where
+        M: PlugType<String>,
+    {
+        path: String,
+        body: <M as PlugType<String>>::T,
+    }
+
+    let draft = Request::<H1Option> {
+        path: "/xpotato".to_string(),
+        body: None,
+    };
+    let complete = Request::<H1Identity> {
+        path: draft.path,
+        body: draft.body.unwrap_or_else(|| "xpotato".to_string()),
+    };
+
+    assert_eq!(complete.path, "/xpotato");
+    assert_eq!(complete.body, "xpotato");
+}
+
+#[test]
+fn fallible_builder_with_hkt() {
+    struct Builder<R>
+    where
+        R: PlugType<usize>,
+    {
+        parse: fn(&str) -> <R as PlugType<usize>>::T,
+    }
+
+    type ParseResult<E> = <<H2Result as PlugType<usize>>::T as PlugType<E>>::T;
+
+    let strict = Builder::<ErrH1Result<ParseIntError>> {
+        parse: |x| x.parse(),
+    };
+    let lenient = Builder::<H1Option> {
+        parse: |x| x.parse().ok(),
+    };
+    let parsed: ParseResult<ParseIntError> = (strict.parse)("42");
+
+    assert_eq!(parsed, Ok(42));
+    assert!((strict.parse)("xpotato").is_err());
+    assert_eq!((lenient.parse)("xpotato"), None);
+}
+
+#[cfg(feature = "either")]
+#[test]
+fn sequence_passthrough_for_either() {
+    use crate::{Sequence, SequenceMut};
+    use ::either::Either;
+
+    fn sum<S>(x: &S) -> usize
+    where
+        S: Sequence<usize>,
+    {
+        (0..x.len()).filter_map(|i| x.get(i)).sum()
+    }
+
+    let mut left: Either<Vec<usize>, Vec<usize>> = Either::Left(vec![1, 2]);
+    let right: Either<Vec<usize>, Vec<usize>> = Either::Right(vec![3]);
+    left.push(3);
+
+    assert_eq!(left.len(), 3);
+    assert_eq!(left.last(), Some(&3));
+    assert_eq!(sum(&left), 6);
+    assert_eq!(sum(&right), 3);
+
+    let mut iter = left.iter();
+    assert_eq!(StreamingIterator::next(&mut iter), Some(&1));
+    assert_eq!(StreamingIterator::next(&mut iter), Some(&2));
+    assert_eq!(StreamingIterator::next(&mut iter), Some(&3));
+    assert_eq!(StreamingIterator::next(&mut iter), None);
+}
+
+#[cfg(feature = "bitvec")]
+#[test]
+fn sequence_of_packed_bits() {
+    use crate::{Sequence, SequenceMut, WithCapacity};
+    use ::bitvec::{order::Lsb0, vec::BitVec};
+
+    fn count_set<S>(x: &S) -> usize
+    where
+        S: Sequence<bool>,
+    {
+        (0..x.len()).filter(|&i| x.get(i) == Some(&true)).count()
+    }
+
+    let mut bits = <BitVec<u8, Lsb0> as WithCapacity>::with_capacity(16);
+    for i in 0..10 {
+        SequenceMut::push(&mut bits, i % 3 == 0);
+    }
+    SequenceMut::insert(&mut bits, 1, true);
+
+    assert_eq!(Sequence::len(&bits), 11);
+    assert_eq!(count_set(&bits), 5);
+    assert_eq!(Sequence::first(&bits), Some(&true));
+    assert_eq!(Sequence::last(&bits), Some(&true));
+    assert_eq!(Sequence::get(&bits, 11), None);
+    assert!(Sequence::contains(&bits, &false));
+    assert!(SequenceMut::remove(&mut bits, 1));
+    assert_eq!(SequenceMut::pop(&mut bits), Some(true));
+
+    let mut iter = Sequence::iter(&bits);
+    assert_eq!(StreamingIterator::next(&mut iter).map(|b| *b), Some(true));
+    assert_eq!(StreamingIterator::next(&mut iter).map(|b| *b), Some(false));
+
+    SequenceMut::clear(&mut bits);
+    assert!(!Sequence::contains(&bits, &false));
+    assert_eq!(Sequence::last(&bits), None);
+}
+
+#[test]
+fn owning_ptr() {
+    fn push_and_unwrap<P>(x: Vec<usize>) -> Vec<usize>
+    where
+        P: OwningPtr<Vec<usize>>,
+    {
+        let mut x = P::new(x);
+        x.push(42);
+
+        P::into_inner(x)
+    }
+
+    assert_eq!(push_and_unwrap::<Box<_>>(vec![1]), vec![1, 42]);
+}
+
+#[test]
+fn struct_using_any_cow_like() {
+    #[derive(Clone)]
+    struct Config<C> {
+        name: C,
+    }
+
+    impl<C> Config<C>
+    where
+        C: CowLike<String> + Clone,
+    {
+        fn rename(&self, suffix: &str) -> Self {
+            let mut renamed = self.clone();
+            renamed.name.to_mut().push_str(suffix);
+
+            renamed
+        }
+    }
+
+    fn check<C>()
+    where
+        C: CowLike<String> + Clone,
+    {
+        let config = Config {
+            name: C::from_owned("x".to_string()),
+        };
+        let renamed = config.rename("potato");
+
+        assert_eq!(config.name.as_str(), "x");
+        assert_eq!(renamed.name.into_owned(), "xpotato");
+    }
+
+    check::<Cow<'static, String>>();
+    check::<Rc<String>>();
+    check::<Arc<String>>();
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn derived_sequence_delegation() {
+    use crate::{Sequence, SequenceMut, WithCapacity};
+
+    #[derive(Sequence, SequenceMut, WithCapacity)]
+    struct Samples(Vec<usize>);
+
+    #[derive(Sequence, SequenceMut, WithCapacity)]
+    struct Tagged<T> {
+        tag: &'static str,
+        #[delegate]
+        items: Vec<T>,
+    }
+
+    fn sum<S>(x: &S) -> usize
+    where
+        S: Sequence<usize>,
+    {
+        (0..x.len()).filter_map(|i| x.get(i)).sum()
+    }
+
+    let mut samples = Samples::with_capacity(4);
+    samples.push(1);
+    samples.push(2);
+    samples.insert(0, 3);
+    assert!(SequenceMut::capacity(&samples) >= 4);
+    assert_eq!(sum(&samples), 6);
+    assert_eq!(samples.first(), Some(&3));
+    assert_eq!(samples.pop(), Some(2));
+
+    let mut iter = samples.iter();
+    assert_eq!(StreamingIterator::next(&mut iter), Some(&3));
+    assert_eq!(StreamingIterator::next(&mut iter), Some(&1));
+    assert_eq!(StreamingIterator::next(&mut iter), None);
+
+    let mut tagged = Tagged::<usize>::with_capacity(2);
+    tagged.push(42);
+    assert_eq!(tagged.tag, "");
+    assert!(tagged.contains(&42));
+    assert_eq!(tagged.remove(0), 42);
+    assert!(tagged.is_empty());
+}
+
+#[test]
+fn delegated_wrappers() {
+    use crate::{
+        lock::{Lock, RwLockLike, ScopedLock},
+        Capacity, MapFactory, Rcb, Sequence, SequenceMut, WithCapacity,
+    };
+    use std::{collections::hash_map::RandomState, sync::RwLock};
+
+    struct Samples(Vec<usize>);
+
+    delegate_generic_std!(
+        Samples => self.0: Vec<usize>;
+        Sequence, SequenceMut, WithCapacity, Capacity,
+    );
+
+    struct Shared<T> {
+        inner: RwLock<T>,
+    }
+
+    delegate_generic_std!(
+        Shared<T> => self.inner: RwLock<T>;
+        Lock, ScopedLock, RwLockLike,
+    );
+
+    #[derive(Clone)]
+    struct Handle<T>(Rc<T>);
+
+    impl<T> Deref for Handle<T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            &self.0
+        }
+    }
+
+    delegate_generic_std!(Handle<T> => self.0: Rc<T>; Rcb);
+
+    struct Index<S>(HashMap<String, usize, S>);
+
+    delegate_generic_std!(
+        Index<S> => self.0: HashMap<String, usize, S>; MapFactory
+    );
+
+    let mut samples = Samples::with_capacity(4);
+    samples.push(1);
+    samples.push(2);
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples.last(), Some(&2));
+    assert!(Capacity::capacity(&samples) >= 4);
+
+    let shared = <Shared<usize> as Lock<_>>::new(1);
+    *shared.write().unwrap() += 1;
+    shared.with_mut(&mut |x| *x *= 21);
+    assert_eq!(*shared.read().unwrap(), 42);
+
+    let handle = <Handle<usize> as Rcb<_>>::new(42);
+    let other = handle.clone();
+    assert!(Rcb::try_unwrap(other).is_err());
+    assert_eq!(Rcb::try_unwrap(handle).ok(), Some(42));
+
+    let index = Index::with_capacity_and_hasher(8, RandomState::new());
+    assert!(index.0.capacity() >= 8);
+}
+
+#[test]
+fn preallocated_collections() {
+    use crate::{Capacity, WithCapacity};
+    use rustc_hash::FxBuildHasher;
+    use std::collections::{BinaryHeap, HashSet, VecDeque};
+
+    fn check<C>()
+    where
+        C: WithCapacity + Capacity,
+    {
+        assert!(C::with_capacity(16).capacity() >= 16);
+    }
+
+    check::<Vec<usize>>();
+    check::<String>();
+    check::<VecDeque<usize>>();
+    check::<BinaryHeap<usize>>();
+    check::<HashMap<usize, usize>>();
+    check::<HashMap<usize, usize, FxBuildHasher>>();
+    check::<HashSet<usize>>();
+    #[cfg(feature = "bitvec")]
+    check::<::bitvec::vec::BitVec>();
+}
+
+#[test]
+fn maps_using_any_hasher() {
+    use crate::MapFactory;
+    use rustc_hash::FxBuildHasher;
+    use std::{
+        collections::{hash_map::RandomState, HashSet},
+        hash::BuildHasher,
+    };
+
+    fn index_words<M, S>(s: &str, hasher: S) -> M
+    where
+        M: MapFactory<S> + Extend<(String, usize)>,
+        S: BuildHasher,
+    {
+        let mut index = M::with_capacity_and_hasher(8, hasher);
+        index.extend(s.split_whitespace().map(str::to_owned).zip(0..));
+
+        index
+    }
+
+    fn check<M, S>(hasher: S)
+    where
+        M: MapFactory<S>
+            + Extend<(String, usize)>
+            + IntoIterator<Item = (String, usize)>,
+        S: BuildHasher,
+    {
+        let index = index_words::<M, S>("a b a c a b", hasher)
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+
+        assert_eq!(index.len(), 3);
+        assert_eq!(index["a"], 4);
+        assert_eq!(index["b"], 5);
+        assert_eq!(index["c"], 3);
+    }
+
+    check::<HashMap<_, _, _>, _>(RandomState::new());
+    check::<HashMap<_, _, _>, _>(FxBuildHasher);
+    #[cfg(feature = "ahash")]
+    check::<ahash::AHashMap<_, _, _>, _>(ahash::RandomState::new());
+
+    let set = <HashSet<usize, _> as MapFactory<_>>::with_capacity_and_hasher(
+        4,
+        FxBuildHasher,
+    );
+    let _: &FxBuildHasher = MapFactory::hasher(&set);
+    assert!(set.capacity() >= 4);
+}
+
+#[test]
+fn heterogeneous_collections_behind_trait_objects() {
+    use crate::erased::{DynMap, DynSequenceMut};
+    use std::collections::{BTreeMap, VecDeque};
+
+    let mut sequences: Vec<Box<dyn DynSequenceMut<usize>>> = vec![
+        Box::new(vec![1, 2, 3]),
+        Box::new(VecDeque::from(vec![4, 5])),
+    ];
+    for sequence in &mut sequences {
+        sequence.insert(0, 0);
+        sequence.push(6);
+    }
+    let contents = sequences
+        .iter()
+        .map(|s| s.iter().copied().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(contents, [vec![0, 1, 2, 3, 6], vec![0, 4, 5, 6]]);
+    assert_eq!(sequences[1].remove(1), 4);
+    assert_eq!(sequences[1].first(), Some(&0));
+    assert_eq!(sequences[1].last(), Some(&6));
+    assert!(sequences[0].contains(&3));
+
+    let mut registry: Vec<Box<dyn DynMap<&str, usize>>> = vec![
+        Box::new(HashMap::new()),
+        Box::new(BTreeMap::new()),
+        #[cfg(feature = "ahash")]
+        Box::new(ahash::AHashMap::new()),
+    ];
+    for map in &mut registry {
+        assert_eq!(map.insert("b", 2), None);
+        assert_eq!(map.insert("a", 1), None);
+        assert_eq!(map.insert("a", 3), Some(1));
+        *map.get_mut(&"b").unwrap() += 10;
+        let mut entries = map.iter().collect::<Vec<_>>();
+        entries.sort();
+        assert_eq!(entries, [(&"a", &3), (&"b", &12)]);
+        assert_eq!(map.remove(&"a"), Some(3));
+        assert!(!map.contains_key(&"a"));
+        assert_eq!(map.len(), 1);
+    }
+    assert_eq!(registry[1].keys().collect::<Vec<_>>(), [&"b"]);
+}
+
+#[test]
+fn generic_owned_and_borrowed_views() {
+    use crate::PlugBorrow;
+    use std::path::{Path, PathBuf};
+
+    fn roundtrip<O>(x: &O) -> O
+    where
+        O: PlugBorrow,
+    {
+        O::to_owned(x.borrow())
+    }
+
+    assert_eq!(roundtrip(&String::from("potato")), "potato");
+    assert_eq!(roundtrip(&vec![1, 2, 3]), [1, 2, 3]);
+    assert_eq!(
+        roundtrip(&PathBuf::from("/tmp/potato")),
+        Path::new("/tmp/potato"),
+    );
+    assert_eq!(PlugBorrow::borrow(&vec![1, 2]), &[1, 2]);
+}
+
+#[test]
+fn struct_owning_or_borrowing_its_fields() {
+    use crate::{reference::H1Reference, MaybeOwned};
+
+    struct Token<K>
+    where
+        K: MaybeOwned<String>,
+    {
+        text: <K as PlugType<String>>::T,
+    }
+
+    impl<K> Token<K>
+    where
+        K: MaybeOwned<String>,
+    {
+        fn len(&self) -> usize {
+            K::get(&self.text).len()
+        }
+
+        fn into_owned(self) -> Token<H1Identity> {
+            Token {
+                text: K::into_owned(self.text),
+            }
+        }
+    }
+
+    let source = String::from("potato");
+    let borrowed = Token::<H1Reference<'_>> { text: &source };
+    let owned = Token::<H1Identity> {
+        text: String::from("tomato"),
+    };
+
+    assert_eq!(borrowed.len(), 6);
+    assert_eq!(owned.len(), 6);
+    assert_eq!(borrowed.into_owned().text, "potato");
+    assert_eq!(owned.into_owned().text, "tomato");
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn derived_std_traits_for_hkt_structs() {
+    use crate::{
+        reference::H1Reference, HktClone, HktDebug, HktPartialEq, MaybeOwned,
+    };
+
+    #[derive(HktClone, HktDebug, HktPartialEq)]
+    struct Token<K>
+    where
+        K: MaybeOwned<String>,
+    {
+        text: <K as PlugType<String>>::T,
+        line: usize,
+    }
+
+    #[derive(HktClone, HktDebug, HktPartialEq)]
+    enum Node<K>
+    where
+        K: MaybeOwned<String>,
+    {
+        Leaf(Token<K>),
+        Pair(Box<Node<K>>, Box<Node<K>>),
+        Empty,
+    }
+
+    let source = String::from("potato");
+    let borrowed = Token::<H1Reference<'_>> {
+        text: &source,
+        line: 1,
+    };
+    let owned = Token::<H1Identity> {
+        text: String::from("potato"),
+        line: 1,
+    };
+
+    assert_eq!(borrowed.clone(), borrowed);
+    assert_eq!(owned.clone(), owned);
+    assert_eq!(
+        format!("{:?}", borrowed),
+        r#"Token { text: "potato", line: 1 }"#,
+    );
+
+    let tree = Node::Pair(Box::new(Node::Leaf(owned)), Box::new(Node::Empty));
+    assert_eq!(tree.clone(), tree);
+    assert_ne!(tree, Node::Empty);
+    assert_eq!(
+        format!("{:?}", tree),
+        r#"Pair(Leaf(Token { text: "potato", line: 1 }), Empty)"#,
+    );
+
+    // Recursive through an HKT projection, so the projection itself must be
+    // bounded. Forms whose impls don't require `Tree<K>` to implement the
+    // trait again, such as `Rc<Vec<_>>` for `Clone`, satisfy the bound
+    #[derive(HktClone, HktDebug, HktPartialEq)]
+    struct Tree<K>
+    where
+        K: PlugType<Tree<K>>,
+    {
+        value: u32,
+        children: <K as PlugType<Tree<K>>>::T,
+    }
+
+    struct H1SharedVec;
+
+    impl<T> PlugType<T> for H1SharedVec {
+        type T = Rc<Vec<T>>;
+    }
+
+    let tree = Tree::<H1SharedVec> {
+        value: 1,
+        children: Rc::new(vec![Tree {
+            value: 2,
+            children: Rc::new(Vec::new()),
+        }]),
+    };
+    let copy = tree.clone();
+    assert_eq!(copy.children[0].value, 2);
+    assert!(Rc::ptr_eq(&copy.children, &tree.children));
+
+    let flat = Tree::<H0<Vec<u32>>> {
+        value: 1,
+        children: vec![2, 3],
+    };
+    assert_eq!(flat.clone(), flat);
+    assert_eq!(format!("{:?}", flat), "Tree { value: 1, children: [2, 3] }");
+}
+
+#[test]
+fn struct_using_any_stringy() {
+    #[derive(Clone)]
+    struct User<S> {
+        name: S,
+        email: S,
+    }
+
+    impl<S> User<S>
+    where
+        S: Stringy,
+    {
+        fn new(name: &str, email: String) -> Self {
+            User {
+                name: S::new(name),
+                email: S::from_string(email),
+            }
+        }
+
+        fn display(&self) -> String {
+            format!("{} <{}>", self.name.as_str(), &*self.email)
+        }
+    }
+
+    fn check<S>()
+    where
+        S: Stringy,
+    {
+        let user = User::<S>::new("potato", "potato@example.com".to_string());
+        assert_eq!(user.clone().display(), "potato <potato@example.com>");
+        assert_eq!(user.name.into_string(), "potato");
+    }
+
+    check::<String>();
+    check::<Box<str>>();
+    check::<Rc<str>>();
+    check::<Arc<str>>();
+    check::<Cow<'static, str>>();
+}
+
+#[test]
+fn struct_using_any_string_builder() {
+    fn join<S>(words: &[&str]) -> S
+    where
+        S: StringBuilder,
+    {
+        let mut out = S::default();
+        for word in words {
+            if !out.is_empty() {
+                out.push(',');
+            }
+            out.push_str(word);
+        }
+
+        out
+    }
+
+    fn check<S>()
+    where
+        S: StringBuilder,
+    {
+        let mut joined = join::<S>(&["a", "bc"]);
+        assert_eq!(joined.as_str(), "a,bc");
+        joined.clear();
+        assert!(joined.is_empty());
+    }
+
+    check::<String>();
+    #[cfg(feature = "arrayvec")]
+    check::<arrayvec::ArrayString<8>>();
+    #[cfg(feature = "smartstring")]
+    check::<smartstring::alias::String>();
+}
+
+#[test]
+fn struct_using_any_byte_buf() {
+    // Length-prefixed frames
+    struct Encoder<B> {
+        buf: B,
+    }
+
+    impl<B> Encoder<B>
+    where
+        B: ByteBuf,
+    {
+        fn encode(&mut self, payload: &[u8]) {
+            self.buf.extend_from_slice(&[payload.len() as u8]);
+            self.buf.extend_from_slice(payload);
+        }
+
+        fn next_frame(&mut self) -> Option<B> {
+            let len = usize::from(*self.buf.as_slice().first()?);
+            let mut frame = self.buf.split_to(len + 1);
+            frame.as_mut_slice().rotate_left(1);
+            frame.truncate(len);
+
+            Some(frame)
+        }
+    }
+
+    fn check<B>()
+    where
+        B: ByteBuf,
+    {
+        let mut encoder = Encoder { buf: B::default() };
+        encoder.encode(b"ab");
+        encoder.encode(b"c");
+        assert_eq!(encoder.next_frame().as_deref(), Some(&b"ab"[..]));
+        assert_eq!(encoder.next_frame().as_deref(), Some(&b"c"[..]));
+        assert!(encoder.next_frame().is_none());
+
+        encoder.encode(b"xyz");
+        assert_eq!(&*encoder.buf.split_off(2), b"yz");
+        encoder.buf.clear();
+        assert!(encoder.buf.is_empty());
+    }
+
+    check::<Vec<u8>>();
+    #[cfg(feature = "bytes")]
+    check::<bytes::BytesMut>();
+    #[cfg(feature = "smallvec")]
+    check::<smallvec::SmallVec<[u8; 4]>>();
+}
+
+#[test]
+fn pipeline_using_any_channel() {
+    use crate::channel::{Receiver, Sender, TryRecvError};
+    use std::thread;
+
+    fn check<R>()
+    where
+        R: Receiver<usize>,
+        R::Sender: Send + 'static,
+        for<'a> <R::H1Iterator as PlugLifetime<'a>>::T:
+            StreamingIterator<H1Item = H0<usize>>,
+    {
+        let (tx, rx) = R::channel();
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+
+        let workers = (0..4)
+            .map(|i| {
+                let tx = tx.clone();
+
+                thread::spawn(move || tx.send(i * 10).unwrap())
+            })
+            .collect::<Vec<_>>();
+        workers
+            .into_iter()
+            .for_each(|worker| worker.join().unwrap());
+        tx.send(1).unwrap();
+        drop(tx);
+
+        let mut sum = rx.recv().unwrap();
+        let mut iter = rx.iter();
+        while let Some(x) = StreamingIterator::next(&mut iter) {
+            sum += x;
+        }
+        assert_eq!(sum, 61);
+        assert!(rx.recv().is_err());
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Disconnected));
+    }
+
+    check::<std::sync::mpsc::Receiver<usize>>();
+    #[cfg(feature = "crossbeam-channel")]
+    check::<crossbeam_channel::Receiver<usize>>();
+    #[cfg(feature = "flume")]
+    check::<flume::Receiver<usize>>();
+}
+
+#[cfg(any(feature = "bumpalo", feature = "typed-arena"))]
+#[test]
+fn struct_using_any_arena() {
+    use crate::Arena;
+
+    fn check<A>()
+    where
+        A: Arena<String>,
+        for<'a> <A::H1Ref as PlugLifetime<'a>>::T: DerefMut<Target = String>,
+    {
+        let arena = A::new();
+        let mut x = arena.alloc("potato".to_owned());
+        let mut y = arena.alloc("tomato".to_owned());
+        x.push('!');
+        y.push('?');
+
+        assert_eq!(*x, "potato!");
+        assert_eq!(*y, "tomato?");
+    }
+
+    #[cfg(feature = "bumpalo")]
+    check::<bumpalo::Bump>();
+    #[cfg(feature = "typed-arena")]
+    check::<typed_arena::Arena<String>>();
+}
+
+#[test]
+fn pinned_rcb() {
+    struct Unmovable {
+        value: usize,
+        _pin: PhantomPinned,
+    }
+
+    fn sum_of_clones<R>() -> usize
+    where
+        R: PinnedRcb<Unmovable>,
+    {
+        let x = R::pin(Unmovable {
+            value: 21,
+            _pin: PhantomPinned,
+        });
+        let y = x.clone();
+
+        x.as_ref().get_ref().value + y.value
+    }
+
+    assert_eq!(sum_of_clones::<Rc<Unmovable>>(), 42);
+    assert_eq!(sum_of_clones::<Arc<Unmovable>>(), 42);
+}
+
+#[test]
+fn heterogeneous_registry_using_either_rc_or_arc() {
+    fn find<U, R>(registry: &[R]) -> Option<U>
+    where
+        U: Any + Copy,
+        R: DowncastRcb<U> + Clone,
+    {
+        registry
+            .iter()
+            .cloned()
+            .find_map(|x| R::downcast(x).ok())
+            .map(|x| *x)
+    }
+
+    let rc_registry: Vec<Rc<dyn Any>> = vec![Rc::new("xpotato"), Rc::new(42)];
+    let arc_registry: Vec<Arc<dyn Any + Send + Sync>> =
+        vec![Arc::new("xpotato"), Arc::new(42)];
+
+    assert_eq!(find::<i32, _>(&rc_registry), Some(42));
+    assert_eq!(find::<&str, _>(&rc_registry), Some("xpotato"));
+    assert_eq!(find::<u8, _>(&rc_registry), None);
+    assert_eq!(find::<i32, _>(&arc_registry), Some(42));
+    assert_eq!(find::<&str, _>(&arc_registry), Some("xpotato"));
+    assert_eq!(find::<u8, _>(&arc_registry), None);
+}
+
+// Non-trivial async-like trait method. Has the advantage that there's no need
+// to box the resulting future (like https://crates.io/crates/async-trait), but
+// has the disadvantage of making the trait object-unsafe (can't be used as
+// `dyn`). The implementation here is very manual and can probably be mostly
+// abstracted by a macro.
+//
+// Also see:
+// https://smallcultfollowing.com/babysteps/blog/2019/10/26/async-fn-in-traits-are-hard/
+#[test]
+fn streaming_stream() {
+    // Yields each line with a countdown prefix, reusing the same buffer
+    struct Numbered {
+        lines: Vec<&'static str>,
+        buf: String,
+    }
+
+    impl StreamingStream for Numbered {
+        type H1Item = TypedH1Reference<str>;
+        type H1NextFuture = ItemH1Ready<TypedH1Reference<str>>;
+
+        fn next<'a>(&'a mut self) -> <Self::H1NextFuture as PlugLifetime<'a>>::T
+        where
+            <Self::H1NextFuture as PlugLifetime<'a>>::T:
+                NextFuture<'a, Self::H1Item>,
+        {
+            std::future::ready(if self.lines.is_empty() {
+                None
+            } else {
+                let n = self.lines.len();
+                self.buf.clear();
+                self.buf
+                    .push_str(&format!("{}: {}", n, self.lines.remove(0)));
+
+                Some(&self.buf)
+            })
+        }
+    }
+
+    async fn concat<S>(stream: &mut S) -> String
+    where
+        S: StreamingStream<H1Item = TypedH1Reference<str>>,
+        for<'a> <S::H1NextFuture as PlugLifetime<'a>>::T:
+            NextFuture<'a, S::H1Item>,
+    {
+        let mut out = String::new();
+        while let Some(line) = stream.next().await {
+            out.push_str(line);
+            out.push(';');
+        }
+
+        out
+    }
+
+    let mut numbered = Numbered {
+        lines: vec!["a", "b"],
+        buf: String::new(),
+    };
+    assert_eq!(future::block_on(concat(&mut numbered)), "2: a;1: b;");
+    let mut words = stream::iter(vec!["x", "y"].into_iter());
+    assert_eq!(future::block_on(words.next()), Some("x"));
+    assert_eq!(future::block_on(words.next()), Some("y"));
+    assert_eq!(future::block_on(words.next()), None);
+}
+
+#[cfg(feature = "futures")]
+#[test]
+fn streaming_stream_from_stream() {
+    use crate::futures_core::from_stream;
+
+    async fn sum<S>(stream: &mut S) -> usize
+    where
+        S: StreamingStream<H1Item = H0<usize>>,
+        for<'a> <S::H1NextFuture as PlugLifetime<'a>>::T:
+            NextFuture<'a, S::H1Item>,
+    {
+        let mut total = 0;
+        while let Some(x) = stream.next().await {
+            total += x;
+        }
+
+        total
+    }
+
+    let mut owned = from_stream(futures_lite::stream::iter(vec![1, 2, 3]));
+    assert_eq!(future::block_on(sum(&mut owned)), 6);
+    let mut sync = stream::iter(vec![4, 5].into_iter());
+    assert_eq!(future::block_on(sum(&mut sync)), 9);
+}
+
+#[test]
+fn streaming_stream_adapters() {
+    // Yields the numbers from 0 formatted into the same buffer
+    struct Counter {
+        n: usize,
+        buf: String,
+    }
+
+    impl StreamingStream for Counter {
+        type H1Item = TypedH1Reference<str>;
+        type H1NextFuture = ItemH1Ready<TypedH1Reference<str>>;
+
+        fn next<'a>(&'a mut self) -> <Self::H1NextFuture as PlugLifetime<'a>>::T
+        where
+            <Self::H1NextFuture as PlugLifetime<'a>>::T:
+                NextFuture<'a, Self::H1Item>,
+        {
+            self.buf = self.n.to_string();
+            self.n += 1;
+
+            std::future::ready(Some(&self.buf))
+        }
+    }
+
+    let counter = Counter {
+        n: 0,
+        buf: String::new(),
+    };
+    let mut odd = counter.filter(|s: &&str| s.ends_with(['1', '3', '5']));
+    let next = odd.next();
+    let next =
+        std::thread::scope(|s| s.spawn(|| future::block_on(next)).join());
+    assert_eq!(next.unwrap(), Some("1"));
+    let lens = odd.take(3).map(|s: &str| s.len());
+    assert_eq!(
+        future::block_on(stream::collect::<_, Vec<_>, _>(lens)),
+        vec![1, 1, 2]
+    );
+}
+
+#[test]
+fn streaming_stream_conformance() {
+    // Counts down from `n`, returning `Pending` before each item
+    struct Countdown {
+        n: usize,
+        ready: bool,
+    }
+
+    impl PollStreamingIterator for Countdown {
+        type H1Item = TypedH1Reference<usize>;
+
+        fn poll_next<'a>(
+            self: Pin<&'a mut Self>,
+            cx: &mut Context,
+        ) -> Poll<Option<&'a usize>> {
+            let this = self.get_mut();
+            if this.n == 0 {
+                return Poll::Ready(None);
+            } else if !this.ready {
+                this.ready = true;
+                cx.waker().wake_by_ref();
+
+                return Poll::Pending;
+            }
+            this.ready = false;
+            this.n -= 1;
+
+            Poll::Ready(Some(&this.n))
+        }
+    }
+
+    let countdown = || stream::from_poll(Countdown { n: 3, ready: false });
+    let words = || stream::iter(vec!["a", "b"].into_iter());
+    future::block_on(async {
+        test_utils::check_streaming_stream(countdown(), &[2, 1, 0], |x| *x)
+            .await;
+        test_utils::check_fused_streaming_stream(countdown()).await;
+        test_utils::check_cancel_safe_streaming_stream(
+            countdown(),
+            &[2, 1, 0],
+            |x| *x,
+        )
+        .await;
+        test_utils::check_cancel_safe_streaming_stream(
+            countdown().take(2),
+            &[2, 1],
+            |x| *x,
+        )
+        .await;
+        test_utils::check_streaming_stream(words().take(1), &["a"], |x| x)
+            .await;
+        test_utils::check_fused_streaming_stream(words()).await;
+        test_utils::check_cancel_safe_streaming_stream(
+            words().map(str::len),
+            &[1, 1],
+            |x| x,
+        )
+        .await;
+    });
+}
+
+#[test]
+fn poll_streaming_iterator() {
+    // Yields windows of its buffer, returning `Pending` before each one
+    struct Windows {
+        buf: Vec<u8>,
+        start: usize,
+        ready: bool,
+    }
+
+    impl PollStreamingIterator for Windows {
+        type H1Item = TypedH1Reference<[u8]>;
+
+        fn poll_next<'a>(
+            self: Pin<&'a mut Self>,
+            cx: &mut Context,
+        ) -> Poll<Option<&'a [u8]>> {
+            let this = self.get_mut();
+            if !this.ready {
+                this.ready = true;
+                cx.waker().wake_by_ref();
+
+                return Poll::Pending;
+            }
+            this.ready = false;
+            this.start += 1;
+
+            Poll::Ready(this.buf.get(this.start - 1..this.start + 1))
+        }
+    }
+
+    let mut windows = stream::from_poll(Windows {
+        buf: vec![1, 2, 3],
+        start: 0,
+        ready: false,
+    });
+    assert_eq!(future::block_on(windows.next()), Some(&[1, 2][..]));
+    let next = windows.next();
+    let next =
+        std::thread::scope(|s| s.spawn(|| future::block_on(next)).join());
+    assert_eq!(next.unwrap(), Some(&[2, 3][..]));
+    assert_eq!(future::block_on(windows.next()), None);
+
+    let mut iter = stream::iter(vec![1].into_iter());
+    assert_eq!(
+        future::block_on(std::future::poll_fn(|cx| {
+            Pin::new(&mut iter).poll_next(cx)
+        })),
+        Some(1)
+    );
+}
+
+#[cfg(feature = "tokio-stream")]
+#[test]
+fn streaming_stream_tokio() {
+    use crate::{futures_core::into_stream, tokio_stream};
+    use ::tokio::sync::mpsc;
+    use futures_lite::StreamExt;
+
+    let (tx, rx) = mpsc::channel(4);
+    let mut rx = tokio_stream::from_receiver(rx);
+    future::block_on(async {
+        tx.send(1).await.unwrap();
+        tx.send(2).await.unwrap();
+        drop(tx);
+    });
+    assert_eq!(future::block_on(rx.next()), Some(1));
+
+    let rest = into_stream(rx);
+    assert_eq!(future::block_on(rest.collect::<Vec<_>>()), vec![2]);
+}
+
+#[test]
+fn async_sequence_mut() {
+    async fn push_twice<C>(sink: &mut C, x: usize) -> Result<(), usize>
+    where
+        C: AsyncSequenceMut<usize>,
+        for<'a> <C::H1PushFuture as PlugLifetime<'a>>::T:
+            Future<Output = Result<(), usize>>,
+    {
+        sink.push(x).await?;
+        sink.push(x + 1).await
+    }
+
+    let mut vec = Vec::new();
+    future::block_on(push_twice(&mut vec, 1)).unwrap();
+    let mut deque = std::collections::VecDeque::new();
+    future::block_on(stream::forward(
+        stream::iter(vec.into_iter()),
+        &mut deque,
+    ))
+    .unwrap();
+    assert_eq!(deque, [1, 2]);
+
+    #[cfg(feature = "tokio")]
+    {
+        use ::tokio::sync::mpsc;
+
+        let (mut tx, mut rx) = mpsc::channel(4);
+        future::block_on(push_twice(&mut tx, 3)).unwrap();
+        assert_eq!(rx.try_recv(), Ok(3));
+        assert_eq!(rx.try_recv(), Ok(4));
+        drop(rx);
+        assert_eq!(future::block_on(push_twice(&mut tx, 5)), Err(5));
+
+        let (mut tx, rx) = mpsc::unbounded_channel();
+        drop(rx);
+        assert_eq!(future::block_on(push_twice(&mut tx, 7)), Err(7));
+    }
+}
+
+#[test]
+fn async_trait_macro() {
+    define_h1_future! {
+        struct RefFuture<'a> {
+            x: &'a usize,
+        } as H1RefFuture;
+    }
+
+    impl<'a> Future for RefFuture<'a> {
+        type Output = &'a usize;
+
+        fn poll(self: Pin<&mut Self>, _: &mut Context) -> Poll<Self::Output> {
+            Poll::Ready(self.x)
+        }
+    }
+
+    hkt_async_trait! {
+        trait AsyncTrait {
+            async fn non_trivial<'a>(&self, x: &'a usize) -> &'a usize
+                as H1NonTrivialFuture;
+            async fn other<'a>(&'a self) -> Option<&'a usize>
+                as H1OtherFuture;
+            async fn generated<'a>(&'a self) -> &'a usize
+                as H1GeneratedFuture;
+        }
+    }
+
+    struct AsyncTraitImpl(usize);
+
+    hkt_async_trait! {
+        impl AsyncTrait for AsyncTraitImpl {
+            async fn non_trivial<'a>(&self, x: &'a usize) -> &'a usize
+                as H1NonTrivialFuture = H1RefFuture
+            {
+                RefFuture { x }
+            }
+
+            async fn other<'a>(&'a self) -> Option<&'a usize>
+                as H1OtherFuture = ItemH1Ready<TypedH1Reference<usize>>
+            {
+                std::future::ready(Some(&self.0))
+            }
+
+            async fn generated<'a>(&'a self) -> &'a usize
+                as H1GeneratedFuture =
+                    struct H1ReadyRef for std::future::Ready<&'a usize>
+            {
+                std::future::ready(&self.0)
+            }
+        }
+    }
+
+    let payload = 42_usize;
+    let x = AsyncTraitImpl(1);
+    assert_eq!(future::block_on(x.non_trivial(&payload)), &payload);
+    assert_eq!(future::block_on(x.other()), Some(&1));
+    assert_eq!(future::block_on(x.generated()), &1);
+}
+
+#[test]
+fn boxed_futures() {
+    hkt_async_trait! {
+        trait AsyncTrait {
+            async fn get<'a>(&'a self) -> &'a usize as H1GetFuture;
+        }
+    }
+
+    struct AsyncTraitImpl(usize);
+
+    hkt_async_trait! {
+        impl AsyncTrait for AsyncTraitImpl {
+            async fn get<'a>(&'a self) -> &'a usize
+                as H1GetFuture = OutputH1BoxFuture<TypedH1Reference<usize>>
+            {
+                boxed::box_future(async move { &self.0 })
+            }
+        }
+    }
+
+    assert_eq!(future::block_on(AsyncTraitImpl(1).get()), &1);
+
+    struct SendAsyncTraitImpl(usize);
+
+    hkt_async_trait! {
+        impl AsyncTrait for SendAsyncTraitImpl {
+            async fn get<'a>(&'a self) -> &'a usize
+                as H1GetFuture = OutputH1SendBoxFuture<TypedH1Reference<usize>>
+            {
+                boxed::box_send_future(async move { &self.0 })
+            }
+        }
+    }
+
+    let x = SendAsyncTraitImpl(2);
+    let get = x.get();
+    let result =
+        std::thread::scope(|s| s.spawn(|| future::block_on(get)).join());
+    assert_eq!(result.unwrap(), &2);
+
+    type DynStream = dyn StreamingStream<
+        H1Item = H0<usize>,
+        H1NextFuture = ItemH1BoxFuture<H0<usize>>,
+    >;
+
+    let mut streams: Vec<Box<DynStream>> = vec![
+        Box::new(stream::iter(vec![1, 2].into_iter()).boxed()),
+        Box::new(stream::iter(vec![3, 4].into_iter()).take(1).boxed()),
+    ];
+    let mut items = Vec::new();
+    for stream in &mut streams {
+        while let Some(x) = future::block_on(stream.next()) {
+            items.push(x);
+        }
+    }
+    assert_eq!(items, [1, 2, 3]);
 }
 
-// Non-trivial async-like trait method. Has the advantage that there's no need
-// to box the resulting future (like https://crates.io/crates/async-trait), but
-// has the disadvantage of making the trait object-unsafe (can't be used as
-// `dyn`). The implementation here is very manual and can probably be mostly
-// abstracted by a macro.
-//
-// Also see:
-// https://smallcultfollowing.com/babysteps/blog/2019/10/26/async-fn-in-traits-are-hard/
 #[test]
 fn async_trait_method() {
     struct H1RefFuture;
@@ -198,5 +3500,5 @@ fn async_trait_method() {
     let payload = 42_usize;
     let future = AsyncTraitImpl.non_trivial(&payload);
 
-    assert_eq!(executor.run(future), &payload);
+    assert_eq!(future::block_on(executor.run(future)), &payload);
 }
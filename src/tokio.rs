@@ -0,0 +1,250 @@
+//! HKT forms and lock trait impls for the
+//! [tokio](https://crates.io/crates/tokio) crate.
+//!
+//! `tokio` futures are anonymous, so the trait impls box them with the `Send`
+//! forms from [`boxed`](../boxed/index.html) to name them.
+
+use crate::{
+    boxed::{self, OutputH1SendBoxFuture, TypedH1SendBoxFuture},
+    lock::{AsyncLock, AsyncOnceLike, AsyncRwLockLike},
+    plug::{PlugLifetime, PlugType, H0},
+    reference::TypedH1Reference,
+    stream::AsyncSequenceMut,
+};
+use ::tokio::sync::{
+    mpsc::{Sender, UnboundedSender},
+    Mutex, MutexGuard, OnceCell, RwLock, RwLockReadGuard, RwLockWriteGuard,
+};
+use std::{
+    future::{self, Future, Ready},
+    marker::PhantomData,
+};
+
+/// HKT `tokio::sync::Mutex<T>` with a type slot.
+pub struct H1Mutex;
+
+impl<T> PlugType<T> for H1Mutex {
+    type T = Mutex<T>;
+}
+
+/// HKT `tokio::sync::MutexGuard<'a, T>` with a lifetime and a type slot.
+pub struct H2MutexGuard;
+
+impl<'a> PlugLifetime<'a> for H2MutexGuard {
+    type T = H1MutexGuard<'a>;
+}
+
+/// HKT `tokio::sync::MutexGuard<'a, T>` with a type slot.
+pub struct H1MutexGuard<'a>(PhantomData<&'a ()>);
+
+impl<'a, T> PlugType<T> for H1MutexGuard<'a>
+where
+    T: 'a + ?Sized,
+{
+    type T = MutexGuard<'a, T>;
+}
+
+/// HKT `tokio::sync::MutexGuard<'a, T>` with a lifetime slot.
+pub struct TypedH1MutexGuard<T>(PhantomData<T>)
+where
+    T: ?Sized;
+
+impl<'a, T> PlugLifetime<'a> for TypedH1MutexGuard<T>
+where
+    T: 'a + ?Sized,
+{
+    type T = MutexGuard<'a, T>;
+}
+
+impl<T> AsyncLock<T> for Mutex<T>
+where
+    T: Send + 'static,
+{
+    type H1Guard = TypedH1MutexGuard<T>;
+    type H1LockFuture = OutputH1SendBoxFuture<TypedH1MutexGuard<T>>;
+
+    fn new(x: T) -> Self {
+        Mutex::<T>::new(x)
+    }
+
+    // The `GuardFuture` bound is left out since it would shadow the blanket
+    // impl for the concrete future, preventing its output from being
+    // normalized
+    fn lock<'a>(&'a self) -> <Self::H1LockFuture as PlugLifetime<'a>>::T
+    where
+        Self: 'a,
+    {
+        boxed::box_send_future(Mutex::<T>::lock(self))
+    }
+}
+
+/// HKT `tokio::sync::RwLock<T>` with a type slot.
+pub struct H1RwLock;
+
+impl<T> PlugType<T> for H1RwLock {
+    type T = RwLock<T>;
+}
+
+/// HKT `tokio::sync::RwLockReadGuard<'a, T>` with a lifetime and a type slot.
+pub struct H2RwLockReadGuard;
+
+impl<'a> PlugLifetime<'a> for H2RwLockReadGuard {
+    type T = H1RwLockReadGuard<'a>;
+}
+
+/// HKT `tokio::sync::RwLockReadGuard<'a, T>` with a type slot.
+pub struct H1RwLockReadGuard<'a>(PhantomData<&'a ()>);
+
+impl<'a, T> PlugType<T> for H1RwLockReadGuard<'a>
+where
+    T: 'a + ?Sized,
+{
+    type T = RwLockReadGuard<'a, T>;
+}
+
+/// HKT `tokio::sync::RwLockReadGuard<'a, T>` with a lifetime slot.
+pub struct TypedH1RwLockReadGuard<T>(PhantomData<T>)
+where
+    T: ?Sized;
+
+impl<'a, T> PlugLifetime<'a> for TypedH1RwLockReadGuard<T>
+where
+    T: 'a + ?Sized,
+{
+    type T = RwLockReadGuard<'a, T>;
+}
+
+/// HKT `tokio::sync::RwLockWriteGuard<'a, T>` with a lifetime and a type slot.
+pub struct H2RwLockWriteGuard;
+
+impl<'a> PlugLifetime<'a> for H2RwLockWriteGuard {
+    type T = H1RwLockWriteGuard<'a>;
+}
+
+/// HKT `tokio::sync::RwLockWriteGuard<'a, T>` with a type slot.
+pub struct H1RwLockWriteGuard<'a>(PhantomData<&'a ()>);
+
+impl<'a, T> PlugType<T> for H1RwLockWriteGuard<'a>
+where
+    T: 'a + ?Sized,
+{
+    type T = RwLockWriteGuard<'a, T>;
+}
+
+/// HKT `tokio::sync::RwLockWriteGuard<'a, T>` with a lifetime slot.
+pub struct TypedH1RwLockWriteGuard<T>(PhantomData<T>)
+where
+    T: ?Sized;
+
+impl<'a, T> PlugLifetime<'a> for TypedH1RwLockWriteGuard<T>
+where
+    T: 'a + ?Sized,
+{
+    type T = RwLockWriteGuard<'a, T>;
+}
+
+/// Locking acquires exclusive access.
+impl<T> AsyncLock<T> for RwLock<T>
+where
+    T: Send + Sync + 'static,
+{
+    type H1Guard = TypedH1RwLockWriteGuard<T>;
+    type H1LockFuture = OutputH1SendBoxFuture<TypedH1RwLockWriteGuard<T>>;
+
+    fn new(x: T) -> Self {
+        RwLock::<T>::new(x)
+    }
+
+    fn lock<'a>(&'a self) -> <Self::H1LockFuture as PlugLifetime<'a>>::T
+    where
+        Self: 'a,
+    {
+        boxed::box_send_future(RwLock::<T>::write(self))
+    }
+}
+
+impl<T> AsyncRwLockLike<T> for RwLock<T>
+where
+    T: Send + Sync + 'static,
+{
+    type H1ReadGuard = TypedH1RwLockReadGuard<T>;
+    type H1ReadFuture = OutputH1SendBoxFuture<TypedH1RwLockReadGuard<T>>;
+
+    // Same as `lock()`, the `ReadGuardFuture` bound is left out
+    fn read<'a>(&'a self) -> <Self::H1ReadFuture as PlugLifetime<'a>>::T
+    where
+        Self: 'a,
+    {
+        boxed::box_send_future(RwLock::<T>::read(self))
+    }
+}
+
+/// HKT `tokio::sync::OnceCell<T>` with a type slot.
+pub struct H1OnceCell;
+
+impl<T> PlugType<T> for H1OnceCell {
+    type T = OnceCell<T>;
+}
+
+impl<T> AsyncOnceLike<T> for OnceCell<T>
+where
+    T: Send + Sync + 'static,
+{
+    type H1InitFuture = OutputH1SendBoxFuture<TypedH1Reference<T>>;
+
+    fn new() -> Self {
+        OnceCell::<T>::new()
+    }
+
+    fn get(&self) -> Option<&T> {
+        OnceCell::<T>::get(self)
+    }
+
+    fn get_or_init<'a, F>(
+        &'a self,
+        init: F,
+    ) -> <Self::H1InitFuture as PlugLifetime<'a>>::T
+    where
+        Self: 'a,
+        F: Future<Output = T> + Send + 'a,
+    {
+        boxed::box_send_future(OnceCell::<T>::get_or_init(self, || init))
+    }
+}
+
+/// Waits for capacity in the channel.
+impl<T> AsyncSequenceMut<T> for Sender<T>
+where
+    T: Send + 'static,
+{
+    type H1PushFuture = TypedH1SendBoxFuture<Result<(), T>>;
+
+    fn push<'a>(
+        &'a mut self,
+        x: T,
+    ) -> <Self::H1PushFuture as PlugLifetime<'a>>::T
+    where
+        <Self::H1PushFuture as PlugLifetime<'a>>::T:
+            Future<Output = Result<(), T>>,
+    {
+        boxed::box_send_future(async move {
+            Sender::<T>::send(self, x).await.map_err(|err| err.0)
+        })
+    }
+}
+
+/// Elements are always accepted immediately while the channel is open.
+impl<T> AsyncSequenceMut<T> for UnboundedSender<T> {
+    type H1PushFuture = H0<Ready<Result<(), T>>>;
+
+    fn push<'a>(
+        &'a mut self,
+        x: T,
+    ) -> <Self::H1PushFuture as PlugLifetime<'a>>::T
+    where
+        <Self::H1PushFuture as PlugLifetime<'a>>::T:
+            Future<Output = Result<(), T>>,
+    {
+        future::ready(UnboundedSender::<T>::send(self, x).map_err(|err| err.0))
+    }
+}
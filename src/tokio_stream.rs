@@ -0,0 +1,25 @@
+//! Conversions between [tokio-stream](https://crates.io/crates/tokio-stream)
+//! streams and the streaming traits in this crate.
+//!
+//! `tokio_stream::Stream` is the same trait as `futures_core::Stream`, so the
+//! adapters from the [futures_core](../futures_core/index.html) module apply
+//! to every Tokio stream.
+
+pub use crate::futures_core::{
+    from_stream, into_stream, FromStream, IntoStream,
+};
+use ::tokio::sync::mpsc::{Receiver, UnboundedReceiver};
+use ::tokio_stream::wrappers::{ReceiverStream, UnboundedReceiverStream};
+
+/// Wrap the receiving half of a Tokio channel as a streaming stream.
+pub fn from_receiver<T>(rx: Receiver<T>) -> FromStream<ReceiverStream<T>> {
+    from_stream(ReceiverStream::new(rx))
+}
+
+/// Wrap the receiving half of an unbounded Tokio channel as a streaming
+/// stream.
+pub fn from_unbounded_receiver<T>(
+    rx: UnboundedReceiver<T>,
+) -> FromStream<UnboundedReceiverStream<T>> {
+    from_stream(UnboundedReceiverStream::new(rx))
+}
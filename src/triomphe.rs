@@ -0,0 +1,84 @@
+//! Thread-safe reference-counting pointers without weak pointer support from
+//! the [triomphe](https://crates.io/crates/triomphe) crate.
+
+use crate::{plug::PlugType, CowLike, NewPtr, Ptr, Rcb};
+use ::triomphe::Arc;
+#[cfg(feature = "allocator_api")]
+use core::alloc::AllocError;
+
+/// HKT `triomphe::Arc<T>` with a type slot.
+pub struct H1Arc;
+
+impl<T> PlugType<T> for H1Arc
+where
+    T: ?Sized,
+{
+    type T = Arc<T>;
+}
+
+impl<T> Ptr<T> for Arc<T> where T: ?Sized {}
+
+impl<T> NewPtr<T> for Arc<T> {
+    fn new(x: T) -> Self {
+        Arc::<T>::new(x)
+    }
+}
+
+impl<T> Rcb<T> for Arc<T>
+where
+    T: ?Sized,
+{
+    fn new(x: T) -> Self
+    where
+        T: Sized,
+    {
+        Arc::<T>::new(x)
+    }
+
+    #[cfg(feature = "allocator_api")]
+    fn try_new(x: T) -> Result<Self, AllocError>
+    where
+        T: Sized,
+    {
+        Arc::<T>::try_new(x).map_err(|_| AllocError)
+    }
+
+    fn try_unwrap(this: Self) -> Result<T, Self>
+    where
+        T: Sized,
+    {
+        Arc::<T>::try_unwrap(this)
+    }
+
+    fn unwrap_or_clone(this: Self) -> T
+    where
+        T: Clone,
+    {
+        Arc::<T>::unwrap_or_clone(this)
+    }
+
+    fn into_raw(this: Self) -> *const T {
+        Arc::<T>::into_raw(this)
+    }
+
+    unsafe fn from_raw(ptr: *const T) -> Self {
+        Arc::<T>::from_raw(ptr)
+    }
+}
+
+impl<T> CowLike<T> for Arc<T>
+where
+    T: Clone,
+{
+    fn from_owned(x: T) -> Self {
+        Arc::<T>::new(x)
+    }
+
+    fn to_mut(&mut self) -> &mut T {
+        Arc::<T>::make_mut(self)
+    }
+
+    fn into_owned(self) -> T {
+        Arc::<T>::unwrap_or_clone(self)
+    }
+}
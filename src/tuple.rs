@@ -0,0 +1,28 @@
+//! HKT forms for tuples.
+
+use crate::{fp::Bifunctor, plug::PlugType};
+use core::marker::PhantomData;
+
+/// HKT `(A, B)` with two type slots.
+pub struct H2Tuple;
+
+impl<A> PlugType<A> for H2Tuple {
+    type T = H1Tuple<A>;
+}
+
+/// HKT `(A, B)` with a second type slot.
+pub struct H1Tuple<A>(PhantomData<A>);
+
+impl<A, B> PlugType<B> for H1Tuple<A> {
+    type T = (A, B);
+}
+
+impl<A, B, C, D> Bifunctor<A, B, C, D> for H2Tuple {
+    fn bimap<F, G>((a, b): (A, B), mut f: F, mut g: G) -> (C, D)
+    where
+        F: FnMut(A) -> C,
+        G: FnMut(B) -> D,
+    {
+        (f(a), g(b))
+    }
+}
@@ -0,0 +1,25 @@
+//! Trait impls for the [typed-arena](https://crates.io/crates/typed-arena)
+//! crate.
+
+use crate::{plug::PlugLifetime, reference::TypedH1MutReference, Arena};
+use ::typed_arena::Arena as TypedArena;
+use core::ops::DerefMut;
+
+/// Destructors run when the arena is dropped.
+impl<T> Arena<T> for TypedArena<T>
+where
+    T: 'static,
+{
+    type H1Ref = TypedH1MutReference<T>;
+
+    fn new() -> Self {
+        TypedArena::<T>::new()
+    }
+
+    fn alloc<'a>(&'a self, x: T) -> <Self::H1Ref as PlugLifetime<'a>>::T
+    where
+        <Self::H1Ref as PlugLifetime<'a>>::T: DerefMut<Target = T>,
+    {
+        TypedArena::<T>::alloc(self, x)
+    }
+}
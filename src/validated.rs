@@ -0,0 +1,141 @@
+//! Error accumulation with the `Validated` type.
+
+use crate::{
+    fp::{Applicative, Bifunctor, Functor, Pure, Semigroup, Zip},
+    plug::PlugType,
+};
+use core::marker::PhantomData;
+
+/// Either a valid value of type `T` or the errors of type `E` found while
+/// producing it.
+///
+/// Unlike `Result`, combining two invalid values with
+/// [`Applicative::map2`](../fp/trait.Applicative.html#tymethod.map2) keeps the
+/// errors of both, combined with [`Semigroup`](../fp/trait.Semigroup.html).
+/// Because of this, `Validated` is intentionally not a
+/// [`Monad`](../fp/trait.Monad.html): `bind` could not look past the first
+/// error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Validated<E, T> {
+    /// The value is valid.
+    Valid(T),
+    /// The value is invalid.
+    Invalid(E),
+}
+
+impl<E, T> Validated<E, T> {
+    /// Returns `true` if the value is valid.
+    pub fn is_valid(&self) -> bool {
+        matches!(self, Validated::Valid(_))
+    }
+
+    /// Convert into a `Result`.
+    pub fn into_result(self) -> Result<T, E> {
+        self.into()
+    }
+}
+
+impl<E, T> From<Result<T, E>> for Validated<E, T> {
+    fn from(x: Result<T, E>) -> Self {
+        match x {
+            Ok(x) => Validated::Valid(x),
+            Err(err) => Validated::Invalid(err),
+        }
+    }
+}
+
+impl<E, T> From<Validated<E, T>> for Result<T, E> {
+    fn from(x: Validated<E, T>) -> Self {
+        match x {
+            Validated::Valid(x) => Ok(x),
+            Validated::Invalid(err) => Err(err),
+        }
+    }
+}
+
+/// HKT `Validated<E, T>` with two type slots.
+pub struct H2Validated;
+
+impl<E> PlugType<E> for H2Validated {
+    type T = H1Validated<E>;
+}
+
+/// HKT `Validated<E, T>` with a value type slot.
+pub struct H1Validated<E>(PhantomData<E>);
+
+impl<E, T> PlugType<T> for H1Validated<E> {
+    type T = Validated<E, T>;
+}
+
+impl<A, B, E> Functor<A, B> for H1Validated<E> {
+    fn fmap<F>(fa: Validated<E, A>, mut f: F) -> Validated<E, B>
+    where
+        F: FnMut(A) -> B,
+    {
+        match fa {
+            Validated::Valid(x) => Validated::Valid(f(x)),
+            Validated::Invalid(err) => Validated::Invalid(err),
+        }
+    }
+}
+
+impl<A, E> Pure<A> for H1Validated<E> {
+    fn pure(x: A) -> Validated<E, A> {
+        Validated::Valid(x)
+    }
+}
+
+impl<A, B, C, E> Applicative<A, B, C> for H1Validated<E>
+where
+    E: Semigroup,
+{
+    fn map2<F>(
+        fa: Validated<E, A>,
+        fb: Validated<E, B>,
+        mut f: F,
+    ) -> Validated<E, C>
+    where
+        F: FnMut(A, B) -> C,
+    {
+        match (fa, fb) {
+            (Validated::Valid(a), Validated::Valid(b)) => {
+                Validated::Valid(f(a, b))
+            }
+            (Validated::Invalid(x), Validated::Invalid(y)) => {
+                Validated::Invalid(x.combine(y))
+            }
+            (Validated::Invalid(err), _) | (_, Validated::Invalid(err)) => {
+                Validated::Invalid(err)
+            }
+        }
+    }
+}
+
+impl<A, B, C, E> Zip<A, B, C> for H1Validated<E>
+where
+    E: Semigroup,
+{
+    fn zip_with<F>(
+        fa: Validated<E, A>,
+        fb: Validated<E, B>,
+        f: F,
+    ) -> Validated<E, C>
+    where
+        F: FnMut(A, B) -> C,
+    {
+        Self::map2(fa, fb, f)
+    }
+}
+
+impl<A, B, C, D> Bifunctor<A, B, C, D> for H2Validated {
+    fn bimap<F, G>(fab: Validated<A, B>, mut f: F, mut g: G) -> Validated<C, D>
+    where
+        F: FnMut(A) -> C,
+        G: FnMut(B) -> D,
+    {
+        match fab {
+            Validated::Valid(x) => Validated::Valid(g(x)),
+            Validated::Invalid(err) => Validated::Invalid(f(err)),
+        }
+    }
+}
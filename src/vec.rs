@@ -2,11 +2,26 @@
 //! `Vec<T>`.
 
 use crate::{
-    plug::{PlugLifetime, PlugType},
+    fp::{
+        Alternative, Applicative, Functor, Monad, Monoid, Pure, Semigroup,
+        Transform, Traversable, Zip,
+    },
+    option::H1Option,
+    plug::{PlugLifetime, PlugType, H0},
+    reference::TypedH1Reference,
     slice::TypedH1Iter,
-    Sequence, SequenceMut, StreamingIterator, WithCapacity,
+    stream::AsyncSequenceMut,
+    ByteBuf, Capacity, PlugBorrow, Sequence, SequenceMut, StreamingIterator,
+    WithCapacity,
+};
+use alloc::{
+    vec,
+    vec::{IntoIter, Vec},
+};
+use core::{
+    future::{self, Future, Ready},
+    mem,
 };
-use std::vec::Vec;
 
 /// HKT `Vec` with a type slot.
 pub struct H1Vec;
@@ -15,12 +30,113 @@ impl<T> PlugType<T> for H1Vec {
     type T = Vec<T>;
 }
 
+impl<A, B> Functor<A, B> for H1Vec {
+    fn fmap<F>(fa: Vec<A>, f: F) -> Vec<B>
+    where
+        F: FnMut(A) -> B,
+    {
+        fa.into_iter().map(f).collect()
+    }
+}
+
+impl<A> Pure<A> for H1Vec {
+    fn pure(x: A) -> Vec<A> {
+        vec![x]
+    }
+}
+
+impl<A, B, C> Applicative<A, B, C> for H1Vec
+where
+    A: Clone,
+    B: Clone,
+{
+    fn map2<F>(fa: Vec<A>, fb: Vec<B>, mut f: F) -> Vec<C>
+    where
+        F: FnMut(A, B) -> C,
+    {
+        let mut fc = Vec::with_capacity(fa.len() * fb.len());
+        for a in fa {
+            for b in &fb {
+                fc.push(f(a.clone(), b.clone()));
+            }
+        }
+
+        fc
+    }
+}
+
+impl<A, B> Monad<A, B> for H1Vec {
+    fn bind<F>(fa: Vec<A>, f: F) -> Vec<B>
+    where
+        F: FnMut(A) -> Vec<B>,
+    {
+        fa.into_iter().flat_map(f).collect()
+    }
+}
+
+/// Does not short-circuit: `f` is called on every element, even after an
+/// effect such as `None` or `Err` has already decided the result. A generic `M`
+/// can't be inspected for failure, so collect into `Option` or `Result`
+/// directly to stop at the first failure.
+impl<M, A, B> Traversable<M, A, B> for H1Vec
+where
+    M: Pure<Vec<B>> + Applicative<Vec<B>, B, Vec<B>>,
+{
+    fn traverse<F>(ta: Vec<A>, mut f: F) -> <M as PlugType<Vec<B>>>::T
+    where
+        F: FnMut(A) -> <M as PlugType<B>>::T,
+    {
+        let len = ta.len();
+        ta.into_iter()
+            .fold(M::pure(Vec::with_capacity(len)), |acc, a| {
+                M::map2(acc, f(a), |mut vec, b| {
+                    vec.push(b);
+
+                    vec
+                })
+            })
+    }
+}
+
+/// Stops at the end of the shorter `Vec`.
+impl<A, B, C> Zip<A, B, C> for H1Vec {
+    fn zip_with<F>(fa: Vec<A>, fb: Vec<B>, mut f: F) -> Vec<C>
+    where
+        F: FnMut(A, B) -> C,
+    {
+        fa.into_iter().zip(fb).map(|(a, b)| f(a, b)).collect()
+    }
+}
+
+/// HKT `std::vec::IntoIter<T>` with a type slot.
+///
+/// Not a [`Functor`](../fp/trait.Functor.html): mapping would have to collect
+/// the remaining elements into a new `Vec`. Map the iterator directly, or use
+/// [`H1Vec`](struct.H1Vec.html) instead.
+pub struct H1VecIntoIter;
+
+impl<T> PlugType<T> for H1VecIntoIter {
+    type T = IntoIter<T>;
+}
+
+impl<A> Pure<A> for H1VecIntoIter {
+    fn pure(x: A) -> IntoIter<A> {
+        vec![x].into_iter()
+    }
+}
+
 impl<T> WithCapacity for Vec<T> {
     fn with_capacity(capacity: usize) -> Self {
         Vec::<T>::with_capacity(capacity)
     }
 }
 
+impl<T> Capacity for Vec<T> {
+    fn capacity(&self) -> usize {
+        Vec::<T>::capacity(self)
+    }
+}
+
 impl<T> Sequence<T> for Vec<T>
 where
     T: 'static,
@@ -35,7 +151,7 @@ where
         Vec::<T>::is_empty(self)
     }
 
-    fn contains<'a>(&'a self, x: &T) -> bool
+    fn contains(&self, x: &T) -> bool
     where
         T: PartialEq,
     {
@@ -62,6 +178,63 @@ where
     }
 }
 
+/// Elements are always accepted immediately.
+impl<T> AsyncSequenceMut<T> for Vec<T> {
+    type H1PushFuture = H0<Ready<Result<(), T>>>;
+
+    fn push<'a>(
+        &'a mut self,
+        x: T,
+    ) -> <Self::H1PushFuture as PlugLifetime<'a>>::T
+    where
+        <Self::H1PushFuture as PlugLifetime<'a>>::T:
+            Future<Output = Result<(), T>>,
+    {
+        Vec::<T>::push(self, x);
+
+        future::ready(Ok(()))
+    }
+}
+
+impl ByteBuf for Vec<u8> {
+    #[inline]
+    fn extend_from_slice(&mut self, bytes: &[u8]) {
+        Vec::<u8>::extend_from_slice(self, bytes)
+    }
+
+    #[inline]
+    fn as_slice(&self) -> &[u8] {
+        Vec::<u8>::as_slice(self)
+    }
+
+    #[inline]
+    fn as_mut_slice(&mut self) -> &mut [u8] {
+        Vec::<u8>::as_mut_slice(self)
+    }
+
+    #[inline]
+    fn truncate(&mut self, len: usize) {
+        Vec::<u8>::truncate(self, len)
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        Vec::<u8>::clear(self)
+    }
+
+    #[inline]
+    fn split_to(&mut self, at: usize) -> Self {
+        let tail = Vec::<u8>::split_off(self, at);
+
+        mem::replace(self, tail)
+    }
+
+    #[inline]
+    fn split_off(&mut self, at: usize) -> Self {
+        Vec::<u8>::split_off(self, at)
+    }
+}
+
 impl<T> SequenceMut<T> for Vec<T> {
     fn capacity(&self) -> usize {
         Vec::<T>::capacity(self)
@@ -99,3 +272,53 @@ impl<T> SequenceMut<T> for Vec<T> {
         Vec::<T>::remove(self, index)
     }
 }
+
+/// Keeps the first element.
+impl<T> Transform<H1Option, T> for H1Vec {
+    fn transform(fa: Vec<T>) -> Option<T> {
+        Iterator::next(&mut fa.into_iter())
+    }
+}
+
+/// Choice keeps all results of both sides.
+impl<A> Alternative<A> for H1Vec {
+    fn empty() -> Vec<A> {
+        Vec::new()
+    }
+
+    fn or(mut x: Vec<A>, mut y: Vec<A>) -> Vec<A> {
+        x.append(&mut y);
+
+        x
+    }
+}
+
+/// Concatenation.
+impl<T> Semigroup for Vec<T> {
+    fn combine(mut self, mut other: Self) -> Self {
+        self.append(&mut other);
+
+        self
+    }
+}
+
+impl<T> Monoid for Vec<T> {
+    fn empty() -> Self {
+        Vec::new()
+    }
+}
+
+impl<T> PlugBorrow for Vec<T>
+where
+    T: 'static + Clone,
+{
+    type H1Borrowed = TypedH1Reference<[T]>;
+
+    fn borrow(&self) -> &[T] {
+        Vec::<T>::as_slice(self)
+    }
+
+    fn to_owned(x: &[T]) -> Self {
+        <[T]>::to_vec(x)
+    }
+}
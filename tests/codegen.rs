@@ -0,0 +1,324 @@
+//! Checks that calls through the trait impls compile to the same code as the
+//! direct std calls in release builds, both for non-generic impls and for
+//! generic ones once monomorphized.
+//!
+//! A scratch crate depending on this one is built with `--emit=asm`, and the
+//! bodies of pairs of `#[no_mangle]` functions are compared.
+
+#![cfg(all(
+    target_os = "linux",
+    feature = "std",
+    feature = "rc",
+    feature = "sync",
+    feature = "vec",
+))]
+
+use std::{collections::HashMap, env, fs, path::Path, process::Command};
+
+const PAIRS: &str = r#"
+use generic_std::{
+    ByteBuf, Capacity, PlugBorrow, Rcb, Sequence, SequenceMut, Stringy,
+    WithCapacity,
+};
+use std::{rc::Rc, sync::Arc};
+
+#[no_mangle]
+pub fn string_as_str_trait(s: &String) -> &str {
+    Stringy::as_str(s)
+}
+
+#[no_mangle]
+pub fn string_as_str_direct(s: &String) -> &str {
+    s.as_str()
+}
+
+#[no_mangle]
+pub fn string_borrow_trait(s: &String) -> &str {
+    PlugBorrow::borrow(s)
+}
+
+#[no_mangle]
+pub fn string_borrow_direct(s: &String) -> &str {
+    s.as_str()
+}
+
+#[no_mangle]
+pub fn string_capacity_trait(s: &String) -> usize {
+    Capacity::capacity(s)
+}
+
+#[no_mangle]
+pub fn string_capacity_direct(s: &String) -> usize {
+    s.capacity()
+}
+
+#[no_mangle]
+pub fn string_with_capacity_trait(n: usize) -> String {
+    WithCapacity::with_capacity(n)
+}
+
+#[no_mangle]
+pub fn string_with_capacity_direct(n: usize) -> String {
+    String::with_capacity(n)
+}
+
+#[no_mangle]
+pub fn bytes_as_slice_trait(v: &Vec<u8>) -> &[u8] {
+    ByteBuf::as_slice(v)
+}
+
+#[no_mangle]
+pub fn bytes_as_slice_direct(v: &Vec<u8>) -> &[u8] {
+    v.as_slice()
+}
+
+#[no_mangle]
+pub fn bytes_truncate_trait(v: &mut Vec<u8>, len: usize) {
+    ByteBuf::truncate(v, len)
+}
+
+#[no_mangle]
+pub fn bytes_truncate_direct(v: &mut Vec<u8>, len: usize) {
+    v.truncate(len)
+}
+
+#[no_mangle]
+pub fn vec_push_trait(v: &mut Vec<u32>, x: u32) {
+    SequenceMut::push(v, x)
+}
+
+#[no_mangle]
+pub fn vec_push_direct(v: &mut Vec<u32>, x: u32) {
+    v.push(x)
+}
+
+#[no_mangle]
+pub fn vec_get_trait(v: &Vec<u32>, index: usize) -> Option<&u32> {
+    Sequence::get(v, index)
+}
+
+#[no_mangle]
+pub fn vec_get_direct(v: &Vec<u32>, index: usize) -> Option<&u32> {
+    v.as_slice().get(index)
+}
+
+#[no_mangle]
+pub fn vec_len_trait(v: &Vec<u32>) -> usize {
+    Sequence::len(v)
+}
+
+#[no_mangle]
+pub fn vec_len_direct(v: &Vec<u32>) -> usize {
+    v.len()
+}
+
+fn new_rcb<R: Rcb<u32>>(x: u32) -> R {
+    R::new(x)
+}
+
+fn clone_rcb<R: Rcb<u32>>(r: &R) -> R {
+    r.clone()
+}
+
+fn try_unwrap_rcb<R: Rcb<u32>>(r: R) -> Result<u32, R> {
+    R::try_unwrap(r)
+}
+
+#[no_mangle]
+pub fn rc_new_trait(x: u32) -> Rc<u32> {
+    new_rcb(x)
+}
+
+#[no_mangle]
+pub fn rc_new_direct(x: u32) -> Rc<u32> {
+    Rc::new(x)
+}
+
+#[no_mangle]
+pub fn rc_clone_trait(r: &Rc<u32>) -> Rc<u32> {
+    clone_rcb(r)
+}
+
+#[no_mangle]
+pub fn rc_clone_direct(r: &Rc<u32>) -> Rc<u32> {
+    Rc::clone(r)
+}
+
+#[no_mangle]
+pub fn rc_try_unwrap_trait(r: Rc<u32>) -> Result<u32, Rc<u32>> {
+    try_unwrap_rcb(r)
+}
+
+#[no_mangle]
+pub fn rc_try_unwrap_direct(r: Rc<u32>) -> Result<u32, Rc<u32>> {
+    Rc::try_unwrap(r)
+}
+
+#[no_mangle]
+pub fn arc_new_trait(x: u32) -> Arc<u32> {
+    new_rcb(x)
+}
+
+#[no_mangle]
+pub fn arc_new_direct(x: u32) -> Arc<u32> {
+    Arc::new(x)
+}
+
+#[no_mangle]
+pub fn arc_clone_trait(r: &Arc<u32>) -> Arc<u32> {
+    clone_rcb(r)
+}
+
+#[no_mangle]
+pub fn arc_clone_direct(r: &Arc<u32>) -> Arc<u32> {
+    Arc::clone(r)
+}
+
+#[no_mangle]
+pub fn arc_try_unwrap_trait(r: Arc<u32>) -> Result<u32, Arc<u32>> {
+    try_unwrap_rcb(r)
+}
+
+#[no_mangle]
+pub fn arc_try_unwrap_direct(r: Arc<u32>) -> Result<u32, Arc<u32>> {
+    Arc::try_unwrap(r)
+}
+"#;
+
+const NAMES: &[&str] = &[
+    "string_as_str",
+    "string_borrow",
+    "string_capacity",
+    "string_with_capacity",
+    "bytes_as_slice",
+    "bytes_truncate",
+    "vec_push",
+    "vec_get",
+    "vec_len",
+    "rc_new",
+    "rc_clone",
+    "rc_try_unwrap",
+    "arc_new",
+    "arc_clone",
+    "arc_try_unwrap",
+];
+
+#[test]
+fn delegation_compiles_to_direct_calls() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("codegen");
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(
+        dir.join("Cargo.toml"),
+        format!(
+            "[package]\nname = \"codegen\"\nversion = \"0.0.0\"\n\
+             edition = \"2018\"\n\n[lib]\npath = \"src/lib.rs\"\n\n\
+             [dependencies]\ngeneric-std = {{ path = {:?} }}\n\n\
+             [workspace]\n",
+            env!("CARGO_MANIFEST_DIR"),
+        ),
+    )
+    .unwrap();
+    fs::write(dir.join("src/lib.rs"), PAIRS).unwrap();
+    // Reuse the resolved versions so the build works offline
+    let lock = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.lock");
+    if lock.exists() {
+        fs::copy(lock, dir.join("Cargo.lock")).unwrap();
+    }
+
+    let status = Command::new(env!("CARGO"))
+        .args(["rustc", "--release", "--lib", "--quiet"])
+        .args(["--", "--emit=asm", "-Ccodegen-units=1"])
+        .current_dir(&dir)
+        .env_remove("RUSTFLAGS")
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let asm_dir = dir.join("target/release/deps");
+    let asm = fs::read_dir(&asm_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|path| {
+            path.extension().is_some_and(|ext| ext == "s")
+                && path
+                    .file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .starts_with("codegen-")
+        })
+        .expect("no assembly output");
+    let bodies = function_bodies(&fs::read_to_string(asm).unwrap());
+
+    for name in NAMES {
+        let via_trait = &bodies[&format!("{}_trait", name)];
+        let direct = &bodies[&format!("{}_direct", name)];
+        assert_eq!(via_trait, direct, "`{}` differs", name);
+    }
+}
+
+/// Normalized instructions of each function, keyed by symbol. Local labels
+/// are renamed in order of appearance, and functions merged into others are
+/// resolved to the body they alias.
+fn function_bodies(asm: &str) -> HashMap<String, Vec<String>> {
+    let mut bodies = HashMap::new();
+    let mut aliases = Vec::new();
+    let mut current: Option<(String, Vec<String>)> = None;
+    for line in asm.lines() {
+        let line = line.trim();
+        let alias = line
+            .strip_prefix(".set ")
+            .and_then(|alias| alias.split_once(','))
+            .or_else(|| line.split_once(" = "));
+        if let Some((name, target)) = alias {
+            aliases.push((name.trim().to_owned(), target.trim().to_owned()));
+        } else if line.starts_with(".Lfunc_end") || line == ".cfi_endproc" {
+            if let Some((name, body)) = current.take() {
+                bodies.insert(name, normalize(&body));
+            }
+        } else if let Some(name) = line.strip_suffix(':') {
+            if !name.starts_with('.') {
+                current = Some((name.to_owned(), Vec::new()));
+            } else if let Some((_, body)) = &mut current {
+                body.push(line.to_owned());
+            }
+        } else if let Some((_, body)) = &mut current {
+            if !line.starts_with('.') && !line.starts_with('#') {
+                body.push(line.to_owned());
+            }
+        }
+    }
+    for (name, target) in aliases {
+        if let Some(body) = bodies.get(&target).cloned() {
+            bodies.insert(name, body);
+        }
+    }
+
+    bodies
+}
+
+fn normalize(body: &[String]) -> Vec<String> {
+    let mut labels = Vec::new();
+    body.iter()
+        .map(|line| {
+            line.split(|c: char| c.is_whitespace() || c == ',')
+                .filter(|token| !token.is_empty())
+                .map(|token| {
+                    let label = token.trim_end_matches(':');
+                    if !label.starts_with(".L") {
+                        return token.to_owned();
+                    }
+                    let index = labels
+                        .iter()
+                        .position(|l| l == label)
+                        .unwrap_or_else(|| {
+                            labels.push(label.to_owned());
+                            labels.len() - 1
+                        });
+
+                    format!(".L{}", index)
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect()
+}
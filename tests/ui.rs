@@ -0,0 +1,29 @@
+//! Diagnostics for common misuses of HKT forms.
+
+use std::{env, process::Command};
+
+// The expected output lists the impls suggested by the compiler, which depend
+// on the enabled features. The suite always runs with this feature set, in a
+// nested build if the current one differs
+const FEATURES: &str = "std collections lock rc sync vec";
+const PINNED: &str = "GENERIC_STD_UI_PINNED";
+
+#[test]
+fn ui() {
+    if env::var_os(PINNED).is_some() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/ui/*.rs");
+
+        return;
+    }
+
+    let status = Command::new(env!("CARGO"))
+        .args(["test", "--test", "ui", "--no-default-features"])
+        .args(["--features", FEATURES])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .env(PINNED, "1")
+        .env("CARGO_TARGET_DIR", env!("CARGO_TARGET_TMPDIR"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
@@ -0,0 +1,13 @@
+use generic_std::plug::PlugType;
+
+fn plug<K>() -> <K as PlugType<usize>>::T
+where
+    K: PlugType<usize>,
+    <K as PlugType<usize>>::T: Default,
+{
+    Default::default()
+}
+
+fn main() {
+    let _ = plug::<Vec<usize>>();
+}
@@ -0,0 +1,28 @@
+use generic_std::{plug::PlugLifetime, Sequence, StreamingIterator};
+
+struct Bytes(Vec<u8>);
+
+impl Sequence<u8> for Bytes {
+    type H1Iterator = std::slice::Iter<'static, u8>;
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn contains(&self, x: &u8) -> bool {
+        self.0.contains(x)
+    }
+
+    fn get(&self, index: usize) -> Option<&u8> {
+        self.0.get(index)
+    }
+
+    fn iter<'a>(&'a self) -> <Self::H1Iterator as PlugLifetime<'a>>::T
+    where
+        <Self::H1Iterator as PlugLifetime<'a>>::T: StreamingIterator,
+    {
+        unimplemented!()
+    }
+}
+
+fn main() {}
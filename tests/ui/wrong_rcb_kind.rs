@@ -0,0 +1,14 @@
+use generic_std::{vec::H1Vec, RcbKind};
+
+struct Shared<K>
+where
+    K: RcbKind<String>,
+{
+    name: K::Rcb,
+}
+
+fn main() {
+    let _ = Shared::<H1Vec> {
+        name: vec!["xpotato".to_string()],
+    };
+}